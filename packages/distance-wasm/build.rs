@@ -0,0 +1,18 @@
+fn main() {
+    // Linker flags for loading the library as a Node.js addon
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    // C header for the `ffi` feature, checked against the committed copy in
+    // include/ by a test; `pnpm header:ffi` regenerates that copy
+    #[cfg(feature = "ffi")]
+    {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out = std::env::var("OUT_DIR").unwrap();
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        cbindgen::generate(&dir)
+            .expect("generating the C header")
+            .write_to_file(format!("{}/nlptools_distance.h", out));
+    }
+}
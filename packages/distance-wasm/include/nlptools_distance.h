@@ -0,0 +1,789 @@
+/* String distance, similarity and search indexes - see src/ffi.rs */
+
+#ifndef NLPTOOLS_DISTANCE_H
+#define NLPTOOLS_DISTANCE_H
+
+/* Generated by cbindgen from src/ffi.rs with `pnpm header:ffi`; do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result code of every exported function
+ */
+typedef enum {
+  NLP_STATUS_OK = 0,
+  /**
+   * A required pointer was null
+   */
+  NLP_STATUS_NULL_POINTER = 1,
+  /**
+   * A string was not valid UTF-8
+   */
+  NLP_STATUS_INVALID_UTF8 = 2,
+  /**
+   * An argument was rejected, see nlp_last_error()
+   */
+  NLP_STATUS_INVALID_ARGUMENT = 3,
+  /**
+   * The output buffer was too small; the required length was still written
+   */
+  NLP_STATUS_BUFFER_TOO_SMALL = 4,
+  /**
+   * The call panicked; handles passed to it should only be freed
+   */
+  NLP_STATUS_PANIC = 5,
+} NlpStatus;
+
+typedef struct NlpBkTree NlpBkTree;
+
+typedef struct NlpBm25Index NlpBm25Index;
+
+typedef struct NlpHnswIndex NlpHnswIndex;
+
+typedef struct NlpNgramIndex NlpNgramIndex;
+
+typedef struct NlpQuantizedStore NlpQuantizedStore;
+
+typedef struct NlpSoftTfIdf NlpSoftTfIdf;
+
+typedef struct NlpSymSpell NlpSymSpell;
+
+typedef struct NlpTermDictionary NlpTermDictionary;
+
+typedef struct NlpTfIdfModel NlpTfIdfModel;
+
+typedef struct NlpVpTree NlpVpTree;
+
+/**
+ * UTF-8 string slice, used for arrays of strings
+ */
+typedef struct {
+  const char *ptr;
+  size_t len;
+} NlpStr;
+
+/**
+ * Search result: an id with its score or distance, as documented per index
+ */
+typedef struct {
+  uint32_t id;
+  double score;
+} NlpHit;
+
+/**
+ * Matched term and its edit distance; `term` points into memory owned by the
+ * handle, valid until the handle is next searched or released
+ */
+typedef struct {
+  NlpStr term;
+  uint32_t distance;
+} NlpTermMatch;
+
+/**
+ * Spelling suggestion; `term` is owned by the handle like `NlpTermMatch::term`
+ */
+typedef struct {
+  NlpStr term;
+  uint32_t distance;
+  double count;
+} NlpSuggestion;
+
+/**
+ * Space-separated words as typed and as corrected, owned by the handle
+ */
+typedef struct {
+  NlpStr segmented;
+  NlpStr corrected;
+  uint32_t distance_sum;
+  double log_probability_sum;
+} NlpSegmentation;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * NUL-terminated message of the last failed call on this thread, valid until
+ * the next failed call; its length without the NUL is written to `len` unless null
+ */
+const char *nlp_last_error(size_t *len);
+
+NlpStatus nlp_levenshtein(const char *s1,
+                          size_t s1_len,
+                          const char *s2,
+                          size_t s2_len,
+                          const char *preprocessor,
+                          size_t preprocessor_len,
+                          uint32_t *out);
+
+NlpStatus nlp_levenshtein_normalized(const char *s1,
+                                     size_t s1_len,
+                                     const char *s2,
+                                     size_t s2_len,
+                                     const char *preprocessor,
+                                     size_t preprocessor_len,
+                                     double *out);
+
+NlpStatus nlp_damerau_levenshtein(const char *s1,
+                                  size_t s1_len,
+                                  const char *s2,
+                                  size_t s2_len,
+                                  const char *preprocessor,
+                                  size_t preprocessor_len,
+                                  uint32_t *out);
+
+NlpStatus nlp_damerau_levenshtein_normalized(const char *s1,
+                                             size_t s1_len,
+                                             const char *s2,
+                                             size_t s2_len,
+                                             const char *preprocessor,
+                                             size_t preprocessor_len,
+                                             double *out);
+
+NlpStatus nlp_jaro(const char *s1,
+                   size_t s1_len,
+                   const char *s2,
+                   size_t s2_len,
+                   const char *preprocessor,
+                   size_t preprocessor_len,
+                   double *out);
+
+NlpStatus nlp_jarowinkler(const char *s1,
+                          size_t s1_len,
+                          const char *s2,
+                          size_t s2_len,
+                          const char *preprocessor,
+                          size_t preprocessor_len,
+                          double *out);
+
+NlpStatus nlp_hamming(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      uint32_t *out);
+
+NlpStatus nlp_hamming_normalized(const char *s1,
+                                 size_t s1_len,
+                                 const char *s2,
+                                 size_t s2_len,
+                                 const char *preprocessor,
+                                 size_t preprocessor_len,
+                                 double *out);
+
+NlpStatus nlp_sift4_simple(const char *s1,
+                           size_t s1_len,
+                           const char *s2,
+                           size_t s2_len,
+                           const char *preprocessor,
+                           size_t preprocessor_len,
+                           uint32_t *out);
+
+NlpStatus nlp_sift4_simple_normalized(const char *s1,
+                                      size_t s1_len,
+                                      const char *s2,
+                                      size_t s2_len,
+                                      const char *preprocessor,
+                                      size_t preprocessor_len,
+                                      double *out);
+
+NlpStatus nlp_myers_levenshtein(const char *s1,
+                                size_t s1_len,
+                                const char *s2,
+                                size_t s2_len,
+                                const char *preprocessor,
+                                size_t preprocessor_len,
+                                uint32_t *out);
+
+NlpStatus nlp_myers_levenshtein_normalized(const char *s1,
+                                           size_t s1_len,
+                                           const char *s2,
+                                           size_t s2_len,
+                                           const char *preprocessor,
+                                           size_t preprocessor_len,
+                                           double *out);
+
+NlpStatus nlp_lcs_seq(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      uint32_t *out);
+
+NlpStatus nlp_lcs_seq_normalized(const char *s1,
+                                 size_t s1_len,
+                                 const char *s2,
+                                 size_t s2_len,
+                                 const char *preprocessor,
+                                 size_t preprocessor_len,
+                                 double *out);
+
+NlpStatus nlp_lcs_str(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      uint32_t *out);
+
+NlpStatus nlp_lcs_str_normalized(const char *s1,
+                                 size_t s1_len,
+                                 const char *s2,
+                                 size_t s2_len,
+                                 const char *preprocessor,
+                                 size_t preprocessor_len,
+                                 double *out);
+
+NlpStatus nlp_ratcliff_obershelp(const char *s1,
+                                 size_t s1_len,
+                                 const char *s2,
+                                 size_t s2_len,
+                                 const char *preprocessor,
+                                 size_t preprocessor_len,
+                                 double *out);
+
+NlpStatus nlp_jaccard(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      double *out);
+
+NlpStatus nlp_cosine(const char *s1,
+                     size_t s1_len,
+                     const char *s2,
+                     size_t s2_len,
+                     const char *preprocessor,
+                     size_t preprocessor_len,
+                     double *out);
+
+NlpStatus nlp_sorensen(const char *s1,
+                       size_t s1_len,
+                       const char *s2,
+                       size_t s2_len,
+                       const char *preprocessor,
+                       size_t preprocessor_len,
+                       double *out);
+
+NlpStatus nlp_tversky(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      double *out);
+
+NlpStatus nlp_smith_waterman(const char *s1,
+                             size_t s1_len,
+                             const char *s2,
+                             size_t s2_len,
+                             const char *preprocessor,
+                             size_t preprocessor_len,
+                             uint32_t *out);
+
+NlpStatus nlp_smith_waterman_normalized(const char *s1,
+                                        size_t s1_len,
+                                        const char *s2,
+                                        size_t s2_len,
+                                        const char *preprocessor,
+                                        size_t preprocessor_len,
+                                        double *out);
+
+NlpStatus nlp_overlap(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      double *out);
+
+NlpStatus nlp_ratio(const char *s1,
+                    size_t s1_len,
+                    const char *s2,
+                    size_t s2_len,
+                    const char *preprocessor,
+                    size_t preprocessor_len,
+                    double *out);
+
+NlpStatus nlp_partial_ratio(const char *s1,
+                            size_t s1_len,
+                            const char *s2,
+                            size_t s2_len,
+                            const char *preprocessor,
+                            size_t preprocessor_len,
+                            double *out);
+
+NlpStatus nlp_token_sort_ratio(const char *s1,
+                               size_t s1_len,
+                               const char *s2,
+                               size_t s2_len,
+                               const char *preprocessor,
+                               size_t preprocessor_len,
+                               double *out);
+
+NlpStatus nlp_token_set_ratio(const char *s1,
+                              size_t s1_len,
+                              const char *s2,
+                              size_t s2_len,
+                              const char *preprocessor,
+                              size_t preprocessor_len,
+                              double *out);
+
+NlpStatus nlp_partial_token_sort_ratio(const char *s1,
+                                       size_t s1_len,
+                                       const char *s2,
+                                       size_t s2_len,
+                                       const char *preprocessor,
+                                       size_t preprocessor_len,
+                                       double *out);
+
+NlpStatus nlp_wratio(const char *s1,
+                     size_t s1_len,
+                     const char *s2,
+                     size_t s2_len,
+                     const char *preprocessor,
+                     size_t preprocessor_len,
+                     double *out);
+
+NlpStatus nlp_prefix(const char *s1,
+                     size_t s1_len,
+                     const char *s2,
+                     size_t s2_len,
+                     const char *preprocessor,
+                     size_t preprocessor_len,
+                     double *out);
+
+NlpStatus nlp_suffix(const char *s1,
+                     size_t s1_len,
+                     const char *s2,
+                     size_t s2_len,
+                     const char *preprocessor,
+                     size_t preprocessor_len,
+                     double *out);
+
+NlpStatus nlp_length(const char *s1,
+                     size_t s1_len,
+                     const char *s2,
+                     size_t s2_len,
+                     const char *preprocessor,
+                     size_t preprocessor_len,
+                     double *out);
+
+NlpStatus nlp_jaccard_bigram(const char *s1,
+                             size_t s1_len,
+                             const char *s2,
+                             size_t s2_len,
+                             const char *preprocessor,
+                             size_t preprocessor_len,
+                             double *out);
+
+NlpStatus nlp_cosine_bigram(const char *s1,
+                            size_t s1_len,
+                            const char *s2,
+                            size_t s2_len,
+                            const char *preprocessor,
+                            size_t preprocessor_len,
+                            double *out);
+
+/**
+ * Similarity by algorithm name, as compare() in the wasm build
+ */
+NlpStatus nlp_compare(const char *s1,
+                      size_t s1_len,
+                      const char *s2,
+                      size_t s2_len,
+                      const char *algorithm,
+                      size_t algorithm_len,
+                      const char *preprocessor,
+                      size_t preprocessor_len,
+                      double *out);
+
+/**
+ * Score every query against every choice into `scores`, which holds `capacity`
+ * values; `query_count * choice_count` scores are written row-major by query
+ */
+NlpStatus nlp_cdist(const NlpStr *queries,
+                    size_t query_count,
+                    const NlpStr *choices,
+                    size_t choice_count,
+                    const char *algorithm,
+                    size_t algorithm_len,
+                    const char *preprocessor,
+                    size_t preprocessor_len,
+                    double *scores,
+                    size_t capacity,
+                    size_t *count);
+
+/**
+ * Fit IDF weights on `corpus`; `inner` defaults to "jaro_winkler" and a NAN
+ * `threshold` to 0.9
+ */
+NlpStatus nlp_soft_tfidf_new(const NlpStr *corpus,
+                             size_t corpus_count,
+                             const char *inner,
+                             size_t inner_len,
+                             double threshold,
+                             const char *preprocessor,
+                             size_t preprocessor_len,
+                             NlpSoftTfIdf **out);
+
+void nlp_soft_tfidf_free(NlpSoftTfIdf *model);
+
+NlpStatus nlp_soft_tfidf_similarity(const NlpSoftTfIdf *model,
+                                    const char *s1,
+                                    size_t s1_len,
+                                    const char *s2,
+                                    size_t s2_len,
+                                    double *out);
+
+/**
+ * `analyzer` is "word" (default) or "char"; zero n-gram bounds take single words
+ * or character trigrams
+ */
+NlpStatus nlp_tfidf_new(const NlpStr *corpus,
+                        size_t corpus_count,
+                        const char *analyzer,
+                        size_t analyzer_len,
+                        uint32_t ngram_min,
+                        uint32_t ngram_max,
+                        bool sublinear_tf,
+                        bool smooth_idf,
+                        const char *preprocessor,
+                        size_t preprocessor_len,
+                        NlpTfIdfModel **out);
+
+void nlp_tfidf_free(NlpTfIdfModel *model);
+
+/**
+ * Cosine similarity of the TF-IDF vectors of two texts
+ */
+NlpStatus nlp_tfidf_similarity(const NlpTfIdfModel *model,
+                               const char *s1,
+                               size_t s1_len,
+                               const char *s2,
+                               size_t s2_len,
+                               double *out);
+
+/**
+ * Non-zero entries of the unit length TF-IDF vector of `text`
+ */
+NlpStatus nlp_tfidf_vectorize(const NlpTfIdfModel *model,
+                              const char *document,
+                              size_t document_len,
+                              NlpHit *hits,
+                              size_t capacity,
+                              size_t *count);
+
+NlpStatus nlp_tfidf_vocabulary_size(const NlpTfIdfModel *model, size_t *size);
+
+/**
+ * Serialize the model as UTF-8 JSON into `buffer`, without a NUL terminator,
+ * writing the full length to `len`
+ */
+NlpStatus nlp_tfidf_serialize(const NlpTfIdfModel *model,
+                              char *buffer,
+                              size_t capacity,
+                              size_t *len);
+
+NlpStatus nlp_tfidf_deserialize(const char *json, size_t json_len, NlpTfIdfModel **out);
+
+/**
+ * `variant` is "bm25" (default), "bm25+" or "bm25l" and `analyzer` is "word"
+ * (default) or "char". NAN `k1`, `b` and `delta` take 1.2, 0.75 and
+ * the variant's delta; zero n-gram bounds take single words or character trigrams
+ */
+NlpStatus nlp_bm25_new(const char *variant,
+                       size_t variant_len,
+                       double k1,
+                       double b,
+                       double delta,
+                       const char *analyzer,
+                       size_t analyzer_len,
+                       uint32_t ngram_min,
+                       uint32_t ngram_max,
+                       const char *preprocessor,
+                       size_t preprocessor_len,
+                       NlpBm25Index **out);
+
+void nlp_bm25_free(NlpBm25Index *index);
+
+/**
+ * Index a document, writing its id to `id`
+ */
+NlpStatus nlp_bm25_add(NlpBm25Index *index,
+                       const char *document,
+                       size_t document_len,
+                       uint32_t *id);
+
+/**
+ * Remove a document, writing whether it was indexed to `removed`
+ */
+NlpStatus nlp_bm25_remove(NlpBm25Index *index, uint32_t id, bool *removed);
+
+NlpStatus nlp_bm25_size(const NlpBm25Index *index, size_t *size);
+
+/**
+ * Top `k` documents by descending score into `hits`, which holds `capacity` hits
+ */
+NlpStatus nlp_bm25_query(const NlpBm25Index *index,
+                         const char *query,
+                         size_t query_len,
+                         uint32_t k,
+                         NlpHit *hits,
+                         size_t capacity,
+                         size_t *count);
+
+/**
+ * `q` of 0 takes the default of 2
+ */
+NlpStatus nlp_ngram_new(uint32_t q, NlpNgramIndex **out);
+
+void nlp_ngram_free(NlpNgramIndex *index);
+
+/**
+ * Index a string, writing its id to `id`
+ */
+NlpStatus nlp_ngram_insert(NlpNgramIndex *index, const char *term, size_t term_len, uint32_t *id);
+
+NlpStatus nlp_ngram_delete(NlpNgramIndex *index, uint32_t id, bool *deleted);
+
+/**
+ * Every string within edit distance `k`, closest first
+ */
+NlpStatus nlp_ngram_search(const NlpNgramIndex *index,
+                           const char *query,
+                           size_t query_len,
+                           uint32_t k,
+                           NlpHit *hits,
+                           size_t capacity,
+                           size_t *count);
+
+/**
+ * `metric` is "levenshtein" (default), "damerau_levenshtein", "hamming" or "myers"
+ */
+NlpStatus nlp_bktree_new(const char *metric, size_t metric_len, NlpBkTree **out);
+
+void nlp_bktree_free(NlpBkTree *tree);
+
+/**
+ * Insert a term, writing false to `inserted` if it was already present
+ */
+NlpStatus nlp_bktree_insert(NlpBkTree *tree, const char *term, size_t term_len, bool *inserted);
+
+NlpStatus nlp_bktree_size(const NlpBkTree *tree, size_t *size);
+
+/**
+ * All terms within distance `k`, closest first
+ */
+NlpStatus nlp_bktree_within(const NlpBkTree *tree,
+                            const char *query,
+                            size_t query_len,
+                            uint32_t k,
+                            NlpTermMatch *matches,
+                            size_t capacity,
+                            size_t *count);
+
+/**
+ * The `n` closest terms, closest first
+ */
+NlpStatus nlp_bktree_nearest(const NlpBkTree *tree,
+                             const char *query,
+                             size_t query_len,
+                             uint32_t n,
+                             NlpTermMatch *matches,
+                             size_t capacity,
+                             size_t *count);
+
+NlpStatus nlp_termdict_new(const NlpStr *terms, size_t term_count, NlpTermDictionary **out);
+
+void nlp_termdict_free(NlpTermDictionary *dictionary);
+
+NlpStatus nlp_termdict_size(const NlpTermDictionary *dictionary, size_t *size);
+
+NlpStatus nlp_termdict_contains(const NlpTermDictionary *dictionary,
+                                const char *term,
+                                size_t term_len,
+                                bool *found);
+
+/**
+ * All terms within distance `k` (at most 3) in lexicographic order; with
+ * `transpositions` an adjacent swap counts as one edit
+ */
+NlpStatus nlp_termdict_fuzzy_search(const NlpTermDictionary *dictionary,
+                                    const char *query,
+                                    size_t query_len,
+                                    uint32_t k,
+                                    bool transpositions,
+                                    NlpTermMatch *matches,
+                                    size_t capacity,
+                                    size_t *count);
+
+/**
+ * `metric` is "yujian_bo" (default), "jaccard" or "jaccard_bigram"
+ */
+NlpStatus nlp_vptree_new(const NlpStr *items,
+                         size_t item_count,
+                         const char *metric,
+                         size_t metric_len,
+                         NlpVpTree **out);
+
+void nlp_vptree_free(NlpVpTree *tree);
+
+/**
+ * The `n` closest items, closest first
+ */
+NlpStatus nlp_vptree_nearest(const NlpVpTree *tree,
+                             const char *query,
+                             size_t query_len,
+                             uint32_t n,
+                             NlpHit *hits,
+                             size_t capacity,
+                             size_t *count);
+
+/**
+ * All items within distance `radius`, closest first
+ */
+NlpStatus nlp_vptree_within(const NlpVpTree *tree,
+                            const char *query,
+                            size_t query_len,
+                            double radius,
+                            NlpHit *hits,
+                            size_t capacity,
+                            size_t *count);
+
+/**
+ * `metric` is "cosine" (default), "l2" or "ip"; zero `m`, `ef_construction`
+ * and `ef_search` take the defaults of 16, 200 and 50
+ */
+NlpStatus nlp_hnsw_new(size_t dim,
+                       const char *metric,
+                       size_t metric_len,
+                       uint32_t m,
+                       uint32_t ef_construction,
+                       uint32_t ef_search,
+                       NlpHnswIndex **out);
+
+void nlp_hnsw_free(NlpHnswIndex *index);
+
+NlpStatus nlp_hnsw_insert(NlpHnswIndex *index, uint32_t id, const float *vector, size_t vector_len);
+
+NlpStatus nlp_hnsw_delete(NlpHnswIndex *index, uint32_t id, bool *deleted);
+
+NlpStatus nlp_hnsw_size(const NlpHnswIndex *index, size_t *size);
+
+/**
+ * The `k` approximate nearest vectors, closest first
+ */
+NlpStatus nlp_hnsw_search(const NlpHnswIndex *index,
+                          const float *query,
+                          size_t query_len,
+                          uint32_t k,
+                          NlpHit *hits,
+                          size_t capacity,
+                          size_t *count);
+
+/**
+ * Serialize the index into `buffer`, writing the full size to `len`
+ */
+NlpStatus nlp_hnsw_serialize(const NlpHnswIndex *index,
+                             uint8_t *buffer,
+                             size_t capacity,
+                             size_t *len);
+
+NlpStatus nlp_hnsw_deserialize(const uint8_t *bytes, size_t bytes_len, NlpHnswIndex **out);
+
+/**
+ * `metric` is "cosine" (default), "l2" or "ip"
+ */
+NlpStatus nlp_quantized_new(size_t dim,
+                            const char *metric,
+                            size_t metric_len,
+                            NlpQuantizedStore **out);
+
+void nlp_quantized_free(NlpQuantizedStore *store);
+
+/**
+ * Insert a vector, replacing any vector stored under the same id
+ */
+NlpStatus nlp_quantized_insert(NlpQuantizedStore *store,
+                               uint32_t id,
+                               const float *vector,
+                               size_t vector_len);
+
+NlpStatus nlp_quantized_delete(NlpQuantizedStore *store, uint32_t id, bool *deleted);
+
+/**
+ * The `k` closest vectors by asymmetric int8 distance; a non-zero `oversample`
+ * shortlists `k * oversample` vectors by their sign bits first
+ */
+NlpStatus nlp_quantized_search(const NlpQuantizedStore *store,
+                               const float *query,
+                               size_t query_len,
+                               uint32_t k,
+                               uint32_t oversample,
+                               NlpHit *hits,
+                               size_t capacity,
+                               size_t *count);
+
+/**
+ * Zero `max_edit_distance` and `prefix_length` take the defaults of 2 and 7
+ */
+NlpStatus nlp_symspell_new(uint32_t max_edit_distance, uint32_t prefix_length, NlpSymSpell **out);
+
+void nlp_symspell_free(NlpSymSpell *index);
+
+/**
+ * Add a word or increase its frequency by `count`
+ */
+NlpStatus nlp_symspell_add_word(NlpSymSpell *index,
+                                const char *word,
+                                size_t word_len,
+                                double count);
+
+/**
+ * Load "word count" lines, writing the number of words loaded to `loaded`
+ */
+NlpStatus nlp_symspell_load_dictionary(NlpSymSpell *index,
+                                       const char *dictionary,
+                                       size_t dictionary_len,
+                                       uint32_t *loaded);
+
+NlpStatus nlp_symspell_size(const NlpSymSpell *index, size_t *size);
+
+/**
+ * `verbosity` is "top" (default), "closest" or "all"; a negative
+ * `max_edit_distance` takes the index's. Unknown verbosities return
+ * `NLP_STATUS_INVALID_ARGUMENT`
+ */
+NlpStatus nlp_symspell_lookup(const NlpSymSpell *index,
+                              const char *input,
+                              size_t input_len,
+                              const char *verbosity,
+                              size_t verbosity_len,
+                              int32_t max_edit_distance,
+                              NlpSuggestion *suggestions,
+                              size_t capacity,
+                              size_t *count);
+
+/**
+ * Split text without spaces into its most probable words; a non-zero
+ * `max_edit_distance` also corrects each word
+ */
+NlpStatus nlp_symspell_word_segmentation(const NlpSymSpell *index,
+                                         const char *input,
+                                         size_t input_len,
+                                         uint32_t max_edit_distance,
+                                         NlpSegmentation *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NLPTOOLS_DISTANCE_H */
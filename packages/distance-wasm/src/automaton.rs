@@ -0,0 +1,151 @@
+// Levenshtein automata - fuzzy term expansion over a sorted dictionary
+// A Schulz-Mihov parametric DFA accepting every string within distance k of
+// the query is walked in lockstep with an FST of the terms, so only the
+// branches the automaton can still accept are ever visited
+
+use std::cell::OnceCell;
+
+use fst::{IntoStreamer, Set, Streamer};
+use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder};
+
+/// Largest supported edit distance; automaton construction grows quickly beyond it
+pub const MAX_DISTANCE: u8 = 3;
+
+/// Sorted set of terms stored as a finite state transducer
+pub struct TermSet {
+    set: Set<Vec<u8>>,
+    /// Builders indexed by distance, without and with transpositions
+    builders: [[OnceCell<LevenshteinAutomatonBuilder>; 2]; MAX_DISTANCE as usize + 1],
+}
+
+impl TermSet {
+    /// Build the set from terms in any order; duplicates are removed
+    pub fn new<S: AsRef<str>>(terms: &[S]) -> TermSet {
+        let mut terms: Vec<&str> = terms.iter().map(|t| t.as_ref()).collect();
+        terms.sort_unstable();
+        terms.dedup();
+
+        TermSet {
+            set: Set::from_iter(terms).expect("terms are sorted and deduplicated"),
+            builders: Default::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn contains(&self, term: &str) -> bool {
+        self.set.contains(term)
+    }
+
+    /// Every term within distance `k` of the query, in lexicographic order
+    ///
+    /// With `transpositions` an adjacent swap counts as a single edit (optimal
+    /// string alignment); distances are counted in Unicode scalar values.
+    pub fn fuzzy(
+        &self,
+        query: &str,
+        k: u8,
+        transpositions: bool,
+    ) -> Result<Vec<(String, u8)>, String> {
+        if k > MAX_DISTANCE {
+            return Err(format!(
+                "edit distance {} exceeds the maximum of {}",
+                k, MAX_DISTANCE
+            ));
+        }
+
+        let builder = self.builders[k as usize][transpositions as usize]
+            .get_or_init(|| LevenshteinAutomatonBuilder::new(k, transpositions));
+        let dfa = builder.build_dfa(query);
+
+        let mut matches = Vec::new();
+        let mut stream = self.set.search_with_state(&dfa).into_stream();
+        while let Some((term, state)) = stream.next() {
+            if let Distance::Exact(d) = dfa.distance(state) {
+                matches.push((String::from_utf8_lossy(term).into_owned(), d));
+            }
+        }
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TERMS: [&str; 12] = [
+        "apple", "apply", "ape", "maple", "applet", "appel", "banana", "bandana", "cafe", "café",
+        "apple", "",
+    ];
+
+    #[test]
+    fn test_term_set() {
+        let set = TermSet::new(&TERMS);
+        assert_eq!(set.len(), 11);
+        assert!(set.contains("café"));
+        assert!(!set.contains("caf"));
+    }
+
+    #[test]
+    fn test_fuzzy_lexicographic() {
+        let set = TermSet::new(&TERMS);
+        assert_eq!(
+            set.fuzzy("apple", 1, false).unwrap(),
+            vec![
+                ("apple".to_string(), 0),
+                ("applet".to_string(), 1),
+                ("apply".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            set.fuzzy("banana", 0, false).unwrap(),
+            vec![("banana".to_string(), 0)]
+        );
+    }
+
+    #[test]
+    fn test_transpositions() {
+        let set = TermSet::new(&TERMS);
+        let without = set.fuzzy("appel", 1, false).unwrap();
+        let with = set.fuzzy("appel", 1, true).unwrap();
+        assert!(!without.iter().any(|(t, _)| t == "apple"));
+        assert!(with.contains(&("apple".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_unicode_distance() {
+        // "é" is one edit away from "e", not two bytes
+        let set = TermSet::new(&TERMS);
+        assert_eq!(
+            set.fuzzy("cafe", 1, false).unwrap(),
+            vec![("cafe".to_string(), 0), ("café".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let set = TermSet::new(&TERMS);
+        for query in ["aple", "bnana", "", "xyz", "mapple"].iter() {
+            for k in 0..=MAX_DISTANCE {
+                let mut expected: Vec<(String, u8)> = TERMS
+                    .iter()
+                    .map(|t| (t.to_string(), textdistance::str::levenshtein(query, t)))
+                    .filter(|&(_, d)| d <= k as usize)
+                    .map(|(t, d)| (t, d as u8))
+                    .collect();
+                expected.sort();
+                expected.dedup();
+                assert_eq!(
+                    set.fuzzy(query, k, false).unwrap(),
+                    expected,
+                    "{} {}",
+                    query,
+                    k
+                );
+            }
+        }
+        assert!(set.fuzzy("apple", 4, false).is_err());
+    }
+}
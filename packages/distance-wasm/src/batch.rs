@@ -0,0 +1,281 @@
+// Batch comparison - score many strings against many others in one call
+// Avoids a JavaScript to WebAssembly round trip per pair. With the `parallel`
+// feature the independent scores are spread over the rayon thread pool; every
+// result is collected in input order, so the output matches the serial path
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Pairwise scorer, shared across threads when the `parallel` feature is on
+pub type Scorer<'a> = &'a (dyn Fn(&str, &str) -> f64 + Sync);
+
+/// Apply `f` to every item in order, on the rayon thread pool when `parallel` is set
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+fn map<T: Sync, R: Send>(
+    items: &[T],
+    parallel: bool,
+    f: impl Fn(usize, &T) -> R + Sync + Send,
+) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return items
+            .par_iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect();
+    }
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+/// Position of the first item matching `f`, searched on the rayon thread pool when `parallel` is set
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+fn position<T: Sync>(
+    items: &[T],
+    parallel: bool,
+    f: impl Fn(&T) -> bool + Sync + Send,
+) -> Option<usize> {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return items.par_iter().position_first(f);
+    }
+    items.iter().position(f)
+}
+
+/// Score every query against every choice, row-major by query
+pub fn cdist<S: AsRef<str> + Sync>(queries: &[S], choices: &[S], scorer: Scorer) -> Vec<f64> {
+    cdist_with(queries, choices, scorer, cfg!(feature = "parallel"))
+}
+
+pub(crate) fn cdist_with<S: AsRef<str> + Sync>(
+    queries: &[S],
+    choices: &[S],
+    scorer: Scorer,
+    parallel: bool,
+) -> Vec<f64> {
+    let rows = map(queries, parallel, |_, query| -> Vec<f64> {
+        choices
+            .iter()
+            .map(|choice| scorer(query.as_ref(), choice.as_ref()))
+            .collect()
+    });
+    rows.concat()
+}
+
+/// Choices scoring at least `score_cutoff` against the query as `(index, score)`,
+/// best first with ties broken by index, truncated to `limit`
+pub fn extract<S: AsRef<str> + Sync>(
+    query: &str,
+    choices: &[S],
+    scorer: Scorer,
+    limit: Option<usize>,
+    score_cutoff: f64,
+) -> Vec<(usize, f64)> {
+    extract_with(
+        query,
+        choices,
+        scorer,
+        limit,
+        score_cutoff,
+        cfg!(feature = "parallel"),
+    )
+}
+
+pub(crate) fn extract_with<S: AsRef<str> + Sync>(
+    query: &str,
+    choices: &[S],
+    scorer: Scorer,
+    limit: Option<usize>,
+    score_cutoff: f64,
+    parallel: bool,
+) -> Vec<(usize, f64)> {
+    let scores = map(choices, parallel, |index, choice| {
+        (index, scorer(query, choice.as_ref()))
+    });
+
+    let mut matches: Vec<(usize, f64)> = scores
+        .into_iter()
+        .filter(|&(_, score)| score >= score_cutoff)
+        .collect();
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    if let Some(limit) = limit {
+        matches.truncate(limit);
+    }
+    matches
+}
+
+/// Group near-duplicates, returning the index of each item's representative
+///
+/// Items are visited in order; an item joins the first earlier representative
+/// it scores at least `threshold` against, or becomes a representative itself.
+pub fn dedupe<S: AsRef<str> + Sync>(items: &[S], scorer: Scorer, threshold: f64) -> Vec<usize> {
+    dedupe_with(items, scorer, threshold, cfg!(feature = "parallel"))
+}
+
+pub(crate) fn dedupe_with<S: AsRef<str> + Sync>(
+    items: &[S],
+    scorer: Scorer,
+    threshold: f64,
+    parallel: bool,
+) -> Vec<usize> {
+    let mut representatives: Vec<usize> = Vec::new();
+    let mut groups = Vec::with_capacity(items.len());
+
+    for (index, item) in items.iter().enumerate() {
+        let found = position(&representatives, parallel, |&representative| {
+            scorer(item.as_ref(), items[representative].as_ref()) >= threshold
+        });
+
+        match found {
+            Some(position) => groups.push(representatives[position]),
+            None => {
+                representatives.push(index);
+                groups.push(index);
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefix_scorer(a: &str, b: &str) -> f64 {
+        textdistance::nstr::prefix(a, b)
+    }
+
+    #[test]
+    fn test_cdist() {
+        let scorer = |a: &str, b: &str| (a == b) as u8 as f64;
+        assert_eq!(
+            cdist(&["a", "b"], &["a", "b", "c"], &scorer),
+            vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        );
+        assert!(cdist::<&str>(&[], &["a"], &scorer).is_empty());
+    }
+
+    #[test]
+    fn test_cdist_matches_pairwise() {
+        let words: Vec<String> = (0..60).map(|i| format!("word{}", i * 7 % 23)).collect();
+        let scores = cdist(&words[..20], &words, &prefix_scorer);
+        for (i, query) in words[..20].iter().enumerate() {
+            for (j, choice) in words.iter().enumerate() {
+                assert_eq!(scores[i * words.len() + j], prefix_scorer(query, choice));
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract() {
+        let choices = ["apple", "apply", "banana", "app", "apple"];
+        let scorer = |a: &str, b: &str| 1.0 - textdistance::nstr::levenshtein(a, b);
+        let matches = extract("apple", &choices, &scorer, Some(3), 0.5);
+        assert_eq!(matches[0], (0, 1.0));
+        assert_eq!(matches[1], (4, 1.0));
+        assert_eq!(matches[2].0, 1);
+
+        let all = extract("apple", &choices, &scorer, None, 0.0);
+        assert_eq!(all.len(), choices.len());
+        assert!(extract("apple", &choices, &scorer, None, 1.1).is_empty());
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let items = ["apple", "banana", "apples", "bananas", "cherry", "apple"];
+        let scorer = |a: &str, b: &str| 1.0 - textdistance::nstr::levenshtein(a, b);
+        assert_eq!(dedupe(&items, &scorer, 0.8), vec![0, 1, 0, 1, 4, 0]);
+        assert_eq!(dedupe(&items, &scorer, 1.1), vec![0, 1, 2, 3, 4, 5]);
+        assert!(dedupe::<&str>(&[], &scorer, 0.5).is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        // Enough work for rayon to split, with many tied scores to order
+        let words: Vec<String> = (0..400)
+            .map(|i| format!("{}{}", ["apple", "apply", "maple", "ample"][i % 4], i % 37))
+            .collect();
+        let scorer = |a: &str, b: &str| crate::similarity_by_name(a, b, "levenshtein");
+
+        assert_eq!(
+            cdist_with(&words[..50], &words, &scorer, true),
+            cdist_with(&words[..50], &words, &scorer, false)
+        );
+        for limit in [None, Some(25)] {
+            assert_eq!(
+                extract_with("apple7", &words, &scorer, limit, 0.5, true),
+                extract_with("apple7", &words, &scorer, limit, 0.5, false)
+            );
+        }
+        for threshold in [0.6, 0.8, 1.0] {
+            assert_eq!(
+                dedupe_with(&words, &scorer, threshold, true),
+                dedupe_with(&words, &scorer, threshold, false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_distance_algorithms_rank_as_similarities() {
+        let choices = vec![
+            "banana".to_string(),
+            "apple".to_string(),
+            "apply".to_string(),
+        ];
+        for algorithm in [
+            "levenshtein",
+            "hamming",
+            "damerau_levenshtein",
+            "sift4",
+            "unknown",
+        ] {
+            let hits = crate::extract("apple", choices.clone(), algorithm, Some(2), None, None)
+                .ok()
+                .unwrap();
+            assert_eq!(hits[0].id, 1, "{}", algorithm);
+            assert_eq!(hits[0].score, 1.0, "{}", algorithm);
+            assert_eq!(hits[1].id, 2, "{}", algorithm);
+            assert_eq!(hits[1].score, 0.8, "{}", algorithm);
+
+            let scores = crate::cdist(vec!["apple".to_string()], choices.clone(), algorithm, None)
+                .ok()
+                .unwrap();
+            assert_eq!(scores[1], 1.0, "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_dedupe_with_distance_algorithms() {
+        let items: Vec<String> = ["apple", "banana", "apply", "cherry", "Apple"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        for algorithm in ["levenshtein", "hamming"] {
+            let groups = crate::dedupe(items.clone(), algorithm, 0.8, None)
+                .ok()
+                .unwrap();
+            assert_eq!(groups, vec![0, 1, 0, 3, 0], "{}", algorithm);
+            let strict = crate::dedupe(items.clone(), algorithm, 0.9, Some("case_fold".into()))
+                .ok()
+                .unwrap();
+            assert_eq!(strict, vec![0, 1, 2, 3, 0], "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_unknown_preprocessing_option_is_rejected() {
+        let items = vec!["apple".to_string(), "Apple".to_string()];
+        let spec = || Some("case_fold,lowercase".to_string());
+        let expected = "unknown preprocessing option \"lowercase\"";
+        let cdist = crate::try_cdist(items.clone(), items.clone(), "ratio", spec());
+        assert_eq!(cdist.unwrap_err(), expected);
+        let extract = crate::try_extract("apple", items.clone(), "ratio", None, None, spec());
+        assert_eq!(extract.err().unwrap(), expected);
+        let dedupe = crate::try_dedupe(items, "ratio", 0.8, spec());
+        assert_eq!(dedupe.unwrap_err(), expected);
+    }
+}
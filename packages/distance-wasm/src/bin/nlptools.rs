@@ -0,0 +1,16 @@
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+use clap::Parser;
+use distance_wasm::cli::{self, Cli};
+
+fn main() {
+    let cli = Cli::parse();
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let result = cli::run(&cli, &mut out).and_then(|_| out.flush().map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        eprintln!("nlptools: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,270 @@
+// BK-tree - metric tree for finding terms within an edit distance of a query
+// Children are keyed by their distance to the parent, so the triangle
+// inequality prunes every subtree that cannot hold a match
+
+use std::collections::BinaryHeap;
+
+use crate::myers::myers_distance;
+
+/// Edit distances satisfying the triangle inequality
+///
+/// Restricted Damerau-Levenshtein (optimal string alignment) is excluded as it
+/// violates the triangle inequality.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Levenshtein,
+    DamerauLevenshtein,
+    /// Mismatched positions plus the length difference
+    Hamming,
+    /// Levenshtein distance over UTF-16 code units with the bit-parallel Myers kernel
+    Myers,
+}
+
+impl Metric {
+    pub fn parse(name: &str) -> Option<Metric> {
+        match name.to_lowercase().as_str() {
+            "levenshtein" => Some(Metric::Levenshtein),
+            "damerau_levenshtein" | "damerau-levenshtein" | "damerau" => {
+                Some(Metric::DamerauLevenshtein)
+            }
+            "hamming" => Some(Metric::Hamming),
+            "myers" | "myers_levenshtein" | "myers-levenshtein" => Some(Metric::Myers),
+            _ => None,
+        }
+    }
+
+    pub fn distance(self, a: &str, b: &str) -> usize {
+        match self {
+            Metric::Levenshtein => textdistance::str::levenshtein(a, b),
+            Metric::DamerauLevenshtein => textdistance::str::damerau_levenshtein(a, b),
+            Metric::Hamming => textdistance::str::hamming(a, b),
+            Metric::Myers => myers_distance(a, b) as usize,
+        }
+    }
+}
+
+struct Node {
+    term: String,
+    /// Child node indices keyed by their distance to this node
+    children: Vec<(usize, usize)>,
+}
+
+pub struct BkTree {
+    metric: Metric,
+    nodes: Vec<Node>,
+}
+
+impl BkTree {
+    pub fn new(metric: Metric) -> BkTree {
+        BkTree {
+            metric,
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Insert a term, returning false if it is already present
+    pub fn insert(&mut self, term: &str) -> bool {
+        let new = Node {
+            term: term.to_string(),
+            children: Vec::new(),
+        };
+        if self.nodes.is_empty() {
+            self.nodes.push(new);
+            return true;
+        }
+
+        let mut current = 0;
+        loop {
+            let d = self.metric.distance(term, &self.nodes[current].term);
+            if d == 0 {
+                return false;
+            }
+            match self.nodes[current]
+                .children
+                .iter()
+                .find(|&&(cd, _)| cd == d)
+            {
+                Some(&(_, child)) => current = child,
+                None => {
+                    let index = self.nodes.len();
+                    self.nodes.push(new);
+                    self.nodes[current].children.push((d, index));
+                    return true;
+                }
+            }
+        }
+    }
+
+    /// All terms within distance `k` of the query, by distance then term
+    pub fn within(&self, query: &str, k: usize) -> Vec<(&str, usize)> {
+        let mut matches = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let d = self.metric.distance(query, &node.term);
+            if d <= k {
+                matches.push((node.term.as_str(), d));
+            }
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(cd, _)| cd + k >= d && cd <= d + k)
+                    .map(|&(_, child)| child),
+            );
+        }
+
+        matches.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        matches
+    }
+
+    /// The `n` terms closest to the query, by distance then term
+    pub fn nearest(&self, query: &str, n: usize) -> Vec<(&str, usize)> {
+        if n == 0 || self.nodes.is_empty() {
+            return Vec::new();
+        }
+
+        // Max-heap of the best candidates so far; its top bounds the search radius
+        let mut best: BinaryHeap<(usize, &str)> = BinaryHeap::new();
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let d = self.metric.distance(query, &node.term);
+            let candidate = (d, node.term.as_str());
+            if best.len() < n {
+                best.push(candidate);
+            } else if best.peek().is_some_and(|&top| candidate < top) {
+                best.pop();
+                best.push(candidate);
+            }
+
+            // Ties on distance are broken by term, so keep subtrees at the radius
+            let radius = if best.len() < n {
+                usize::MAX
+            } else {
+                best.peek().map_or(usize::MAX, |&(top, _)| top)
+            };
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(cd, _)| {
+                        cd.saturating_add(radius) >= d && cd <= d.saturating_add(radius)
+                    })
+                    .map(|&(_, child)| child),
+            );
+        }
+
+        let mut matches: Vec<(&str, usize)> = best.into_iter().map(|(d, term)| (term, d)).collect();
+        matches.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 10] = [
+        "book", "books", "cake", "boo", "boon", "cook", "cape", "cart", "brook", "back",
+    ];
+
+    fn tree(metric: Metric) -> BkTree {
+        let mut tree = BkTree::new(metric);
+        for word in WORDS.iter() {
+            assert!(tree.insert(word));
+        }
+        tree
+    }
+
+    fn brute_force(metric: Metric, query: &str) -> Vec<(&'static str, usize)> {
+        let mut all: Vec<(&str, usize)> = WORDS
+            .iter()
+            .map(|&w| (w, metric.distance(query, w)))
+            .collect();
+        all.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        all
+    }
+
+    #[test]
+    fn test_insert_duplicate() {
+        let mut tree = tree(Metric::Levenshtein);
+        assert!(!tree.insert("book"));
+        assert_eq!(tree.len(), WORDS.len());
+    }
+
+    #[test]
+    fn test_within() {
+        let tree = tree(Metric::Levenshtein);
+        assert_eq!(tree.within("bo", 1), vec![("boo", 1)]);
+        assert_eq!(
+            tree.within("book", 1),
+            vec![
+                ("book", 0),
+                ("boo", 1),
+                ("books", 1),
+                ("boon", 1),
+                ("brook", 1),
+                ("cook", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let metrics = [
+            Metric::Levenshtein,
+            Metric::DamerauLevenshtein,
+            Metric::Hamming,
+            Metric::Myers,
+        ];
+        for &metric in metrics.iter() {
+            let tree = tree(metric);
+            for query in ["bokc", "cake", "xyz", "", "brooks"].iter() {
+                let expected = brute_force(metric, query);
+                for k in 0..4 {
+                    let within: Vec<_> =
+                        expected.iter().copied().filter(|&(_, d)| d <= k).collect();
+                    assert_eq!(
+                        tree.within(query, k),
+                        within,
+                        "{:?} {} {}",
+                        metric,
+                        query,
+                        k
+                    );
+                }
+                for n in 0..5 {
+                    assert_eq!(
+                        tree.nearest(query, n),
+                        expected[..n].to_vec(),
+                        "{:?} {}",
+                        metric,
+                        query
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_damerau_transposition() {
+        let tree = tree(Metric::DamerauLevenshtein);
+        assert_eq!(tree.nearest("obok", 1), vec![("book", 1)]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Metric::parse("Myers"), Some(Metric::Myers));
+        assert_eq!(Metric::parse("jaro"), None);
+        assert_eq!(Metric::parse("damerau_levenshtein_restricted"), None);
+    }
+}
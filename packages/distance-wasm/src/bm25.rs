@@ -0,0 +1,349 @@
+// BM25 ranking - probabilistic relevance scoring for short-text retrieval
+// Implements Okapi BM25 and the BM25+ and BM25L variants of Lv and Zhai,
+// which correct the over-penalisation of long documents
+
+use std::collections::HashMap;
+
+use crate::preprocess::Preprocessor;
+use crate::tfidf::Analyzer;
+
+/// Term frequency normalisation variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bm25Variant {
+    Okapi,
+    /// Adds `delta` to the term score of every matching document
+    Plus,
+    /// Shifts the length-normalised term frequency by `delta`
+    L,
+}
+
+impl Bm25Variant {
+    /// Parse a variant name, rejecting unknown ones
+    pub fn parse(name: &str) -> Result<Bm25Variant, String> {
+        match name.trim().to_lowercase().as_str() {
+            "bm25" | "okapi" | "bm25_okapi" => Ok(Bm25Variant::Okapi),
+            "bm25+" | "bm25plus" | "bm25_plus" | "plus" => Ok(Bm25Variant::Plus),
+            "bm25l" | "bm25_l" | "l" => Ok(Bm25Variant::L),
+            _ => Err(format!("unsupported BM25 variant: {}", name)),
+        }
+    }
+
+    /// Default `delta`: 1.0 for BM25+ and 0.5 for BM25L
+    pub fn default_delta(self) -> f64 {
+        match self {
+            Bm25Variant::Okapi => 0.0,
+            Bm25Variant::Plus => 1.0,
+            Bm25Variant::L => 0.5,
+        }
+    }
+}
+
+/// Scoring and tokenization parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bm25Params {
+    pub variant: Bm25Variant,
+    /// Term frequency saturation
+    pub k1: f64,
+    /// Document length normalisation, 0 disables it
+    pub b: f64,
+    pub delta: f64,
+    pub analyzer: Analyzer,
+    pub ngram_range: (usize, usize),
+    pub preprocessor: Preprocessor,
+}
+
+impl Default for Bm25Params {
+    fn default() -> Bm25Params {
+        Bm25Params {
+            variant: Bm25Variant::Okapi,
+            k1: 1.2,
+            b: 0.75,
+            delta: 0.0,
+            analyzer: Analyzer::Word,
+            ngram_range: (1, 1),
+            preprocessor: Preprocessor::default(),
+        }
+    }
+}
+
+/// Inverted index ranking documents against a query with BM25
+pub struct Bm25Index {
+    params: Bm25Params,
+    next_id: u32,
+    /// Document id to its length in terms
+    lengths: HashMap<u32, usize>,
+    /// Document id to its distinct terms, so removal only visits their postings
+    document_terms: HashMap<u32, Vec<String>>,
+    /// Term to the frequency of the term in each document containing it
+    postings: HashMap<String, HashMap<u32, u32>>,
+    total_length: usize,
+}
+
+impl Bm25Index {
+    pub fn new(params: Bm25Params) -> Bm25Index {
+        Bm25Index {
+            params,
+            next_id: 0,
+            lengths: HashMap::new(),
+            document_terms: HashMap::new(),
+            postings: HashMap::new(),
+            total_length: 0,
+        }
+    }
+
+    fn terms(&self, text: &str) -> Vec<String> {
+        let text = self.params.preprocessor.apply(text);
+        self.params
+            .analyzer
+            .features(&text, self.params.ngram_range)
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Index a document and return its id
+    pub fn add(&mut self, text: &str) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let terms = self.terms(text);
+        self.total_length += terms.len();
+        self.lengths.insert(id, terms.len());
+        let mut distinct = Vec::new();
+        for term in terms {
+            let tf = self
+                .postings
+                .entry(term.clone())
+                .or_default()
+                .entry(id)
+                .or_insert(0);
+            if *tf == 0 {
+                distinct.push(term);
+            }
+            *tf += 1;
+        }
+        self.document_terms.insert(id, distinct);
+        id
+    }
+
+    /// Remove a document, returning whether it was indexed
+    pub fn remove(&mut self, id: u32) -> bool {
+        let length = match self.lengths.remove(&id) {
+            Some(length) => length,
+            None => return false,
+        };
+        self.total_length -= length;
+        for term in self.document_terms.remove(&id).unwrap_or_default() {
+            if let Some(docs) = self.postings.get_mut(&term) {
+                docs.remove(&id);
+                if docs.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+        true
+    }
+
+    fn idf(&self, df: usize) -> f64 {
+        let (n, df) = (self.len() as f64, df as f64);
+        match self.params.variant {
+            // Lucene's form, never negative for terms in most documents
+            Bm25Variant::Okapi => ((n - df + 0.5) / (df + 0.5) + 1.0).ln(),
+            Bm25Variant::Plus => ((n + 1.0) / df).ln(),
+            Bm25Variant::L => ((n + 1.0) / (df + 0.5)).ln(),
+        }
+    }
+
+    fn term_score(&self, tf: f64, length: usize, avg_length: f64) -> f64 {
+        let Bm25Params { k1, b, delta, .. } = self.params;
+        let norm = 1.0 - b + b * length as f64 / avg_length;
+        match self.params.variant {
+            Bm25Variant::Okapi => tf * (k1 + 1.0) / (tf + k1 * norm),
+            Bm25Variant::Plus => tf * (k1 + 1.0) / (tf + k1 * norm) + delta,
+            Bm25Variant::L => {
+                let ctd = tf / norm;
+                (k1 + 1.0) * (ctd + delta) / (k1 + ctd + delta)
+            }
+        }
+    }
+
+    /// BM25 score of every document matching a query term
+    pub fn scores(&self, query: &str) -> HashMap<u32, f64> {
+        let mut scores: HashMap<u32, f64> = HashMap::new();
+        if self.total_length == 0 {
+            return scores;
+        }
+        let avg_length = self.total_length as f64 / self.len() as f64;
+
+        for term in self.terms(query) {
+            let docs = match self.postings.get(&term) {
+                Some(docs) => docs,
+                None => continue,
+            };
+            let idf = self.idf(docs.len());
+            for (&id, &tf) in docs {
+                let score = idf * self.term_score(tf as f64, self.lengths[&id], avg_length);
+                *scores.entry(id).or_insert(0.0) += score;
+            }
+        }
+        scores
+    }
+
+    /// The `k` best documents as `(id, score)`, by descending score then ascending id
+    pub fn query(&self, query: &str, k: usize) -> Vec<(u32, f64)> {
+        let mut hits: Vec<(u32, f64)> = self.scores(query).into_iter().collect();
+        hits.sort_unstable_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.truncate(k);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(params: Bm25Params) -> Bm25Index {
+        let mut index = Bm25Index::new(params);
+        for doc in [
+            "hello there good man",
+            "it is quite windy in london",
+            "how is the weather today",
+        ]
+        .iter()
+        {
+            index.add(doc);
+        }
+        index
+    }
+
+    #[test]
+    fn test_okapi_scores() {
+        // Reference from the Lucene formula: N = 3, df = 1, dl = 6, avgdl = 5
+        let index = index(Bm25Params::default());
+        let hits = index.query("windy London", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, 1);
+
+        let idf = (2.5f64 / 1.5 + 1.0).ln();
+        let tf = 2.2 / (1.0 + 1.2 * (0.25 + 0.75 * 6.0 / 5.0));
+        // Case-sensitive by default: only "windy" matches
+        assert!((hits[0].1 - idf * tf).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ranking_and_top_k() {
+        let params = Bm25Params {
+            preprocessor: Preprocessor::parse("case_fold"),
+            ..Bm25Params::default()
+        };
+        let index = index(params);
+
+        let hits = index.query("windy London weather", 10);
+        let ids: Vec<u32> = hits.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(index.query("windy London weather", 1).len(), 1);
+        assert!(index.query("nothing matches", 10).is_empty());
+    }
+
+    #[test]
+    fn test_variants() {
+        for variant in [Bm25Variant::Okapi, Bm25Variant::Plus, Bm25Variant::L].iter() {
+            let params = Bm25Params {
+                variant: *variant,
+                delta: variant.default_delta(),
+                ..Bm25Params::default()
+            };
+            let index = index(params);
+            let hits = index.query("is", 10);
+            assert_eq!(hits.len(), 2, "{:?}", variant);
+            // The shorter document ranks first
+            assert_eq!(hits[0].0, 2, "{:?}", variant);
+            assert!(hits[1].1 > 0.0, "{:?}", variant);
+        }
+
+        // BM25+ lifts every matching document by at least idf * delta
+        let plus = index(Bm25Params {
+            variant: Bm25Variant::Plus,
+            delta: 1.0,
+            ..Bm25Params::default()
+        });
+        let okapi = index(Bm25Params::default());
+        assert!(plus.query("weather", 1)[0].1 > okapi.query("weather", 1)[0].1);
+    }
+
+    #[test]
+    fn test_parse_variant() {
+        assert_eq!(Bm25Variant::parse("bm25"), Ok(Bm25Variant::Okapi));
+        assert_eq!(Bm25Variant::parse("BM25+"), Ok(Bm25Variant::Plus));
+        assert_eq!(Bm25Variant::parse("bm25l"), Ok(Bm25Variant::L));
+        assert_eq!(
+            Bm25Variant::parse("bm26"),
+            Err("unsupported BM25 variant: bm26".to_string())
+        );
+        let variant = Some("bm26".to_string());
+        let index = crate::Bm25Index::try_new(variant, None, None, None, None, None, None, None);
+        assert!(index.is_err());
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let mut index = index(Bm25Params::default());
+        let id = index.add("weather in paris");
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.query("weather", 10).len(), 2);
+
+        assert!(index.remove(id));
+        assert!(!index.remove(id));
+        assert_eq!(index.len(), 3);
+        assert_eq!(
+            index.query("weather", 10),
+            vec![(2, index.query("weather", 1)[0].1)]
+        );
+        assert!(!index.postings.contains_key("paris"));
+    }
+
+    #[test]
+    fn test_remove_keeps_other_postings() {
+        let mut index = index(Bm25Params::default());
+        let repeated = index.add("paris paris weather");
+        assert_eq!(index.document_terms[&repeated], vec!["paris", "weather"]);
+        let before = index.postings.clone();
+
+        assert!(index.remove(repeated));
+        assert!(!index.document_terms.contains_key(&repeated));
+        for (term, docs) in &before {
+            let mut expected = docs.clone();
+            expected.remove(&repeated);
+            match index.postings.get(term) {
+                Some(docs) => assert_eq!(docs, &expected, "{}", term),
+                None => assert!(expected.is_empty(), "{}", term),
+            }
+        }
+        assert!(!index.postings.contains_key("paris"));
+        assert_eq!(index.postings["weather"].len(), 1);
+    }
+
+    #[test]
+    fn test_char_ngrams() {
+        let params = Bm25Params {
+            analyzer: Analyzer::Char,
+            ngram_range: (3, 3),
+            ..Bm25Params::default()
+        };
+        let index = index(params);
+        // Misspelled query still shares trigrams with the right document
+        assert_eq!(index.query("wether", 1)[0].0, 2);
+    }
+
+    #[test]
+    fn test_unknown_preprocessing_option_is_rejected() {
+        let spec = Some("nfkc,lowercase".to_string());
+        let index = crate::Bm25Index::try_new(None, None, None, None, None, None, None, spec);
+        assert_eq!(
+            index.err().unwrap(),
+            "unknown preprocessing option \"lowercase\""
+        );
+    }
+}
@@ -0,0 +1,600 @@
+// Command-line interface - score, search and deduplicate text files
+// Backs the `nlptools` binary. Files hold one text per line, or JSON Lines with
+// a string or an object per line; blank lines are skipped. `join` reads CSV.
+// Results are written as JSON or as TSV with a header row
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::join::{fuzzy_join, Blocking, JoinOptions};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "nlptools",
+    version,
+    about = "String similarity from the command line"
+)]
+pub struct Cli {
+    /// Format of the results
+    #[arg(long, short, value_enum, default_value_t = Output::Json, global = true)]
+    pub output: Output,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Output {
+    Json,
+    Tsv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    /// JSON Lines for .jsonl and .ndjson files, plain lines otherwise
+    Auto,
+    Lines,
+    Jsonl,
+}
+
+#[derive(Args, Debug)]
+pub struct ScorerArgs {
+    /// Algorithm name accepted by compare(), scored so that higher is more similar
+    #[arg(long, default_value = "ratio", value_parser = parse_algorithm)]
+    pub algo: String,
+
+    /// Preprocessing options such as "nfkc,case_fold"
+    #[arg(long, value_parser = parse_preprocessor)]
+    pub preprocessor: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct InputArgs {
+    /// Input file, or "-" for standard input
+    pub file: String,
+
+    #[arg(long, value_enum, default_value_t = InputFormat::Auto)]
+    pub format: InputFormat,
+
+    /// Field holding the text when JSON Lines records are objects
+    #[arg(long, default_value = "text")]
+    pub field: String,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Score two strings
+    Compare {
+        a: String,
+        b: String,
+        #[command(flatten)]
+        scorer: ScorerArgs,
+    },
+    /// Rank the texts of a file by similarity to a query
+    Search {
+        query: String,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Number of results
+        #[arg(long, default_value_t = 10)]
+        top: u32,
+        /// Minimum score of a result
+        #[arg(long, default_value_t = 0.0)]
+        cutoff: f64,
+        #[command(flatten)]
+        scorer: ScorerArgs,
+    },
+    /// Group near-duplicate texts of a file under their first occurrence
+    Dedupe {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Minimum score for two texts to be duplicates
+        #[arg(long, default_value_t = 0.9)]
+        threshold: f64,
+        #[command(flatten)]
+        scorer: ScorerArgs,
+    },
+    /// Match the rows of two CSV files on the similarity of a column each
+    Join {
+        /// Left CSV file with a header row, streamed; "-" for standard input
+        left: String,
+        /// Right CSV file with a header row, loaded and indexed
+        right: String,
+        /// Column of the left file to match on
+        #[arg(long)]
+        left_col: String,
+        /// Column of the right file to match on, the left column name by default
+        #[arg(long)]
+        right_col: Option<String>,
+        /// Minimum score of a matched pair
+        #[arg(long, default_value_t = 0.8)]
+        threshold: f64,
+        /// Candidate generation: "ngram[:q]", "prefix[:n]", "token" or "none"
+        #[arg(long, default_value = "ngram:3")]
+        blocking: String,
+        /// Match every row at most once, keeping the best pairs
+        #[arg(long)]
+        one_to_one: bool,
+        #[command(flatten)]
+        scorer: ScorerArgs,
+    },
+}
+
+fn parse_algorithm(name: &str) -> Result<String, String> {
+    if crate::is_algorithm(name) {
+        Ok(name.to_string())
+    } else {
+        Err(format!("unknown algorithm \"{}\"", name))
+    }
+}
+
+fn parse_preprocessor(spec: &str) -> Result<String, String> {
+    crate::preprocess::Preprocessor::try_parse(spec).map(|_| spec.to_string())
+}
+
+/// Read the texts of a file, one per line or one per JSON Lines record
+pub fn read_texts(input: &InputArgs) -> Result<Vec<String>, String> {
+    let jsonl = match input.format {
+        InputFormat::Lines => false,
+        InputFormat::Jsonl => true,
+        InputFormat::Auto => Path::new(&input.file)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("ndjson")
+            }),
+    };
+    if input.file == "-" {
+        parse_texts(io::stdin().lock(), jsonl, &input.field)
+    } else {
+        let file = File::open(&input.file).map_err(|e| format!("{}: {}", input.file, e))?;
+        parse_texts(BufReader::new(file), jsonl, &input.field)
+            .map_err(|e| format!("{}: {}", input.file, e))
+    }
+}
+
+fn parse_texts(reader: impl BufRead, jsonl: bool, field: &str) -> Result<Vec<String>, String> {
+    let mut texts = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !jsonl {
+            texts.push(line.to_string());
+            continue;
+        }
+        let record: Value = serde_json::from_str(line)
+            .map_err(|e| format!("line {}: invalid JSON: {}", number + 1, e))?;
+        let text = match &record {
+            Value::String(text) => Some(text),
+            Value::Object(fields) => match fields.get(field) {
+                Some(Value::String(text)) => Some(text),
+                _ => None,
+            },
+            _ => None,
+        };
+        match text {
+            Some(text) => texts.push(text.clone()),
+            None => {
+                return Err(format!(
+                    "line {}: expected a string or an object with a string `{}` field",
+                    number + 1,
+                    field
+                ))
+            }
+        }
+    }
+    Ok(texts)
+}
+
+/// Escape the characters TSV cannot hold in a field
+fn tsv_field(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Streams rows as a JSON array of objects or as TSV under a header of the keys
+struct RowWriter<'a> {
+    out: &'a mut dyn Write,
+    output: Output,
+    keys: Vec<String>,
+    rows: usize,
+}
+
+impl<'a> RowWriter<'a> {
+    fn new(out: &'a mut dyn Write, output: Output, keys: Vec<String>) -> io::Result<RowWriter<'a>> {
+        match output {
+            Output::Json => write!(out, "[")?,
+            Output::Tsv => writeln!(out, "{}", keys.join("\t"))?,
+        }
+        Ok(RowWriter {
+            out,
+            output,
+            keys,
+            rows: 0,
+        })
+    }
+
+    fn row(&mut self, row: &[Value]) -> io::Result<()> {
+        match self.output {
+            Output::Json => {
+                // One object per line, keys in column order
+                let fields: Vec<String> = self
+                    .keys
+                    .iter()
+                    .zip(row)
+                    .map(|(key, value)| format!("{}: {}", json!(key), value))
+                    .collect();
+                let separator = if self.rows == 0 { "" } else { "," };
+                write!(self.out, "{}\n  {{{}}}", separator, fields.join(", "))?;
+            }
+            Output::Tsv => {
+                let fields: Vec<String> = row
+                    .iter()
+                    .map(|value| match value {
+                        Value::String(text) => tsv_field(text),
+                        value => value.to_string(),
+                    })
+                    .collect();
+                writeln!(self.out, "{}", fields.join("\t"))?;
+            }
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self.output {
+            Output::Json => writeln!(self.out, "\n]"),
+            Output::Tsv => Ok(()),
+        }
+    }
+}
+
+fn write_rows(
+    out: &mut dyn Write,
+    output: Output,
+    keys: &[&str],
+    rows: &[Vec<Value>],
+) -> Result<(), String> {
+    let keys = keys.iter().map(|key| key.to_string()).collect();
+    let mut writer = RowWriter::new(out, output, keys).map_err(|e| e.to_string())?;
+    for row in rows {
+        writer.row(row).map_err(|e| e.to_string())?;
+    }
+    writer.finish().map_err(|e| e.to_string())
+}
+
+fn open(path: &str) -> Result<Box<dyn Read>, String> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin()));
+    }
+    File::open(path)
+        .map(|file| Box::new(file) as Box<dyn Read>)
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Stream the matches of a fuzzy join as rows of both records and their score
+fn write_join(
+    out: &mut dyn Write,
+    output: Output,
+    left: &str,
+    right: &str,
+    options: JoinOptions,
+) -> Result<(), String> {
+    let join = fuzzy_join(open(left)?, open(right)?, options)?;
+    let mut keys = join.headers().to_vec();
+    keys.push("score".to_string());
+
+    let mut writer = RowWriter::new(out, output, keys).map_err(|e| e.to_string())?;
+    for joined in join {
+        let joined = joined?;
+        let mut row: Vec<Value> = joined.fields.into_iter().map(Value::String).collect();
+        row.push(json!(joined.score));
+        writer.row(&row).map_err(|e| e.to_string())?;
+    }
+    writer.finish().map_err(|e| e.to_string())
+}
+
+/// Run a parsed command, writing its results to `out`
+pub fn run(cli: &Cli, out: &mut dyn Write) -> Result<(), String> {
+    match &cli.command {
+        Command::Compare { a, b, scorer } => {
+            let (a, b) = crate::preprocess::prepare(a, b, scorer.preprocessor.as_deref());
+            let score = crate::similarity_by_name(&a, &b, &scorer.algo);
+            write_rows(
+                out,
+                cli.output,
+                &["algorithm", "score"],
+                &[vec![json!(scorer.algo), json!(score)]],
+            )
+        }
+        Command::Search {
+            query,
+            input,
+            top,
+            cutoff,
+            scorer,
+        } => {
+            let texts = read_texts(input)?;
+            let hits = crate::try_extract(
+                query,
+                texts.clone(),
+                &scorer.algo,
+                Some(*top),
+                Some(*cutoff),
+                scorer.preprocessor.clone(),
+            )?;
+            let rows: Vec<Vec<Value>> = hits
+                .iter()
+                .map(|hit| {
+                    vec![
+                        json!(hit.id),
+                        json!(hit.score),
+                        json!(texts[hit.id as usize]),
+                    ]
+                })
+                .collect();
+            write_rows(out, cli.output, &["index", "score", "text"], &rows)
+        }
+        Command::Dedupe {
+            input,
+            threshold,
+            scorer,
+        } => {
+            let texts = read_texts(input)?;
+            let groups = crate::try_dedupe(
+                texts.clone(),
+                &scorer.algo,
+                *threshold,
+                scorer.preprocessor.clone(),
+            )?;
+            let rows: Vec<Vec<Value>> = groups
+                .iter()
+                .zip(&texts)
+                .enumerate()
+                .map(|(index, (group, text))| vec![json!(index), json!(group), json!(text)])
+                .collect();
+            write_rows(out, cli.output, &["index", "group", "text"], &rows)
+        }
+        Command::Join {
+            left,
+            right,
+            left_col,
+            right_col,
+            threshold,
+            blocking,
+            one_to_one,
+            scorer,
+        } => {
+            let options = JoinOptions {
+                left_col: left_col.clone(),
+                right_col: right_col.clone().unwrap_or_else(|| left_col.clone()),
+                algorithm: scorer.algo.clone(),
+                threshold: *threshold,
+                blocking: Blocking::parse(blocking)?,
+                one_to_one: *one_to_one,
+                preprocessor: scorer.preprocessor.clone(),
+            };
+            write_join(out, cli.output, left, right, options)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_args(args: &[&str]) -> Result<String, String> {
+        let cli = Cli::try_parse_from(args).map_err(|e| e.to_string())?;
+        let mut out = Vec::new();
+        run(&cli, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn temp_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("nlptools-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_parse_texts() {
+        let lines = "apple\r\n\nbanana\n";
+        assert_eq!(
+            parse_texts(lines.as_bytes(), false, "text").unwrap(),
+            vec!["apple", "banana"]
+        );
+
+        let jsonl = "\"apple\"\n{\"name\": \"banana\", \"id\": 2}\n";
+        assert_eq!(
+            parse_texts(jsonl.as_bytes(), true, "name").unwrap(),
+            vec!["apple", "banana"]
+        );
+        let err = parse_texts(jsonl.as_bytes(), true, "text").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{}", err);
+        assert!(parse_texts("{oops".as_bytes(), true, "text").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let out =
+            run_args(&["nlptools", "compare", "kitten", "sitting", "--algo", "jaro"]).unwrap();
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value[0]["algorithm"], "jaro");
+        assert_eq!(
+            value[0]["score"],
+            crate::compare("kitten", "sitting", "jaro", None)
+        );
+
+        let out = run_args(&["nlptools", "-o", "tsv", "compare", "a", "a"]).unwrap();
+        assert_eq!(out, "algorithm\tscore\nratio\t1.0\n");
+    }
+
+    #[test]
+    fn test_search() {
+        let file = temp_file("search.txt", "apple pie\nbanana\napple\napplesauce\n");
+        let out = run_args(&["nlptools", "search", "apple", &file, "--top", "2"]).unwrap();
+        let hits: Vec<Value> = serde_json::from_str(&out).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0]["index"], 2);
+        assert_eq!(hits[0]["text"], "apple");
+        assert_eq!(hits[0]["score"], 1.0);
+
+        let out = run_args(&[
+            "nlptools", "search", "apple", &file, "--cutoff", "1", "--output", "tsv",
+        ])
+        .unwrap();
+        assert_eq!(out, "index\tscore\ttext\n2\t1.0\tapple\n");
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let file = temp_file(
+            "dedupe.jsonl",
+            "{\"text\": \"Apple\"}\n{\"text\": \"banana\"}\n{\"text\": \"apple\"}\n{\"text\": \"tab\\there\"}\n",
+        );
+        let out = run_args(&[
+            "nlptools",
+            "dedupe",
+            &file,
+            "--threshold",
+            "0.9",
+            "--preprocessor",
+            "case_fold",
+            "-o",
+            "tsv",
+        ])
+        .unwrap();
+        assert_eq!(
+            out,
+            "index\tgroup\ttext\n0\t0\tApple\n1\t1\tbanana\n2\t0\tapple\n3\t3\ttab\\there\n"
+        );
+
+        // Read as plain lines the records themselves are the texts
+        let out = run_args(&["nlptools", "dedupe", &file, "--format", "lines"]).unwrap();
+        let rows: Vec<Value> = serde_json::from_str(&out).unwrap();
+        assert_eq!(rows[0]["text"], "{\"text\": \"Apple\"}");
+        assert!(run_args(&["nlptools", "dedupe", "/nonexistent/file.txt"]).is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_edit_distance_algorithms() {
+        let out = run_args(&[
+            "nlptools",
+            "compare",
+            "apple",
+            "apply",
+            "--algo",
+            "levenshtein",
+        ])
+        .unwrap();
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value[0]["score"], 0.8);
+
+        let file = temp_file(
+            "algo.txt",
+            "banana
+apply
+apple
+mango
+",
+        );
+        for algo in ["levenshtein", "hamming"] {
+            let out = run_args(&[
+                "nlptools", "-o", "tsv", "search", "apple", &file, "--top", "2", "--algo", algo,
+            ])
+            .unwrap();
+            assert_eq!(out, "index\tscore\ttext\n2\t1.0\tapple\n1\t0.8\tapply\n");
+
+            let out = run_args(&[
+                "nlptools",
+                "dedupe",
+                &file,
+                "--threshold",
+                "0.8",
+                "--algo",
+                algo,
+            ])
+            .unwrap();
+            let rows: Vec<Value> = serde_json::from_str(&out).unwrap();
+            let groups: Vec<&Value> = rows.iter().map(|row| &row["group"]).collect();
+            assert_eq!(groups, [0, 1, 1, 3]);
+        }
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let err = run_args(&["nlptools", "compare", "a", "b", "--algo", "levenstein"]).unwrap_err();
+        assert!(err.contains("unknown algorithm \"levenstein\""), "{}", err);
+        assert!(run_args(&["nlptools", "compare", "a", "b", "--algo", "Jaro-Winkler"]).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_preprocessor() {
+        let args = [
+            "nlptools",
+            "compare",
+            "a",
+            "b",
+            "--preprocessor",
+            "nfkc,lowercase",
+        ];
+        let err = run_args(&args).unwrap_err();
+        assert!(
+            err.contains("unknown preprocessing option \"lowercase\""),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        let left = temp_file(
+            "left.csv",
+            "id,company\n1,Acme Inc\n2,Globex\n3,\"Initech, LLC\"\n",
+        );
+        let right = temp_file(
+            "right.csv",
+            "name,city\nACME Inc.,Austin\nInitech LLC,Dallas\n",
+        );
+        let out = run_args(&[
+            "nlptools",
+            "-o",
+            "tsv",
+            "join",
+            &left,
+            &right,
+            "--left-col",
+            "company",
+            "--right-col",
+            "name",
+            "--preprocessor",
+            "case_fold",
+            "--threshold",
+            "0.9",
+        ])
+        .unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "id\tcompany\tname\tcity\tscore");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("1\tAcme Inc\tACME Inc.\tAustin\t0.94"));
+        assert!(lines[2].starts_with("3\tInitech, LLC\tInitech LLC\tDallas\t"));
+
+        let err = run_args(&["nlptools", "join", &left, &right, "--left-col", "company"]);
+        assert!(err.unwrap_err().contains("no column `company`"));
+        std::fs::remove_file(left).unwrap();
+        std::fs::remove_file(right).unwrap();
+    }
+}
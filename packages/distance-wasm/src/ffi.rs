@@ -0,0 +1,2439 @@
+// C ABI - scorers and indexes for embedding in C and C++ programs
+// Strings are UTF-8 pointer and length pairs and need not be NUL-terminated; a
+// null pointer with length 0 is the empty string, and a null preprocessor means
+// none while unknown preprocessing options are rejected. Every function returns an `NlpStatus` and writes its result through an
+// out pointer. Indexes are opaque handles released with their `_free` function.
+// The header is generated by cbindgen and committed as include/nlptools_distance.h
+//
+// Safety: callers pass pointers valid for the given lengths, handles created by
+// this library and not yet freed, and never use one handle from two threads at once
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+use crate::preprocess::Preprocessor;
+use crate::{automaton, bktree, hnsw, quantize, tfidf, vptree};
+
+/// Result code of every exported function
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NlpStatus {
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// A string was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// An argument was rejected, see nlp_last_error()
+    InvalidArgument = 3,
+    /// The output buffer was too small; the required length was still written
+    BufferTooSmall = 4,
+    /// The call panicked; handles passed to it should only be freed
+    Panic = 5,
+}
+
+/// UTF-8 string slice, used for arrays of strings
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NlpStr {
+    pub ptr: *const c_char,
+    pub len: usize,
+}
+
+/// Search result: an id with its score or distance, as documented per index
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NlpHit {
+    pub id: u32,
+    pub score: f64,
+}
+
+/// Matched term and its edit distance; `term` points into memory owned by the
+/// handle, valid until the handle is next searched or released
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NlpTermMatch {
+    pub term: NlpStr,
+    pub distance: u32,
+}
+
+/// Spelling suggestion; `term` is owned by the handle like `NlpTermMatch::term`
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NlpSuggestion {
+    pub term: NlpStr,
+    pub distance: u32,
+    pub count: f64,
+}
+
+/// Space-separated words as typed and as corrected, owned by the handle
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NlpSegmentation {
+    pub segmented: NlpStr,
+    pub corrected: NlpStr,
+    pub distance_sum: u32,
+    pub log_probability_sum: f64,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// NUL-terminated message of the last failed call on this thread, valid until
+/// the next failed call; its length without the NUL is written to `len` unless null
+#[no_mangle]
+pub unsafe extern "C" fn nlp_last_error(len: *mut usize) -> *const c_char {
+    LAST_ERROR.with(|error| {
+        let error = error.borrow();
+        if !len.is_null() {
+            *len = error.as_bytes().len();
+        }
+        error.as_ptr()
+    })
+}
+
+fn fail(status: NlpStatus, message: &str) -> NlpStatus {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = message);
+    status
+}
+
+fn invalid(message: String) -> NlpStatus {
+    fail(NlpStatus::InvalidArgument, &message)
+}
+
+/// Run a call body, turning panics into `NlpStatus::Panic`
+fn guard(body: impl FnOnce() -> Result<(), NlpStatus>) -> NlpStatus {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => NlpStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => fail(NlpStatus::Panic, "panic in nlptools"),
+    }
+}
+
+unsafe fn text<'a>(ptr: *const c_char, len: usize) -> Result<&'a str, NlpStatus> {
+    if ptr.is_null() {
+        return if len == 0 {
+            Ok("")
+        } else {
+            Err(fail(
+                NlpStatus::NullPointer,
+                "null string with non-zero length",
+            ))
+        };
+    }
+    str::from_utf8(slice::from_raw_parts(ptr as *const u8, len))
+        .map_err(|e| fail(NlpStatus::InvalidUtf8, &e.to_string()))
+}
+
+unsafe fn optional_text(ptr: *const c_char, len: usize) -> Result<Option<String>, NlpStatus> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        text(ptr, len).map(|s| Some(s.to_string()))
+    }
+}
+
+unsafe fn texts(items: *const NlpStr, count: usize) -> Result<Vec<String>, NlpStatus> {
+    let items = array(items, count)?;
+    items
+        .iter()
+        .map(|item| text(item.ptr, item.len).map(str::to_string))
+        .collect()
+}
+
+unsafe fn array<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], NlpStatus> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(fail(
+            NlpStatus::NullPointer,
+            "null array with non-zero length",
+        ));
+    }
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), NlpStatus> {
+    if out.is_null() {
+        return Err(fail(NlpStatus::NullPointer, "null output pointer"));
+    }
+    out.write(value);
+    Ok(())
+}
+
+/// Copy `values` into a caller buffer, writing the full length to `len`
+unsafe fn write_all<T: Copy>(
+    values: &[T],
+    buffer: *mut T,
+    capacity: usize,
+    len: *mut usize,
+) -> Result<(), NlpStatus> {
+    write(len, values.len())?;
+    let written = values.len().min(capacity);
+    if written > 0 {
+        if buffer.is_null() {
+            return Err(fail(NlpStatus::NullPointer, "null output buffer"));
+        }
+        ptr::copy_nonoverlapping(values.as_ptr(), buffer, written);
+    }
+    if written < values.len() {
+        return Err(fail(NlpStatus::BufferTooSmall, "output buffer too small"));
+    }
+    Ok(())
+}
+
+unsafe fn handle<'a, T>(handle: *const T) -> Result<&'a T, NlpStatus> {
+    handle
+        .as_ref()
+        .ok_or_else(|| fail(NlpStatus::NullPointer, "null handle"))
+}
+
+unsafe fn handle_mut<'a, T>(handle: *mut T) -> Result<&'a mut T, NlpStatus> {
+    handle
+        .as_mut()
+        .ok_or_else(|| fail(NlpStatus::NullPointer, "null handle"))
+}
+
+unsafe fn free<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+fn hits(hits: impl IntoIterator<Item = (u32, f64)>) -> Vec<NlpHit> {
+    hits.into_iter()
+        .map(|(id, score)| NlpHit { id, score })
+        .collect()
+}
+
+/// Optional preprocessor spec, rejecting option names the scorers would ignore
+unsafe fn preprocessor_spec(ptr: *const c_char, len: usize) -> Result<Option<String>, NlpStatus> {
+    let spec = optional_text(ptr, len)?;
+    if let Some(spec) = &spec {
+        Preprocessor::try_parse(spec).map_err(invalid)?;
+    }
+    Ok(spec)
+}
+
+fn nlp_str(s: &str) -> NlpStr {
+    NlpStr {
+        ptr: s.as_ptr() as *const c_char,
+        len: s.len(),
+    }
+}
+
+/// `value`, or `default` when the caller passed NAN
+fn or_default(value: f64, default: f64) -> f64 {
+    if value.is_nan() {
+        default
+    } else {
+        value
+    }
+}
+
+/// `value`, or none when the caller passed 0
+fn nonzero(value: u32) -> Option<u32> {
+    Some(value).filter(|&value| value > 0)
+}
+
+// ============================================================================
+// Scorers - Same results as the wasm exports of the same name
+// ============================================================================
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_levenshtein(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::levenshtein(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_levenshtein_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::levenshtein_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_damerau_levenshtein(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::damerau_levenshtein(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_damerau_levenshtein_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::damerau_levenshtein_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_jaro(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::jaro(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_jarowinkler(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::jarowinkler(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hamming(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::hamming(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hamming_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::hamming_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_sift4_simple(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::sift4_simple(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_sift4_simple_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::sift4_simple_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_myers_levenshtein(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::myers_levenshtein(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_myers_levenshtein_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::myers_levenshtein_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_lcs_seq(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::lcs_seq(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_lcs_seq_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::lcs_seq_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_lcs_str(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::lcs_str(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_lcs_str_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::lcs_str_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ratcliff_obershelp(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::ratcliff_obershelp(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_jaccard(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::jaccard(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_cosine(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::cosine(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_sorensen(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::sorensen(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tversky(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::tversky(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_smith_waterman(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::smith_waterman(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_smith_waterman_normalized(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::smith_waterman_normalized(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_overlap(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::overlap(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::ratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_partial_ratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::partial_ratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_token_sort_ratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::token_sort_ratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_token_set_ratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::token_set_ratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_partial_token_sort_ratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::partial_token_sort_ratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_wratio(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::wratio(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_prefix(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::prefix(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_suffix(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::suffix(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_length(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::length(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_jaccard_bigram(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::jaccard_bigram(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_cosine_bigram(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::cosine_bigram(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+/// Similarity by algorithm name, as compare() in the wasm build
+#[no_mangle]
+pub unsafe extern "C" fn nlp_compare(
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    algorithm: *const c_char,
+    algorithm_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let score = crate::compare(
+            text(s1, s1_len)?,
+            text(s2, s2_len)?,
+            text(algorithm, algorithm_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        );
+        write(out, score)
+    })
+}
+
+/// Score every query against every choice into `scores`, which holds `capacity`
+/// values; `query_count * choice_count` scores are written row-major by query
+#[no_mangle]
+pub unsafe extern "C" fn nlp_cdist(
+    queries: *const NlpStr,
+    query_count: usize,
+    choices: *const NlpStr,
+    choice_count: usize,
+    algorithm: *const c_char,
+    algorithm_len: usize,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    scores: *mut f64,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = crate::try_cdist(
+            texts(queries, query_count)?,
+            texts(choices, choice_count)?,
+            text(algorithm, algorithm_len)?,
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        )
+        .map_err(invalid)?;
+        write_all(&found, scores, capacity, count)
+    })
+}
+
+// ============================================================================
+// Soft TF-IDF - Token similarity with an inner character metric
+// ============================================================================
+
+pub struct NlpSoftTfIdf(crate::SoftTfIdf);
+
+/// Fit IDF weights on `corpus`; `inner` defaults to "jaro_winkler" and a NAN
+/// `threshold` to 0.9
+#[no_mangle]
+pub unsafe extern "C" fn nlp_soft_tfidf_new(
+    corpus: *const NlpStr,
+    corpus_count: usize,
+    inner: *const c_char,
+    inner_len: usize,
+    threshold: f64,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut *mut NlpSoftTfIdf,
+) -> NlpStatus {
+    guard(|| {
+        let model = crate::SoftTfIdf::try_new(
+            texts(corpus, corpus_count)?,
+            optional_text(inner, inner_len)?,
+            Some(or_default(threshold, 0.9)),
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        )
+        .map_err(invalid)?;
+        write(out, Box::into_raw(Box::new(NlpSoftTfIdf(model))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_soft_tfidf_free(model: *mut NlpSoftTfIdf) {
+    free(model)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_soft_tfidf_similarity(
+    model: *const NlpSoftTfIdf,
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let model = handle(model)?;
+        write(
+            out,
+            model.0.similarity(text(s1, s1_len)?, text(s2, s2_len)?),
+        )
+    })
+}
+
+// ============================================================================
+// TF-IDF Model - Vector hits carry the feature index and its weight
+// ============================================================================
+
+pub struct NlpTfIdfModel(crate::TfIdfModel);
+
+/// `analyzer` is "word" (default) or "char"; zero n-gram bounds take single words
+/// or character trigrams
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_new(
+    corpus: *const NlpStr,
+    corpus_count: usize,
+    analyzer: *const c_char,
+    analyzer_len: usize,
+    ngram_min: u32,
+    ngram_max: u32,
+    sublinear_tf: bool,
+    smooth_idf: bool,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut *mut NlpTfIdfModel,
+) -> NlpStatus {
+    guard(|| {
+        let model = crate::TfIdfModel::try_new(
+            texts(corpus, corpus_count)?,
+            optional_text(analyzer, analyzer_len)?,
+            nonzero(ngram_min),
+            nonzero(ngram_max),
+            Some(sublinear_tf),
+            Some(smooth_idf),
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        )
+        .map_err(invalid)?;
+        write(out, Box::into_raw(Box::new(NlpTfIdfModel(model))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_free(model: *mut NlpTfIdfModel) {
+    free(model)
+}
+
+/// Cosine similarity of the TF-IDF vectors of two texts
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_similarity(
+    model: *const NlpTfIdfModel,
+    s1: *const c_char,
+    s1_len: usize,
+    s2: *const c_char,
+    s2_len: usize,
+    out: *mut f64,
+) -> NlpStatus {
+    guard(|| {
+        let model = handle(model)?;
+        write(
+            out,
+            model.0.similarity(text(s1, s1_len)?, text(s2, s2_len)?),
+        )
+    })
+}
+
+/// Non-zero entries of the unit length TF-IDF vector of `text`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_vectorize(
+    model: *const NlpTfIdfModel,
+    document: *const c_char,
+    document_len: usize,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let vector = handle(model)?.0.vectorize(text(document, document_len)?);
+        let found = self::hits(vector.indices().into_iter().zip(vector.values()));
+        write_all(&found, hits, capacity, count)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_vocabulary_size(
+    model: *const NlpTfIdfModel,
+    size: *mut usize,
+) -> NlpStatus {
+    guard(|| write(size, handle(model)?.0.vocabulary_size()))
+}
+
+/// Serialize the model as UTF-8 JSON into `buffer`, without a NUL terminator,
+/// writing the full length to `len`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_serialize(
+    model: *const NlpTfIdfModel,
+    buffer: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let json = handle(model)?.0.serialize();
+        let json = slice::from_raw_parts(json.as_ptr() as *const c_char, json.len());
+        write_all(json, buffer, capacity, len)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_tfidf_deserialize(
+    json: *const c_char,
+    json_len: usize,
+    out: *mut *mut NlpTfIdfModel,
+) -> NlpStatus {
+    guard(|| {
+        let model = tfidf::TfIdf::from_json(text(json, json_len)?).map_err(invalid)?;
+        write(
+            out,
+            Box::into_raw(Box::new(NlpTfIdfModel(crate::TfIdfModel { model }))),
+        )
+    })
+}
+
+// ============================================================================
+// BM25 Index - Hits carry the BM25 score
+// ============================================================================
+
+pub struct NlpBm25Index(crate::Bm25Index);
+
+/// `variant` is "bm25" (default), "bm25+" or "bm25l" and `analyzer` is "word"
+/// (default) or "char". NAN `k1`, `b` and `delta` take 1.2, 0.75 and
+/// the variant's delta; zero n-gram bounds take single words or character trigrams
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_new(
+    variant: *const c_char,
+    variant_len: usize,
+    k1: f64,
+    b: f64,
+    delta: f64,
+    analyzer: *const c_char,
+    analyzer_len: usize,
+    ngram_min: u32,
+    ngram_max: u32,
+    preprocessor: *const c_char,
+    preprocessor_len: usize,
+    out: *mut *mut NlpBm25Index,
+) -> NlpStatus {
+    guard(|| {
+        let given = |value: f64| Some(value).filter(|value| !value.is_nan());
+        let index = crate::Bm25Index::try_new(
+            optional_text(variant, variant_len)?,
+            given(k1),
+            given(b),
+            given(delta),
+            optional_text(analyzer, analyzer_len)?,
+            nonzero(ngram_min),
+            nonzero(ngram_max),
+            preprocessor_spec(preprocessor, preprocessor_len)?,
+        )
+        .map_err(invalid)?;
+        write(out, Box::into_raw(Box::new(NlpBm25Index(index))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_free(index: *mut NlpBm25Index) {
+    free(index)
+}
+
+/// Index a document, writing its id to `id`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_add(
+    index: *mut NlpBm25Index,
+    document: *const c_char,
+    document_len: usize,
+    id: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle_mut(index)?;
+        let added = index.0.add(text(document, document_len)?);
+        write(id, added)
+    })
+}
+
+/// Remove a document, writing whether it was indexed to `removed`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_remove(
+    index: *mut NlpBm25Index,
+    id: u32,
+    removed: *mut bool,
+) -> NlpStatus {
+    guard(|| write(removed, handle_mut(index)?.0.remove(id)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_size(index: *const NlpBm25Index, size: *mut usize) -> NlpStatus {
+    guard(|| write(size, handle(index)?.0.size()))
+}
+
+/// Top `k` documents by descending score into `hits`, which holds `capacity` hits
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bm25_query(
+    index: *const NlpBm25Index,
+    query: *const c_char,
+    query_len: usize,
+    k: u32,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(index)?.0.query(text(query, query_len)?, Some(k));
+        let found = self::hits(found.into_iter().map(|hit| (hit.id, hit.score)));
+        write_all(&found, hits, capacity, count)
+    })
+}
+
+// ============================================================================
+// N-gram Index - Hits carry the edit distance
+// ============================================================================
+
+pub struct NlpNgramIndex(crate::NgramIndex);
+
+/// `q` of 0 takes the default of 2
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ngram_new(q: u32, out: *mut *mut NlpNgramIndex) -> NlpStatus {
+    guard(|| {
+        let index = crate::NgramIndex::new(nonzero(q));
+        write(out, Box::into_raw(Box::new(NlpNgramIndex(index))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ngram_free(index: *mut NlpNgramIndex) {
+    free(index)
+}
+
+/// Index a string, writing its id to `id`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ngram_insert(
+    index: *mut NlpNgramIndex,
+    term: *const c_char,
+    term_len: usize,
+    id: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle_mut(index)?;
+        let inserted = index.0.insert(text(term, term_len)?);
+        write(id, inserted)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ngram_delete(
+    index: *mut NlpNgramIndex,
+    id: u32,
+    deleted: *mut bool,
+) -> NlpStatus {
+    guard(|| write(deleted, handle_mut(index)?.0.delete(id)))
+}
+
+/// Every string within edit distance `k`, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_ngram_search(
+    index: *const NlpNgramIndex,
+    query: *const c_char,
+    query_len: usize,
+    k: u32,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(index)?.0.search(text(query, query_len)?, k);
+        let found = self::hits(found.into_iter().map(|hit| (hit.id, hit.distance as f64)));
+        write_all(&found, hits, capacity, count)
+    })
+}
+
+// ============================================================================
+// BK-Tree - Terms within an edit distance of a query
+// ============================================================================
+
+pub struct NlpBkTree(bktree::BkTree);
+
+fn term_matches(matches: Vec<(&str, usize)>) -> Vec<NlpTermMatch> {
+    matches
+        .into_iter()
+        .map(|(term, distance)| NlpTermMatch {
+            term: nlp_str(term),
+            distance: distance as u32,
+        })
+        .collect()
+}
+
+/// `metric` is "levenshtein" (default), "damerau_levenshtein", "hamming" or "myers"
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_new(
+    metric: *const c_char,
+    metric_len: usize,
+    out: *mut *mut NlpBkTree,
+) -> NlpStatus {
+    guard(|| {
+        let name = optional_text(metric, metric_len)?;
+        let name = name.as_deref().unwrap_or("levenshtein");
+        let metric = bktree::Metric::parse(name)
+            .ok_or_else(|| invalid(format!("unsupported BK-tree metric: {}", name)))?;
+        write(
+            out,
+            Box::into_raw(Box::new(NlpBkTree(bktree::BkTree::new(metric)))),
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_free(tree: *mut NlpBkTree) {
+    free(tree)
+}
+
+/// Insert a term, writing false to `inserted` if it was already present
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_insert(
+    tree: *mut NlpBkTree,
+    term: *const c_char,
+    term_len: usize,
+    inserted: *mut bool,
+) -> NlpStatus {
+    guard(|| {
+        let tree = handle_mut(tree)?;
+        let added = tree.0.insert(text(term, term_len)?);
+        write(inserted, added)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_size(tree: *const NlpBkTree, size: *mut usize) -> NlpStatus {
+    guard(|| write(size, handle(tree)?.0.len()))
+}
+
+/// All terms within distance `k`, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_within(
+    tree: *const NlpBkTree,
+    query: *const c_char,
+    query_len: usize,
+    k: u32,
+    matches: *mut NlpTermMatch,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(tree)?.0.within(text(query, query_len)?, k as usize);
+        write_all(&term_matches(found), matches, capacity, count)
+    })
+}
+
+/// The `n` closest terms, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_bktree_nearest(
+    tree: *const NlpBkTree,
+    query: *const c_char,
+    query_len: usize,
+    n: u32,
+    matches: *mut NlpTermMatch,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(tree)?.0.nearest(text(query, query_len)?, n as usize);
+        write_all(&term_matches(found), matches, capacity, count)
+    })
+}
+
+// ============================================================================
+// Term Dictionary - Fuzzy term expansion with Levenshtein automata
+// ============================================================================
+
+pub struct NlpTermDictionary {
+    terms: automaton::TermSet,
+    /// Terms of the last search, borrowed by the returned matches
+    matches: RefCell<Vec<String>>,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_termdict_new(
+    terms: *const NlpStr,
+    term_count: usize,
+    out: *mut *mut NlpTermDictionary,
+) -> NlpStatus {
+    guard(|| {
+        let dictionary = NlpTermDictionary {
+            terms: automaton::TermSet::new(&texts(terms, term_count)?),
+            matches: RefCell::default(),
+        };
+        write(out, Box::into_raw(Box::new(dictionary)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_termdict_free(dictionary: *mut NlpTermDictionary) {
+    free(dictionary)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_termdict_size(
+    dictionary: *const NlpTermDictionary,
+    size: *mut usize,
+) -> NlpStatus {
+    guard(|| write(size, handle(dictionary)?.terms.len()))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_termdict_contains(
+    dictionary: *const NlpTermDictionary,
+    term: *const c_char,
+    term_len: usize,
+    found: *mut bool,
+) -> NlpStatus {
+    guard(|| {
+        let dictionary = handle(dictionary)?;
+        write(found, dictionary.terms.contains(text(term, term_len)?))
+    })
+}
+
+/// All terms within distance `k` (at most 3) in lexicographic order; with
+/// `transpositions` an adjacent swap counts as one edit
+#[no_mangle]
+pub unsafe extern "C" fn nlp_termdict_fuzzy_search(
+    dictionary: *const NlpTermDictionary,
+    query: *const c_char,
+    query_len: usize,
+    k: u32,
+    transpositions: bool,
+    matches: *mut NlpTermMatch,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let dictionary = handle(dictionary)?;
+        let k = k.min(u8::MAX as u32) as u8;
+        let found = dictionary
+            .terms
+            .fuzzy(text(query, query_len)?, k, transpositions)
+            .map_err(invalid)?;
+        let (terms, distances): (Vec<String>, Vec<u8>) = found.into_iter().unzip();
+        let mut owned = dictionary.matches.borrow_mut();
+        *owned = terms;
+        let found: Vec<NlpTermMatch> = owned
+            .iter()
+            .zip(distances)
+            .map(|(term, distance)| NlpTermMatch {
+                term: nlp_str(term),
+                distance: distance as u32,
+            })
+            .collect();
+        write_all(&found, matches, capacity, count)
+    })
+}
+
+// ============================================================================
+// VP-Tree - Hits carry the distance; ids are positions in the item array
+// ============================================================================
+
+pub struct NlpVpTree(vptree::VpTree);
+
+/// `metric` is "yujian_bo" (default), "jaccard" or "jaccard_bigram"
+#[no_mangle]
+pub unsafe extern "C" fn nlp_vptree_new(
+    items: *const NlpStr,
+    item_count: usize,
+    metric: *const c_char,
+    metric_len: usize,
+    out: *mut *mut NlpVpTree,
+) -> NlpStatus {
+    guard(|| {
+        let name = optional_text(metric, metric_len)?;
+        let name = name.as_deref().unwrap_or("yujian_bo");
+        let metric = vptree::FloatMetric::parse(name)
+            .ok_or_else(|| invalid(format!("unsupported VP-tree metric: {}", name)))?;
+        let tree = vptree::VpTree::new(texts(items, item_count)?, metric);
+        write(out, Box::into_raw(Box::new(NlpVpTree(tree))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_vptree_free(tree: *mut NlpVpTree) {
+    free(tree)
+}
+
+/// The `n` closest items, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_vptree_nearest(
+    tree: *const NlpVpTree,
+    query: *const c_char,
+    query_len: usize,
+    n: u32,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(tree)?.0.nearest(text(query, query_len)?, n as usize);
+        write_all(&self::hits(found), hits, capacity, count)
+    })
+}
+
+/// All items within distance `radius`, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_vptree_within(
+    tree: *const NlpVpTree,
+    query: *const c_char,
+    query_len: usize,
+    radius: f64,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(tree)?.0.within(text(query, query_len)?, radius);
+        write_all(&self::hits(found), hits, capacity, count)
+    })
+}
+
+// ============================================================================
+// HNSW Index - Hits carry the metric distance
+// ============================================================================
+
+pub struct NlpHnswIndex(hnsw::Hnsw);
+
+fn hnsw_metric(name: Option<String>) -> Result<hnsw::HnswMetric, NlpStatus> {
+    let name = name.as_deref().unwrap_or("cosine");
+    hnsw::HnswMetric::parse(name)
+        .ok_or_else(|| invalid(format!("unsupported HNSW metric: {}", name)))
+}
+
+/// `metric` is "cosine" (default), "l2" or "ip"; zero `m`, `ef_construction`
+/// and `ef_search` take the defaults of 16, 200 and 50
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_new(
+    dim: usize,
+    metric: *const c_char,
+    metric_len: usize,
+    m: u32,
+    ef_construction: u32,
+    ef_search: u32,
+    out: *mut *mut NlpHnswIndex,
+) -> NlpStatus {
+    guard(|| {
+        let metric = hnsw_metric(optional_text(metric, metric_len)?)?;
+        let defaults = hnsw::HnswParams::default();
+        let or_default = |value: u32, default: usize| match value {
+            0 => default,
+            value => value as usize,
+        };
+        let params = hnsw::HnswParams {
+            m: or_default(m, defaults.m),
+            ef_construction: or_default(ef_construction, defaults.ef_construction),
+            ef_search: or_default(ef_search, defaults.ef_search),
+            ..defaults
+        };
+        write(
+            out,
+            Box::into_raw(Box::new(NlpHnswIndex(hnsw::Hnsw::new(dim, metric, params)))),
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_free(index: *mut NlpHnswIndex) {
+    free(index)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_insert(
+    index: *mut NlpHnswIndex,
+    id: u32,
+    vector: *const f32,
+    vector_len: usize,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle_mut(index)?;
+        index
+            .0
+            .insert(id, array(vector, vector_len)?)
+            .map_err(invalid)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_delete(
+    index: *mut NlpHnswIndex,
+    id: u32,
+    deleted: *mut bool,
+) -> NlpStatus {
+    guard(|| write(deleted, handle_mut(index)?.0.delete(id)))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_size(index: *const NlpHnswIndex, size: *mut usize) -> NlpStatus {
+    guard(|| write(size, handle(index)?.0.len()))
+}
+
+/// The `k` approximate nearest vectors, closest first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_search(
+    index: *const NlpHnswIndex,
+    query: *const f32,
+    query_len: usize,
+    k: u32,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let found = handle(index)?
+            .0
+            .search(array(query, query_len)?, k as usize, None)
+            .map_err(invalid)?;
+        let found = self::hits(
+            found
+                .into_iter()
+                .map(|(id, distance)| (id, distance as f64)),
+        );
+        write_all(&found, hits, capacity, count)
+    })
+}
+
+/// Serialize the index into `buffer`, writing the full size to `len`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_serialize(
+    index: *const NlpHnswIndex,
+    buffer: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> NlpStatus {
+    guard(|| write_all(&handle(index)?.0.to_bytes(), buffer, capacity, len))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_hnsw_deserialize(
+    bytes: *const u8,
+    bytes_len: usize,
+    out: *mut *mut NlpHnswIndex,
+) -> NlpStatus {
+    guard(|| {
+        let index = hnsw::Hnsw::from_bytes(array(bytes, bytes_len)?).map_err(invalid)?;
+        write(out, Box::into_raw(Box::new(NlpHnswIndex(index))))
+    })
+}
+
+// ============================================================================
+// Quantized Vector Store - Hits carry the metric distance
+// ============================================================================
+
+pub struct NlpQuantizedStore(quantize::QuantizedStore);
+
+/// `metric` is "cosine" (default), "l2" or "ip"
+#[no_mangle]
+pub unsafe extern "C" fn nlp_quantized_new(
+    dim: usize,
+    metric: *const c_char,
+    metric_len: usize,
+    out: *mut *mut NlpQuantizedStore,
+) -> NlpStatus {
+    guard(|| {
+        let metric = hnsw_metric(optional_text(metric, metric_len)?)?;
+        let store = quantize::QuantizedStore::new(dim, metric);
+        write(out, Box::into_raw(Box::new(NlpQuantizedStore(store))))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_quantized_free(store: *mut NlpQuantizedStore) {
+    free(store)
+}
+
+/// Insert a vector, replacing any vector stored under the same id
+#[no_mangle]
+pub unsafe extern "C" fn nlp_quantized_insert(
+    store: *mut NlpQuantizedStore,
+    id: u32,
+    vector: *const f32,
+    vector_len: usize,
+) -> NlpStatus {
+    guard(|| {
+        let store = handle_mut(store)?;
+        store
+            .0
+            .insert(id, array(vector, vector_len)?)
+            .map_err(invalid)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_quantized_delete(
+    store: *mut NlpQuantizedStore,
+    id: u32,
+    deleted: *mut bool,
+) -> NlpStatus {
+    guard(|| write(deleted, handle_mut(store)?.0.delete(id)))
+}
+
+/// The `k` closest vectors by asymmetric int8 distance; a non-zero `oversample`
+/// shortlists `k * oversample` vectors by their sign bits first
+#[no_mangle]
+pub unsafe extern "C" fn nlp_quantized_search(
+    store: *const NlpQuantizedStore,
+    query: *const f32,
+    query_len: usize,
+    k: u32,
+    oversample: u32,
+    hits: *mut NlpHit,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let store = &handle(store)?.0;
+        let query = array(query, query_len)?;
+        let found = match oversample {
+            0 => store.search(query, k as usize),
+            oversample => store.search_binary(query, k as usize, oversample as usize),
+        }
+        .map_err(invalid)?;
+        let found = self::hits(
+            found
+                .into_iter()
+                .map(|(id, distance)| (id, distance as f64)),
+        );
+        write_all(&found, hits, capacity, count)
+    })
+}
+
+// ============================================================================
+// SymSpell - Spelling suggestions from a word frequency dictionary
+// ============================================================================
+
+pub struct NlpSymSpell {
+    index: crate::SymSpell,
+    /// Strings of the last lookup or segmentation, borrowed by the returned results
+    results: RefCell<Vec<String>>,
+}
+
+/// Zero `max_edit_distance` and `prefix_length` take the defaults of 2 and 7
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_new(
+    max_edit_distance: u32,
+    prefix_length: u32,
+    out: *mut *mut NlpSymSpell,
+) -> NlpStatus {
+    guard(|| {
+        let index = NlpSymSpell {
+            index: crate::SymSpell::new(nonzero(max_edit_distance), nonzero(prefix_length)),
+            results: RefCell::default(),
+        };
+        write(out, Box::into_raw(Box::new(index)))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_free(index: *mut NlpSymSpell) {
+    free(index)
+}
+
+/// Add a word or increase its frequency by `count`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_add_word(
+    index: *mut NlpSymSpell,
+    word: *const c_char,
+    word_len: usize,
+    count: f64,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle_mut(index)?;
+        index.index.add_word(text(word, word_len)?, Some(count));
+        Ok(())
+    })
+}
+
+/// Load "word count" lines, writing the number of words loaded to `loaded`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_load_dictionary(
+    index: *mut NlpSymSpell,
+    dictionary: *const c_char,
+    dictionary_len: usize,
+    loaded: *mut u32,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle_mut(index)?;
+        let words = index
+            .index
+            .load_dictionary(text(dictionary, dictionary_len)?);
+        write(loaded, words)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_size(
+    index: *const NlpSymSpell,
+    size: *mut usize,
+) -> NlpStatus {
+    guard(|| write(size, handle(index)?.index.size()))
+}
+
+/// `verbosity` is "top" (default), "closest" or "all"; a negative
+/// `max_edit_distance` takes the index's. Unknown verbosities return
+/// `NLP_STATUS_INVALID_ARGUMENT`
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_lookup(
+    index: *const NlpSymSpell,
+    input: *const c_char,
+    input_len: usize,
+    verbosity: *const c_char,
+    verbosity_len: usize,
+    max_edit_distance: i32,
+    suggestions: *mut NlpSuggestion,
+    capacity: usize,
+    count: *mut usize,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle(index)?;
+        let found = index
+            .index
+            .try_lookup(
+                text(input, input_len)?,
+                optional_text(verbosity, verbosity_len)?,
+                u32::try_from(max_edit_distance).ok(),
+            )
+            .map_err(invalid)?;
+        let mut owned = index.results.borrow_mut();
+        *owned = found.iter().map(|s| s.term.clone()).collect();
+        let found: Vec<NlpSuggestion> = owned
+            .iter()
+            .zip(&found)
+            .map(|(term, suggestion)| NlpSuggestion {
+                term: nlp_str(term),
+                distance: suggestion.distance,
+                count: suggestion.count,
+            })
+            .collect();
+        write_all(&found, suggestions, capacity, count)
+    })
+}
+
+/// Split text without spaces into its most probable words; a non-zero
+/// `max_edit_distance` also corrects each word
+#[no_mangle]
+pub unsafe extern "C" fn nlp_symspell_word_segmentation(
+    index: *const NlpSymSpell,
+    input: *const c_char,
+    input_len: usize,
+    max_edit_distance: u32,
+    out: *mut NlpSegmentation,
+) -> NlpStatus {
+    guard(|| {
+        let index = handle(index)?;
+        let result = index
+            .index
+            .word_segmentation(text(input, input_len)?, Some(max_edit_distance));
+        let mut owned = index.results.borrow_mut();
+        *owned = vec![result.segmented, result.corrected];
+        write(
+            out,
+            NlpSegmentation {
+                segmented: nlp_str(&owned[0]),
+                corrected: nlp_str(&owned[1]),
+                distance_sum: result.distance_sum,
+                log_probability_sum: result.log_probability_sum,
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn bytes(s: &str) -> (*const c_char, usize) {
+        (s.as_ptr() as *const c_char, s.len())
+    }
+
+    #[test]
+    fn test_committed_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/nlptools_distance.h"));
+        let committed = include_str!("../include/nlptools_distance.h");
+        assert!(
+            generated == committed,
+            "include/nlptools_distance.h is out of date, run `pnpm header:ffi`"
+        );
+    }
+
+    #[test]
+    fn test_scorers_match_wasm_exports() {
+        let (a, a_len) = bytes("kitten");
+        let (b, b_len) = bytes("sitting");
+        let mut distance = 0u32;
+        let mut score = 0f64;
+        unsafe {
+            assert_eq!(
+                nlp_levenshtein(a, a_len, b, b_len, ptr::null(), 0, &mut distance),
+                NlpStatus::Ok
+            );
+            assert_eq!(distance, crate::levenshtein("kitten", "sitting", None));
+
+            let (p, p_len) = bytes("case_fold");
+            assert_eq!(
+                nlp_wratio(a, a_len, b, b_len, p, p_len, &mut score),
+                NlpStatus::Ok
+            );
+            assert_eq!(
+                score,
+                crate::wratio("kitten", "sitting", Some("case_fold".into()))
+            );
+
+            let (algorithm, algorithm_len) = bytes("jaro");
+            let status = nlp_compare(
+                a,
+                a_len,
+                b,
+                b_len,
+                algorithm,
+                algorithm_len,
+                ptr::null(),
+                0,
+                &mut score,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(score, crate::compare("kitten", "sitting", "jaro", None));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let invalid = [0xffu8, 0xfe];
+        let mut score = 0f64;
+        let mut len = 0usize;
+        unsafe {
+            let status = nlp_ratio(
+                invalid.as_ptr() as *const c_char,
+                2,
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                &mut score,
+            );
+            assert_eq!(status, NlpStatus::InvalidUtf8);
+            let status = nlp_ratio(ptr::null(), 3, ptr::null(), 0, ptr::null(), 0, &mut score);
+            assert_eq!(status, NlpStatus::NullPointer);
+            let message = nlp_last_error(&mut len);
+            assert_eq!(
+                CStr::from_ptr(message).to_str(),
+                Ok("null string with non-zero length")
+            );
+            assert_eq!(
+                len,
+                CStr::from_ptr(nlp_last_error(ptr::null_mut()))
+                    .to_bytes()
+                    .len()
+            );
+
+            let status = nlp_ratio(
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                ptr::null_mut(),
+            );
+            assert_eq!(status, NlpStatus::NullPointer);
+
+            let mut index = ptr::null_mut();
+            let (metric, metric_len) = bytes("hamming");
+            assert_eq!(
+                nlp_hnsw_new(4, metric, metric_len, 0, 0, 0, &mut index),
+                NlpStatus::InvalidArgument
+            );
+            assert!(index.is_null());
+
+            let (p, p_len) = bytes("case_fold,lowercase");
+            let status = nlp_ratio(ptr::null(), 0, ptr::null(), 0, p, p_len, &mut score);
+            assert_eq!(status, NlpStatus::InvalidArgument);
+            assert_eq!(
+                CStr::from_ptr(nlp_last_error(ptr::null_mut())).to_str(),
+                Ok("unknown preprocessing option \"lowercase\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_cdist() {
+        let words = ["apple", "apples", "banana"];
+        let items: Vec<NlpStr> = words
+            .iter()
+            .map(|w| NlpStr {
+                ptr: w.as_ptr() as *const c_char,
+                len: w.len(),
+            })
+            .collect();
+        let mut scores = vec![0f64; 9];
+        let mut count = 0usize;
+        let (algorithm, algorithm_len) = bytes("ratio");
+        unsafe {
+            let cdist = |scores: *mut f64, capacity: usize, count: &mut usize| {
+                nlp_cdist(
+                    items.as_ptr(),
+                    3,
+                    items.as_ptr(),
+                    3,
+                    algorithm,
+                    algorithm_len,
+                    ptr::null(),
+                    0,
+                    scores,
+                    capacity,
+                    count,
+                )
+            };
+            // Too small a buffer reports the needed length and fills what fits
+            assert_eq!(
+                cdist(scores.as_mut_ptr(), 4, &mut count),
+                NlpStatus::BufferTooSmall
+            );
+            assert_eq!(count, 9);
+            assert_eq!(scores[4..], [0.0; 5]);
+            assert_eq!(cdist(scores.as_mut_ptr(), 9, &mut count), NlpStatus::Ok);
+        }
+        let owned: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            scores,
+            crate::try_cdist(owned.clone(), owned, "ratio", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bm25_handle() {
+        let mut index = ptr::null_mut();
+        let mut id = 0u32;
+        let mut hits = [NlpHit { id: 0, score: 0.0 }; 1];
+        let mut count = 0usize;
+        unsafe {
+            assert_eq!(
+                nlp_bm25_new(
+                    ptr::null(),
+                    0,
+                    f64::NAN,
+                    f64::NAN,
+                    f64::NAN,
+                    ptr::null(),
+                    0,
+                    0,
+                    0,
+                    ptr::null(),
+                    0,
+                    &mut index
+                ),
+                NlpStatus::Ok
+            );
+            for document in ["the quick brown fox", "lazy dogs sleep", "quick quick fox"].iter() {
+                let (d, d_len) = bytes(document);
+                assert_eq!(nlp_bm25_add(index, d, d_len, &mut id), NlpStatus::Ok);
+            }
+            assert_eq!(id, 2);
+
+            // Two documents match but the buffer holds one
+            let (q, q_len) = bytes("quick fox");
+            let status = nlp_bm25_query(index, q, q_len, 10, hits.as_mut_ptr(), 1, &mut count);
+            assert_eq!(status, NlpStatus::BufferTooSmall);
+            assert_eq!(count, 2);
+            assert_eq!(hits[0].id, 2);
+
+            // With b = 0 document length no longer matters
+            let mut flat = ptr::null_mut();
+            let (v, v_len) = bytes("bm25");
+            let status = nlp_bm25_new(
+                v,
+                v_len,
+                2.0,
+                0.0,
+                f64::NAN,
+                ptr::null(),
+                0,
+                0,
+                0,
+                ptr::null(),
+                0,
+                &mut flat,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            for document in ["fox", "fox and many other words"].iter() {
+                let (d, d_len) = bytes(document);
+                assert_eq!(nlp_bm25_add(flat, d, d_len, &mut id), NlpStatus::Ok);
+            }
+            let mut both = [NlpHit { id: 0, score: 0.0 }; 2];
+            let (q, q_len) = bytes("fox");
+            let status = nlp_bm25_query(flat, q, q_len, 10, both.as_mut_ptr(), 2, &mut count);
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(both[0].score, both[1].score);
+            nlp_bm25_free(flat);
+            nlp_bm25_free(index);
+        }
+    }
+
+    #[test]
+    fn test_tfidf_handles() {
+        let corpus: Vec<NlpStr> = ["the quick brown fox", "the lazy dog", "quick thinking"]
+            .iter()
+            .map(|s| nlp_str(s))
+            .collect();
+        let (a, a_len) = bytes("quick fox");
+        let (b, b_len) = bytes("the quick brown fox");
+        let mut model = ptr::null_mut();
+        let mut score = 0f64;
+        let mut size = 0usize;
+        unsafe {
+            let status = nlp_tfidf_new(
+                corpus.as_ptr(),
+                corpus.len(),
+                ptr::null(),
+                0,
+                0,
+                0,
+                false,
+                true,
+                ptr::null(),
+                0,
+                &mut model,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(nlp_tfidf_vocabulary_size(model, &mut size), NlpStatus::Ok);
+            assert_eq!(size, 7);
+            let status = nlp_tfidf_similarity(model, a, a_len, b, b_len, &mut score);
+            assert_eq!(status, NlpStatus::Ok);
+            assert!(score > 0.0 && score < 1.0);
+
+            let mut hits = [NlpHit { id: 0, score: 0.0 }; 2];
+            let mut count = 0usize;
+            let status = nlp_tfidf_vectorize(model, a, a_len, hits.as_mut_ptr(), 2, &mut count);
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(count, 2);
+            let norm: f64 = hits.iter().map(|hit| hit.score * hit.score).sum();
+            assert!((norm - 1.0).abs() < 1e-9);
+
+            // Size the buffer with a first call, then round-trip the model
+            let mut len = 0usize;
+            let status = nlp_tfidf_serialize(model, ptr::null_mut(), 0, &mut len);
+            assert_eq!(status, NlpStatus::BufferTooSmall);
+            let mut json = vec![0 as c_char; len];
+            let status = nlp_tfidf_serialize(model, json.as_mut_ptr(), len, &mut len);
+            assert_eq!(status, NlpStatus::Ok);
+            let mut restored = ptr::null_mut();
+            let status = nlp_tfidf_deserialize(json.as_ptr(), len, &mut restored);
+            assert_eq!(status, NlpStatus::Ok);
+            let mut restored_score = 0f64;
+            nlp_tfidf_similarity(restored, a, a_len, b, b_len, &mut restored_score);
+            assert_eq!(restored_score, score);
+            nlp_tfidf_free(restored);
+            nlp_tfidf_free(model);
+
+            let (json, json_len) = bytes("{}");
+            let status = nlp_tfidf_deserialize(json, json_len, &mut restored);
+            assert_eq!(status, NlpStatus::InvalidArgument);
+
+            let mut soft = ptr::null_mut();
+            let status = nlp_soft_tfidf_new(
+                corpus.as_ptr(),
+                corpus.len(),
+                ptr::null(),
+                0,
+                f64::NAN,
+                ptr::null(),
+                0,
+                &mut soft,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            let (c, c_len) = bytes("quikc fox");
+            let status = nlp_soft_tfidf_similarity(soft, a, a_len, c, c_len, &mut score);
+            assert_eq!(status, NlpStatus::Ok);
+            let expected = crate::SoftTfIdf::try_new(
+                vec![
+                    "the quick brown fox".into(),
+                    "the lazy dog".into(),
+                    "quick thinking".into(),
+                ],
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .similarity("quick fox", "quikc fox");
+            assert_eq!(score, expected);
+            nlp_soft_tfidf_free(soft);
+        }
+    }
+
+    #[test]
+    fn test_term_handles() {
+        let terms: Vec<NlpStr> = ["book", "books", "cake", "boo"]
+            .iter()
+            .map(|s| nlp_str(s))
+            .collect();
+        let empty = NlpTermMatch {
+            term: nlp_str(""),
+            distance: 0,
+        };
+        let mut matches = [empty; 4];
+        let mut count = 0usize;
+        let mut inserted = false;
+        let (q, q_len) = bytes("bok");
+        unsafe {
+            let mut tree = ptr::null_mut();
+            assert_eq!(nlp_bktree_new(ptr::null(), 0, &mut tree), NlpStatus::Ok);
+            for term in &terms {
+                let status = nlp_bktree_insert(tree, term.ptr, term.len, &mut inserted);
+                assert_eq!(status, NlpStatus::Ok);
+                assert!(inserted);
+            }
+            nlp_bktree_insert(tree, terms[0].ptr, terms[0].len, &mut inserted);
+            assert!(!inserted);
+            let status = nlp_bktree_within(tree, q, q_len, 1, matches.as_mut_ptr(), 4, &mut count);
+            assert_eq!(status, NlpStatus::Ok);
+            let found: Vec<(&str, u32)> = matches[..count]
+                .iter()
+                .map(|m| (text(m.term.ptr, m.term.len).unwrap(), m.distance))
+                .collect();
+            assert_eq!(found, [("boo", 1), ("book", 1)]);
+            let status = nlp_bktree_nearest(tree, q, q_len, 1, matches.as_mut_ptr(), 4, &mut count);
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!((count, matches[0].distance), (1, 1));
+            nlp_bktree_free(tree);
+
+            let (metric, metric_len) = bytes("cosine");
+            assert_eq!(
+                nlp_bktree_new(metric, metric_len, &mut tree),
+                NlpStatus::InvalidArgument
+            );
+
+            let mut dictionary = ptr::null_mut();
+            let status = nlp_termdict_new(terms.as_ptr(), terms.len(), &mut dictionary);
+            assert_eq!(status, NlpStatus::Ok);
+            let status = nlp_termdict_fuzzy_search(
+                dictionary,
+                q,
+                q_len,
+                1,
+                false,
+                matches.as_mut_ptr(),
+                4,
+                &mut count,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            let found: Vec<(&str, u32)> = matches[..count]
+                .iter()
+                .map(|m| (text(m.term.ptr, m.term.len).unwrap(), m.distance))
+                .collect();
+            assert_eq!(found, [("boo", 1), ("book", 1)]);
+            let status = nlp_termdict_fuzzy_search(
+                dictionary,
+                q,
+                q_len,
+                4,
+                false,
+                matches.as_mut_ptr(),
+                4,
+                &mut count,
+            );
+            assert_eq!(status, NlpStatus::InvalidArgument);
+            nlp_termdict_free(dictionary);
+        }
+    }
+
+    #[test]
+    fn test_symspell_handle() {
+        let mut index = ptr::null_mut();
+        let mut loaded = 0u32;
+        let mut count = 0usize;
+        let mut suggestions = [NlpSuggestion {
+            term: nlp_str(""),
+            distance: 0,
+            count: 0.0,
+        }; 2];
+        unsafe {
+            assert_eq!(nlp_symspell_new(0, 0, &mut index), NlpStatus::Ok);
+            let (d, d_len) = bytes("the 500\nquick 100\nbrown 80\nfox 60\n");
+            let status = nlp_symspell_load_dictionary(index, d, d_len, &mut loaded);
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(loaded, 4);
+
+            let (q, q_len) = bytes("quikc");
+            let status = nlp_symspell_lookup(
+                index,
+                q,
+                q_len,
+                ptr::null(),
+                0,
+                -1,
+                suggestions.as_mut_ptr(),
+                2,
+                &mut count,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!(count, 1);
+            let term = text(suggestions[0].term.ptr, suggestions[0].term.len).unwrap();
+            assert_eq!((term, suggestions[0].distance), ("quick", 1));
+            let (v, v_len) = bytes("best");
+            let status = nlp_symspell_lookup(
+                index,
+                q,
+                q_len,
+                v,
+                v_len,
+                -1,
+                suggestions.as_mut_ptr(),
+                2,
+                &mut count,
+            );
+            assert_eq!(status, NlpStatus::InvalidArgument);
+
+            let mut segmentation = NlpSegmentation {
+                segmented: nlp_str(""),
+                corrected: nlp_str(""),
+                distance_sum: 0,
+                log_probability_sum: 0.0,
+            };
+            let (t, t_len) = bytes("thequickbrwnfox");
+            let status = nlp_symspell_word_segmentation(index, t, t_len, 1, &mut segmentation);
+            assert_eq!(status, NlpStatus::Ok);
+            let corrected = text(segmentation.corrected.ptr, segmentation.corrected.len).unwrap();
+            assert_eq!(corrected, "the quick brown fox");
+            nlp_symspell_free(index);
+        }
+    }
+
+    #[test]
+    fn test_hnsw_handle() {
+        let mut index = ptr::null_mut();
+        let mut hits = [NlpHit { id: 0, score: 0.0 }; 2];
+        let mut count = 0usize;
+        let vectors = [[1.0f32, 0.0], [0.0, 1.0], [0.7, 0.7]];
+        unsafe {
+            assert_eq!(
+                nlp_hnsw_new(2, ptr::null(), 0, 0, 0, 0, &mut index),
+                NlpStatus::Ok
+            );
+            for (id, vector) in vectors.iter().enumerate() {
+                assert_eq!(
+                    nlp_hnsw_insert(index, id as u32, vector.as_ptr(), 2),
+                    NlpStatus::Ok
+                );
+            }
+            assert_eq!(
+                nlp_hnsw_insert(index, 9, vectors[0].as_ptr(), 1),
+                NlpStatus::InvalidArgument
+            );
+
+            // Size the buffer with a first call, then round-trip the bytes
+            let mut len = 0usize;
+            assert_eq!(
+                nlp_hnsw_serialize(index, ptr::null_mut(), 0, &mut len),
+                NlpStatus::BufferTooSmall
+            );
+            let mut buffer = vec![0u8; len];
+            assert_eq!(
+                nlp_hnsw_serialize(index, buffer.as_mut_ptr(), len, &mut len),
+                NlpStatus::Ok
+            );
+            nlp_hnsw_free(index);
+
+            let mut restored = ptr::null_mut();
+            assert_eq!(
+                nlp_hnsw_deserialize(buffer.as_ptr(), len, &mut restored),
+                NlpStatus::Ok
+            );
+            let query = [1.0f32, 0.1];
+            let status = nlp_hnsw_search(
+                restored,
+                query.as_ptr(),
+                2,
+                2,
+                hits.as_mut_ptr(),
+                2,
+                &mut count,
+            );
+            assert_eq!(status, NlpStatus::Ok);
+            assert_eq!((count, hits[0].id, hits[1].id), (2, 0, 2));
+            nlp_hnsw_free(restored);
+        }
+    }
+}
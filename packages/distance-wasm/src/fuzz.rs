@@ -0,0 +1,530 @@
+// Composite fuzzy matching scorers compatible with RapidFuzz / fuzzywuzzy
+// All scores are built on the normalized indel similarity and returned in
+// [0, 1]; multiply by 100 to get the RapidFuzz scale
+
+use crate::indel::{self, Pattern};
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+/// Whitespace separated tokens in sorted order
+fn sorted_tokens(s: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = s.split_whitespace().collect();
+    tokens.sort_unstable();
+    tokens
+}
+
+/// Intersection and both differences of two deduplicated token sets
+struct Decomposition<'a> {
+    intersection: Vec<&'a str>,
+    diff_ab: Vec<&'a str>,
+    diff_ba: Vec<&'a str>,
+}
+
+fn decompose<'a>(a: &[&'a str], b: &[&'a str]) -> Decomposition<'a> {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.dedup();
+    b.dedup();
+
+    let mut intersection = Vec::new();
+    let mut diff_ab = Vec::new();
+    let mut diff_ba = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(b[j]) {
+            std::cmp::Ordering::Less => {
+                diff_ab.push(a[i]);
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                diff_ba.push(b[j]);
+                j += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                intersection.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    diff_ab.extend_from_slice(&a[i..]);
+    diff_ba.extend_from_slice(&b[j..]);
+
+    Decomposition {
+        intersection,
+        diff_ab,
+        diff_ba,
+    }
+}
+
+fn joined_len(tokens: &[&str]) -> usize {
+    if tokens.is_empty() {
+        return 0;
+    }
+    tokens.iter().map(|t| t.chars().count()).sum::<usize>() + tokens.len() - 1
+}
+
+/// Best of the set based ratios shared by `token_set_ratio` and `token_ratio`
+///
+/// Compares `sect + diff_ab` against `sect + diff_ba`, and the intersection
+/// against each of them. Only the differences need an actual distance since
+/// the intersection is common to both sides.
+fn set_ratio(decomposition: &Decomposition) -> f64 {
+    let diff_ab = chars(&decomposition.diff_ab.join(" "));
+    let diff_ba = chars(&decomposition.diff_ba.join(" "));
+    let ab_len = diff_ab.len();
+    let ba_len = diff_ba.len();
+    let sect_len = joined_len(&decomposition.intersection);
+    let sep = (sect_len != 0) as usize;
+
+    let sect_ab_len = sect_len + sep + ab_len;
+    let sect_ba_len = sect_len + sep + ba_len;
+
+    let dist = indel::indel_distance(&diff_ab, &diff_ba);
+    let result = indel::normalized(dist, sect_ab_len + sect_ba_len);
+
+    if sect_len == 0 {
+        return result;
+    }
+
+    let sect_ab_ratio = indel::normalized(sep + ab_len, sect_len + sect_ab_len);
+    let sect_ba_ratio = indel::normalized(sep + ba_len, sect_len + sect_ba_len);
+
+    result.max(sect_ab_ratio).max(sect_ba_ratio)
+}
+
+/// Normalized indel similarity of the two strings
+pub fn ratio(a: &str, b: &str) -> f64 {
+    indel::indel_similarity(&chars(a), &chars(b))
+}
+
+/// Score of a partial match and where it was found, in character offsets
+///
+/// `src` refers to the first string passed in and `dest` to the second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Alignment {
+    pub score: f64,
+    pub src_start: usize,
+    pub src_end: usize,
+    pub dest_start: usize,
+    pub dest_end: usize,
+}
+
+impl Alignment {
+    fn swapped(self) -> Alignment {
+        Alignment {
+            score: self.score,
+            src_start: self.dest_start,
+            src_end: self.dest_end,
+            dest_start: self.src_start,
+            dest_end: self.src_end,
+        }
+    }
+}
+
+/// Best full length window of the haystack for the needle
+///
+/// The indel distance of neighbouring windows differs by at most 2 per
+/// shifted position, so a range of windows is only split and searched
+/// further when its best possible distance, bounded by the distances at
+/// both ends, could still improve on the best window found so far.
+fn best_full_window(pattern: &Pattern, len1: usize, haystack: &[char]) -> Option<(usize, usize)> {
+    let count = haystack.len() - len1;
+    let mut scores: Vec<Option<usize>> = vec![None; count];
+    let mut cutoff = 2 * len1;
+    let mut best: Option<(usize, usize)> = None;
+    let mut windows = vec![(0, count - 1)];
+
+    while !windows.is_empty() {
+        let mut next = Vec::new();
+
+        for &(first, last) in &windows {
+            for &start in &[first, last] {
+                if scores[start].is_none() {
+                    let dist = pattern.distance(&haystack[start..start + len1]);
+                    scores[start] = Some(dist);
+                    if dist < cutoff {
+                        cutoff = dist;
+                        best = Some((start, dist));
+                        if dist == 0 {
+                            return best;
+                        }
+                    }
+                }
+            }
+
+            let cell_diff = last - first;
+            if cell_diff <= 1 {
+                continue;
+            }
+
+            let (a, b) = (scores[first].unwrap_or(0), scores[last].unwrap_or(0));
+            let known_edits = a.max(b) - a.min(b);
+            let max_improvement = (cell_diff - known_edits / 2) / 2 * 2;
+            if (a.min(b) as isize - max_improvement as isize) < cutoff as isize {
+                let center = cell_diff / 2;
+                next.push((first, first + center));
+                next.push((first + center, last));
+            }
+        }
+
+        windows = next;
+    }
+
+    best
+}
+
+/// Ratio of the needle against its best matching window of the haystack
+fn partial_ratio_impl(needle: &[char], haystack: &[char]) -> Alignment {
+    let (len1, len2) = (needle.len(), haystack.len());
+    let pattern = Pattern::new(needle);
+    let mut res = Alignment {
+        score: 0.0,
+        src_start: 0,
+        src_end: len1,
+        dest_start: 0,
+        dest_end: len1,
+    };
+
+    if len2 > len1 {
+        if let Some((start, dist)) = best_full_window(&pattern, len1, haystack) {
+            res.score = indel::normalized(dist, 2 * len1);
+            res.dest_start = start;
+            res.dest_end = start + len1;
+            if res.score == 1.0 {
+                return res;
+            }
+        }
+    }
+
+    // Windows hanging over the start of the haystack
+    for i in 1..len1 {
+        if !pattern.contains(haystack[i - 1]) {
+            continue;
+        }
+        let score = pattern.similarity(&haystack[..i]);
+        if score > res.score {
+            res.score = score;
+            res.dest_start = 0;
+            res.dest_end = i;
+            if score == 1.0 {
+                return res;
+            }
+        }
+    }
+
+    // Windows hanging over the end of the haystack
+    for i in len2 - len1..len2 {
+        if !pattern.contains(haystack[i]) {
+            continue;
+        }
+        let score = pattern.similarity(&haystack[i..]);
+        if score > res.score {
+            res.score = score;
+            res.dest_start = i;
+            res.dest_end = len2;
+            if score == 1.0 {
+                return res;
+            }
+        }
+    }
+
+    res
+}
+
+/// Partial ratio of two character slices together with the matching ranges
+pub fn partial_ratio_alignment_chars(a: &[char], b: &[char]) -> Alignment {
+    if a.len() > b.len() {
+        return partial_ratio_alignment_chars(b, a).swapped();
+    }
+    if a.is_empty() || b.is_empty() {
+        let score = if a.len() == b.len() { 1.0 } else { 0.0 };
+        return Alignment {
+            score,
+            src_start: 0,
+            src_end: a.len(),
+            dest_start: 0,
+            dest_end: a.len(),
+        };
+    }
+
+    let res = partial_ratio_impl(a, b);
+    if res.score < 1.0 && a.len() == b.len() {
+        // Equal lengths: either string may serve as the needle
+        let res2 = partial_ratio_impl(b, a);
+        if res2.score > res.score {
+            return res2.swapped();
+        }
+    }
+    res
+}
+
+/// Ratio of the shorter string against the best matching substring of the longer one
+pub fn partial_ratio(a: &str, b: &str) -> f64 {
+    partial_ratio_alignment_chars(&chars(a), &chars(b)).score
+}
+
+/// Partial ratio together with where the strings matched, in character offsets
+pub fn partial_ratio_alignment(a: &str, b: &str) -> Alignment {
+    partial_ratio_alignment_chars(&chars(a), &chars(b))
+}
+
+/// Ratio after sorting the whitespace separated tokens of both strings
+pub fn token_sort_ratio(a: &str, b: &str) -> f64 {
+    ratio(&sorted_tokens(a).join(" "), &sorted_tokens(b).join(" "))
+}
+
+/// Ratio based on the shared and differing token sets of both strings
+///
+/// Returns 1.0 when the tokens of one string are a subset of the other and
+/// 0.0 when either string has no tokens.
+pub fn token_set_ratio(a: &str, b: &str) -> f64 {
+    let tokens_a = sorted_tokens(a);
+    let tokens_b = sorted_tokens(b);
+    if tokens_a.is_empty() || tokens_b.is_empty() {
+        return 0.0;
+    }
+
+    let decomposition = decompose(&tokens_a, &tokens_b);
+    if !decomposition.intersection.is_empty()
+        && (decomposition.diff_ab.is_empty() || decomposition.diff_ba.is_empty())
+    {
+        return 1.0;
+    }
+
+    set_ratio(&decomposition)
+}
+
+/// Partial ratio after sorting the whitespace separated tokens of both strings
+pub fn partial_token_sort_ratio(a: &str, b: &str) -> f64 {
+    partial_ratio(&sorted_tokens(a).join(" "), &sorted_tokens(b).join(" "))
+}
+
+/// Maximum of `token_sort_ratio` and `token_set_ratio`
+fn token_ratio(a: &str, b: &str) -> f64 {
+    let tokens_a = sorted_tokens(a);
+    let tokens_b = sorted_tokens(b);
+    let decomposition = decompose(&tokens_a, &tokens_b);
+
+    if !decomposition.intersection.is_empty()
+        && (decomposition.diff_ab.is_empty() || decomposition.diff_ba.is_empty())
+    {
+        return 1.0;
+    }
+
+    let sort_ratio = ratio(&tokens_a.join(" "), &tokens_b.join(" "));
+    sort_ratio.max(set_ratio(&decomposition))
+}
+
+/// Maximum of `partial_token_sort_ratio` and the partial ratio of the token differences
+fn partial_token_ratio(a: &str, b: &str) -> f64 {
+    let tokens_a = sorted_tokens(a);
+    let tokens_b = sorted_tokens(b);
+    let decomposition = decompose(&tokens_a, &tokens_b);
+
+    // Any common word gives a perfect partial match
+    if !decomposition.intersection.is_empty() {
+        return 1.0;
+    }
+
+    let result = partial_ratio(&tokens_a.join(" "), &tokens_b.join(" "));
+    if tokens_a.len() == decomposition.diff_ab.len()
+        && tokens_b.len() == decomposition.diff_ba.len()
+    {
+        return result;
+    }
+
+    result.max(partial_ratio(
+        &decomposition.diff_ab.join(" "),
+        &decomposition.diff_ba.join(" "),
+    ))
+}
+
+/// Weighted ratio combining the other scorers depending on the length ratio
+///
+/// Inputs with no words, empty or only whitespace, score 0.
+pub fn wratio(a: &str, b: &str) -> f64 {
+    const UNBASE_SCALE: f64 = 0.95;
+
+    if a.trim().is_empty() || b.trim().is_empty() {
+        return 0.0;
+    }
+    let len1 = a.chars().count();
+    let len2 = b.chars().count();
+
+    let len_ratio = len1.max(len2) as f64 / len1.min(len2) as f64;
+    let end_ratio = ratio(a, b);
+
+    if len_ratio < 1.5 {
+        return end_ratio.max(token_ratio(a, b) * UNBASE_SCALE);
+    }
+
+    let partial_scale = if len_ratio < 8.0 { 0.9 } else { 0.6 };
+    let end_ratio = end_ratio.max(partial_ratio(a, b) * partial_scale);
+    end_ratio.max(partial_token_ratio(a, b) * UNBASE_SCALE * partial_scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values from RapidFuzz 3.x, divided by 100
+    fn assert_score(actual: f64, rapidfuzz: f64) {
+        assert!(
+            (actual * 100.0 - rapidfuzz).abs() < 1e-4,
+            "{} != {}",
+            actual * 100.0,
+            rapidfuzz
+        );
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert_score(
+            ratio("this is a test", "this is a test!"),
+            96.55172413793103,
+        );
+        assert_score(
+            ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            90.9090909090909,
+        );
+        assert_score(ratio("YANKEES", "NEW YORK YANKEES"), 60.86956521739131);
+        assert_score(ratio("", ""), 100.0);
+        assert_score(ratio("abc", ""), 0.0);
+    }
+
+    #[test]
+    fn test_partial_ratio() {
+        assert_score(partial_ratio("this is a test", "this is a test!"), 100.0);
+        assert_score(partial_ratio("YANKEES", "NEW YORK YANKEES"), 100.0);
+        // Windows overhanging the start count too, unlike fuzzywuzzy (69)
+        assert_score(
+            partial_ratio("NEW YORK METS", "NEW YORK YANKEES"),
+            81.81818181818181,
+        );
+        assert_score(
+            partial_ratio("a certain string", "cetain"),
+            83.33333333333334,
+        );
+        assert_score(partial_ratio("", ""), 100.0);
+        assert_score(partial_ratio("", "abc"), 0.0);
+    }
+
+    #[test]
+    fn test_partial_ratio_equal_lengths() {
+        // Both strings are tried as the needle when lengths are equal
+        assert_eq!(partial_ratio("abcd", "bcda"), partial_ratio("bcda", "abcd"));
+    }
+
+    #[test]
+    fn test_partial_ratio_alignment() {
+        let res = partial_ratio_alignment("a certain string", "cetain");
+        assert_score(res.score, 83.33333333333334);
+        assert_eq!(
+            (res.src_start, res.src_end, res.dest_start, res.dest_end),
+            (2, 8, 0, 6)
+        );
+
+        let res = partial_ratio_alignment("cetain", "a certain string");
+        assert_eq!(
+            (res.src_start, res.src_end, res.dest_start, res.dest_end),
+            (0, 6, 2, 8)
+        );
+
+        let res = partial_ratio_alignment("YANKEES", "NEW YORK YANKEES");
+        assert_score(res.score, 100.0);
+        assert_eq!((res.dest_start, res.dest_end), (9, 16));
+    }
+
+    #[test]
+    fn test_partial_ratio_matches_exhaustive_search() {
+        // The pruned window search must find the same score as trying every window
+        let haystack: Vec<char> = "the quick brown fox jumps over the lazy dog near the riverbank"
+            .chars()
+            .collect();
+        for needle in &["lazy cat", "quack", "brown dog", "river", "xyz", "the"] {
+            let needle: Vec<char> = needle.chars().collect();
+            let pattern = Pattern::new(&needle);
+            let mut best = 0.0f64;
+            for start in 0..haystack.len() {
+                for end in start + 1..=haystack.len().min(start + needle.len()) {
+                    if end - start == needle.len() || start == 0 || end == haystack.len() {
+                        best = best.max(pattern.similarity(&haystack[start..end]));
+                    }
+                }
+            }
+            let res = partial_ratio_alignment_chars(&needle, &haystack);
+            assert!((res.score - best).abs() < 1e-12, "{:?}", needle);
+            let window = &haystack[res.dest_start..res.dest_end];
+            assert!((pattern.similarity(window) - res.score).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_token_sort_ratio() {
+        assert_score(
+            token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            100.0,
+        );
+        assert_score(
+            token_sort_ratio(
+                "New York Mets vs Atlanta Braves",
+                "Atlanta Braves vs New York Mets",
+            ),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn test_token_set_ratio() {
+        assert_score(
+            token_set_ratio("fuzzy was a bear", "fuzzy fuzzy was a bear"),
+            100.0,
+        );
+        assert_score(
+            token_set_ratio(
+                "mariners vs angels",
+                "los angeles angels of anaheim at seattle mariners",
+            ),
+            90.9090909090909,
+        );
+        assert_score(token_set_ratio("", ""), 0.0);
+        assert_score(token_set_ratio("abc", "   "), 0.0);
+    }
+
+    #[test]
+    fn test_partial_token_sort_ratio() {
+        assert_score(
+            partial_token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            100.0,
+        );
+        assert_score(
+            partial_token_sort_ratio(
+                "new york mets vs atlanta braves",
+                "atlanta braves vs new york mets",
+            ),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn test_wratio() {
+        assert_score(wratio("this is a test", "this is a new test!!!"), 85.5);
+        assert_score(
+            wratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            95.0,
+        );
+        assert_score(wratio("", "abc"), 0.0);
+    }
+
+    #[test]
+    fn test_wratio_whitespace_only() {
+        assert_eq!(wratio("   ", "abc"), 0.0);
+        assert_eq!(wratio("  ", "  "), 0.0);
+        assert_eq!(wratio("\t\n", "a b c d e f g h i j k"), 0.0);
+        assert!(wratio(" a ", "a") > 0.0);
+    }
+}
@@ -0,0 +1,732 @@
+// HNSW - hierarchical navigable small world graphs for approximate k-NN
+// Following Malkov and Yashunin: each vector is linked to its nearest
+// neighbours on a random number of layers, and searches descend greedily from
+// the sparse top layer before a beam search on the dense bottom layer
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::vector::dot;
+
+/// Distance between stored vectors; smaller is closer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HnswMetric {
+    /// One minus the cosine similarity; vectors are normalized on insert
+    Cosine,
+    /// Euclidean distance
+    L2,
+    /// Negated dot product
+    InnerProduct,
+}
+
+impl HnswMetric {
+    pub fn parse(name: &str) -> Option<HnswMetric> {
+        match name.to_lowercase().as_str() {
+            "cosine" => Some(HnswMetric::Cosine),
+            "l2" | "euclidean" => Some(HnswMetric::L2),
+            "ip" | "dot" | "inner_product" | "inner-product" => Some(HnswMetric::InnerProduct),
+            _ => None,
+        }
+    }
+
+    fn code(self) -> u8 {
+        match self {
+            HnswMetric::Cosine => 0,
+            HnswMetric::L2 => 1,
+            HnswMetric::InnerProduct => 2,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<HnswMetric> {
+        match code {
+            0 => Some(HnswMetric::Cosine),
+            1 => Some(HnswMetric::L2),
+            2 => Some(HnswMetric::InnerProduct),
+            _ => None,
+        }
+    }
+
+    fn distance(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            HnswMetric::Cosine => 1.0 - dot(a, b),
+            HnswMetric::L2 => crate::vector::euclidean(a, b),
+            HnswMetric::InnerProduct => -dot(a, b),
+        }
+    }
+}
+
+/// Graph construction and search parameters
+#[derive(Clone, Debug, PartialEq)]
+pub struct HnswParams {
+    /// Links per node on the upper layers; twice as many on the bottom layer
+    pub m: usize,
+    /// Beam width while inserting
+    pub ef_construction: usize,
+    /// Beam width while searching, raised to `k` when smaller
+    pub ef_search: usize,
+    /// Seed of the layer assignment generator
+    pub seed: u64,
+}
+
+impl Default for HnswParams {
+    fn default() -> HnswParams {
+        HnswParams {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 50,
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+/// Candidate node and its distance to the query, ordered by distance
+#[derive(Clone, Copy, Debug)]
+struct Scored(f32, usize);
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Scored) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Scored) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Scored) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+struct Node {
+    id: u32,
+    vector: Vec<f32>,
+    /// Neighbour node indices on each layer up to the node's level
+    links: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+pub struct Hnsw {
+    params: HnswParams,
+    metric: HnswMetric,
+    dim: usize,
+    nodes: Vec<Node>,
+    /// Live node index of every id
+    ids: HashMap<u32, usize>,
+    entry: Option<usize>,
+    rng: u64,
+}
+
+impl Hnsw {
+    pub fn new(dim: usize, metric: HnswMetric, params: HnswParams) -> Hnsw {
+        let rng = params.seed;
+        Hnsw {
+            params: HnswParams {
+                m: params.m.max(2),
+                ef_construction: params.ef_construction.max(1),
+                ef_search: params.ef_search.max(1),
+                ..params
+            },
+            metric,
+            dim,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            entry: None,
+            rng,
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of live (not deleted) vectors
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        self.params.ef_search = ef_search.max(1);
+    }
+
+    pub fn ef_search(&self) -> usize {
+        self.params.ef_search
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            2 * self.params.m
+        } else {
+            self.params.m
+        }
+    }
+
+    /// Draw a level with P(level >= l) = M^-l, from a splitmix64 stream
+    fn random_level(&mut self) -> usize {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        // Uniform in (0, 1]
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        (-uniform.ln() / (self.params.m as f64).ln()) as usize
+    }
+
+    fn prepare(&self, vector: &[f32]) -> Result<Vec<f32>, String> {
+        if vector.len() != self.dim {
+            return Err(format!(
+                "vector has length {}, expected {}",
+                vector.len(),
+                self.dim
+            ));
+        }
+        let mut vector = vector.to_vec();
+        if self.metric == HnswMetric::Cosine {
+            let norm = dot(&vector, &vector).sqrt();
+            if norm > 0.0 {
+                vector.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+        Ok(vector)
+    }
+
+    fn distance_to(&self, query: &[f32], node: usize) -> f32 {
+        self.metric.distance(query, &self.nodes[node].vector)
+    }
+
+    /// Beam search on one layer, returning up to `ef` accepted nodes closest first
+    ///
+    /// Rejected nodes are still expanded so that filtered searches stay connected.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[usize],
+        ef: usize,
+        layer: usize,
+        accept: &dyn Fn(usize) -> bool,
+    ) -> Vec<Scored> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Scored>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Scored> = BinaryHeap::new();
+
+        for &node in entry_points {
+            let scored = Scored(self.distance_to(query, node), node);
+            candidates.push(Reverse(scored));
+            if accept(node) {
+                results.push(scored);
+                if results.len() > ef {
+                    results.pop();
+                }
+            }
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|worst| current.0 > worst.0) {
+                break;
+            }
+
+            for &neighbor in &self.nodes[current.1].links[layer] {
+                let neighbor = neighbor as usize;
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                let scored = Scored(self.distance_to(query, neighbor), neighbor);
+                let worst = results.peek().map_or(f32::INFINITY, |w| w.0);
+                if results.len() < ef || scored.0 < worst {
+                    candidates.push(Reverse(scored));
+                    if accept(neighbor) {
+                        results.push(scored);
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Keep up to `m` candidates that are closer to the base than to any kept neighbour
+    fn select_neighbors(&self, candidates: &[Scored], m: usize) -> Vec<u32> {
+        let mut selected: Vec<Scored> = Vec::with_capacity(m);
+        for &candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let diverse = selected.iter().all(|kept| {
+                let between = self
+                    .metric
+                    .distance(&self.nodes[candidate.1].vector, &self.nodes[kept.1].vector);
+                candidate.0 < between
+            });
+            if diverse {
+                selected.push(candidate);
+            }
+        }
+
+        // Fill up with the closest remaining candidates so nodes stay well connected
+        for &candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            if !selected.iter().any(|kept| kept.1 == candidate.1) {
+                selected.push(candidate);
+            }
+        }
+        selected.iter().map(|s| s.1 as u32).collect()
+    }
+
+    /// Insert a vector under an id that is not already live
+    pub fn insert(&mut self, id: u32, vector: &[f32]) -> Result<(), String> {
+        if self.ids.contains_key(&id) {
+            return Err(format!("id {} is already indexed", id));
+        }
+        let vector = self.prepare(vector)?;
+        let level = self.random_level();
+        let index = self.nodes.len();
+
+        self.nodes.push(Node {
+            id,
+            vector,
+            links: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.ids.insert(id, index);
+
+        let entry = match self.entry {
+            Some(entry) => entry,
+            None => {
+                self.entry = Some(index);
+                return Ok(());
+            }
+        };
+        let top = self.nodes[entry].links.len() - 1;
+        let query = self.nodes[index].vector.clone();
+        let all = |_: usize| true;
+
+        let mut entry_points = vec![entry];
+        for layer in (level + 1..=top).rev() {
+            let closest = self.search_layer(&query, &entry_points, 1, layer, &all);
+            entry_points = vec![closest[0].1];
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(
+                &query,
+                &entry_points,
+                self.params.ef_construction,
+                layer,
+                &all,
+            );
+            let neighbors = self.select_neighbors(&candidates, self.params.m);
+
+            for &neighbor in &neighbors {
+                let neighbor = neighbor as usize;
+                self.nodes[neighbor].links[layer].push(index as u32);
+                if self.nodes[neighbor].links[layer].len() > self.max_links(layer) {
+                    let base = self.nodes[neighbor].vector.clone();
+                    let mut linked: Vec<Scored> = self.nodes[neighbor].links[layer]
+                        .iter()
+                        .map(|&n| Scored(self.distance_to(&base, n as usize), n as usize))
+                        .collect();
+                    linked.sort_unstable();
+                    self.nodes[neighbor].links[layer] =
+                        self.select_neighbors(&linked, self.max_links(layer));
+                }
+            }
+            self.nodes[index].links[layer] = neighbors;
+            entry_points = candidates.iter().map(|s| s.1).collect();
+        }
+
+        if level > top {
+            self.entry = Some(index);
+        }
+        Ok(())
+    }
+
+    /// Delete a vector; it stays in the graph for navigation but is never returned
+    pub fn delete(&mut self, id: u32) -> bool {
+        match self.ids.remove(&id) {
+            Some(index) => {
+                self.nodes[index].deleted = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The `k` nearest live vectors accepted by `filter` as `(id, distance)`, closest first
+    pub fn search(
+        &self,
+        query: &[f32],
+        k: usize,
+        filter: Option<&dyn Fn(u32) -> bool>,
+    ) -> Result<Vec<(u32, f32)>, String> {
+        let query = self.prepare(query)?;
+        let entry = match self.entry {
+            Some(entry) if k > 0 => entry,
+            _ => return Ok(Vec::new()),
+        };
+
+        let all = |_: usize| true;
+        let mut entry_points = vec![entry];
+        for layer in (1..self.nodes[entry].links.len()).rev() {
+            let closest = self.search_layer(&query, &entry_points, 1, layer, &all);
+            entry_points = vec![closest[0].1];
+        }
+
+        let accept = |node: usize| {
+            let node = &self.nodes[node];
+            !node.deleted && filter.is_none_or(|f| f(node.id))
+        };
+        let ef = self.params.ef_search.max(k);
+        let mut hits: Vec<(u32, f32)> = self
+            .search_layer(&query, &entry_points, ef, 0, &accept)
+            .into_iter()
+            .map(|s| (self.nodes[s.1].id, s.0))
+            .collect();
+        hits.truncate(k);
+        Ok(hits)
+    }
+
+    /// Serialize the index to a little-endian binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(self.metric.code());
+        for value in [
+            self.dim,
+            self.params.m,
+            self.params.ef_construction,
+            self.params.ef_search,
+            self.nodes.len(),
+        ]
+        .iter()
+        {
+            out.extend_from_slice(&(*value as u32).to_le_bytes());
+        }
+        out.extend_from_slice(&self.rng.to_le_bytes());
+        let entry = self.entry.map_or(u32::MAX, |e| e as u32);
+        out.extend_from_slice(&entry.to_le_bytes());
+
+        for node in &self.nodes {
+            out.extend_from_slice(&node.id.to_le_bytes());
+            out.push(node.deleted as u8);
+            for x in &node.vector {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+            out.extend_from_slice(&(node.links.len() as u32).to_le_bytes());
+            for links in &node.links {
+                out.extend_from_slice(&(links.len() as u32).to_le_bytes());
+                for link in links {
+                    out.extend_from_slice(&link.to_le_bytes());
+                }
+            }
+        }
+        out
+    }
+
+    /// Restore an index written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Hnsw, String> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err("not an HNSW index".to_string());
+        }
+        let metric = HnswMetric::from_code(reader.take(1)?[0])
+            .ok_or_else(|| "unknown HNSW metric".to_string())?;
+        let dim = reader.u32()? as usize;
+        let m = reader.u32()? as usize;
+        let ef_construction = reader.u32()? as usize;
+        let ef_search = reader.u32()? as usize;
+        if m < 2 || ef_construction == 0 || ef_search == 0 {
+            return Err("invalid HNSW parameters".to_string());
+        }
+        let count = reader.u32()? as usize;
+        let rng = u64::from_le_bytes(reader.array()?);
+        let entry = reader.u32()?;
+
+        let mut nodes = Vec::with_capacity(count.min(bytes.len()));
+        let mut ids = HashMap::new();
+        for index in 0..count {
+            let id = reader.u32()?;
+            let deleted = reader.take(1)?[0] != 0;
+            let vector = (0..dim)
+                .map(|_| reader.array().map(f32::from_le_bytes))
+                .collect::<Result<Vec<f32>, String>>()?;
+            let levels = reader.u32()? as usize;
+            let mut links = Vec::with_capacity(levels.min(bytes.len()));
+            for _ in 0..levels {
+                let len = reader.u32()? as usize;
+                let layer = (0..len)
+                    .map(|_| reader.u32())
+                    .collect::<Result<Vec<u32>, String>>()?;
+                if layer.iter().any(|&link| link as usize >= count) {
+                    return Err("HNSW link out of range".to_string());
+                }
+                links.push(layer);
+            }
+            if links.is_empty() {
+                return Err("HNSW node without layers".to_string());
+            }
+            if !deleted {
+                ids.insert(id, index);
+            }
+            nodes.push(Node {
+                id,
+                vector,
+                links,
+                deleted,
+            });
+        }
+
+        let entry = match entry {
+            u32::MAX => None,
+            e if (e as usize) < count => Some(e as usize),
+            _ => return Err("HNSW entry point out of range".to_string()),
+        };
+        // Search walks a link on layer L into the target's layer L and starts from the
+        // entry point's top layer, so both must exist
+        for node in &nodes {
+            for (layer, links) in node.links.iter().enumerate() {
+                if links
+                    .iter()
+                    .any(|&link| nodes[link as usize].links.len() <= layer)
+                {
+                    return Err("HNSW link to a node without that layer".to_string());
+                }
+            }
+        }
+        let top = nodes.iter().map(|node| node.links.len()).max();
+        if entry.map(|e| nodes[e].links.len()) != top {
+            return Err("HNSW entry point is not on the top layer".to_string());
+        }
+        Ok(Hnsw {
+            params: HnswParams {
+                m,
+                ef_construction,
+                ef_search,
+                seed: 0,
+            },
+            metric,
+            dim,
+            nodes,
+            ids,
+            entry,
+            rng,
+        })
+    }
+}
+
+const MAGIC: &[u8] = b"HNSW\x01";
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or_else(|| "truncated HNSW index".to_string())?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        self.array().map(u32::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random vectors in [-1, 1)
+    fn vectors(count: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        };
+        (0..count)
+            .map(|_| (0..dim).map(|_| next()).collect())
+            .collect()
+    }
+
+    fn build(metric: HnswMetric, data: &[Vec<f32>]) -> Hnsw {
+        let mut index = Hnsw::new(data[0].len(), metric, HnswParams::default());
+        for (id, vector) in data.iter().enumerate() {
+            index.insert(id as u32, vector).unwrap();
+        }
+        index
+    }
+
+    fn exact(metric: HnswMetric, data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<u32> {
+        let index = Hnsw::new(query.len(), metric, HnswParams::default());
+        let query = index.prepare(query).unwrap();
+        let mut all: Vec<(f32, u32)> = data
+            .iter()
+            .enumerate()
+            .map(|(id, v)| {
+                (
+                    metric.distance(&query, &index.prepare(v).unwrap()),
+                    id as u32,
+                )
+            })
+            .collect();
+        all.sort_by(|a, b| a.0.total_cmp(&b.0));
+        all.iter().take(k).map(|&(_, id)| id).collect()
+    }
+
+    #[test]
+    fn test_recall() {
+        let data = vectors(600, 12, 7);
+        let queries = vectors(20, 12, 99);
+        for &metric in [HnswMetric::Cosine, HnswMetric::L2, HnswMetric::InnerProduct].iter() {
+            let index = build(metric, &data);
+            let mut found = 0;
+            for query in &queries {
+                let expected = exact(metric, &data, query, 10);
+                let hits = index.search(query, 10, None).unwrap();
+                found += hits.iter().filter(|(id, _)| expected.contains(id)).count();
+            }
+            let recall = found as f64 / (queries.len() * 10) as f64;
+            assert!(recall > 0.9, "{:?} recall {}", metric, recall);
+        }
+    }
+
+    #[test]
+    fn test_exact_match_distance() {
+        let data = vectors(50, 4, 3);
+        let index = build(HnswMetric::L2, &data);
+        let hits = index.search(&data[17], 1, None).unwrap();
+        assert_eq!(hits, vec![(17, 0.0)]);
+    }
+
+    #[test]
+    fn test_filter_and_delete() {
+        let data = vectors(300, 8, 11);
+        let mut index = build(HnswMetric::Cosine, &data);
+
+        let even = |id: u32| id.is_multiple_of(2);
+        let hits = index.search(&data[5], 5, Some(&even)).unwrap();
+        assert_eq!(hits.len(), 5);
+        assert!(hits.iter().all(|&(id, _)| id.is_multiple_of(2)));
+
+        assert!(index.delete(5));
+        assert!(!index.delete(5));
+        assert_eq!(index.len(), 299);
+        let hits = index.search(&data[5], 10, None).unwrap();
+        assert!(hits.iter().all(|&(id, _)| id != 5));
+
+        // A deleted id may be inserted again
+        index.insert(5, &data[5]).unwrap();
+        assert_eq!(index.search(&data[5], 1, None).unwrap()[0].0, 5);
+        assert!(index.insert(5, &data[5]).is_err());
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let mut index = Hnsw::new(3, HnswMetric::L2, HnswParams::default());
+        assert!(index.insert(0, &[1.0, 2.0]).is_err());
+        assert!(index.search(&[1.0], 1, None).is_err());
+        assert!(index.search(&[1.0, 2.0, 3.0], 1, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let data = vectors(200, 6, 5);
+        let mut index = build(HnswMetric::InnerProduct, &data);
+        index.delete(3);
+
+        let restored = Hnsw::from_bytes(&index.to_bytes()).unwrap();
+        assert_eq!(restored.len(), index.len());
+        for query in vectors(5, 6, 42).iter() {
+            assert_eq!(
+                restored.search(query, 5, None).unwrap(),
+                index.search(query, 5, None).unwrap()
+            );
+        }
+        assert_eq!(restored.to_bytes(), index.to_bytes());
+
+        let bytes = index.to_bytes();
+        assert!(Hnsw::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Hnsw::from_bytes(b"nope").is_err());
+    }
+
+    #[test]
+    fn test_malformed_header() {
+        let index = build(HnswMetric::L2, &vectors(10, 3, 7));
+        let bytes = index.to_bytes();
+        // m, ef_construction and ef_search follow the magic, metric and dimension
+        let m = MAGIC.len() + 5;
+        for (offset, value) in [(m, 1u32), (m, 0), (m + 4, 0), (m + 8, 0)] {
+            let mut bytes = bytes.clone();
+            bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            assert_eq!(
+                Hnsw::from_bytes(&bytes).err().as_deref(),
+                Some("invalid HNSW parameters")
+            );
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_layers() {
+        let mut index = build(HnswMetric::L2, &vectors(3, 2, 1));
+        index.nodes[0].links = vec![vec![1, 2], vec![1]];
+        index.nodes[1].links = vec![vec![0, 2]];
+        index.nodes[2].links = vec![vec![0, 1]];
+        index.entry = Some(0);
+        assert_eq!(
+            Hnsw::from_bytes(&index.to_bytes()).err().as_deref(),
+            Some("HNSW link to a node without that layer")
+        );
+
+        index.nodes[0].links[1].clear();
+        assert!(Hnsw::from_bytes(&index.to_bytes()).is_ok());
+        index.entry = Some(1);
+        assert_eq!(
+            Hnsw::from_bytes(&index.to_bytes()).err().as_deref(),
+            Some("HNSW entry point is not on the top layer")
+        );
+        index.entry = None;
+        assert!(Hnsw::from_bytes(&index.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_degenerate_params_are_clamped() {
+        let params = HnswParams {
+            m: 1,
+            ef_construction: 0,
+            ef_search: 0,
+            ..HnswParams::default()
+        };
+        let mut index = Hnsw::new(3, HnswMetric::L2, params);
+        for (id, vector) in vectors(20, 3, 9).iter().enumerate() {
+            index.insert(id as u32, vector).unwrap();
+        }
+        assert_eq!(index.ef_search(), 1);
+        assert_eq!(index.search(&[0.0, 0.0, 0.0], 3, None).unwrap().len(), 3);
+        assert!(Hnsw::from_bytes(&index.to_bytes()).is_ok());
+    }
+}
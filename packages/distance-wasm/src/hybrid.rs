@@ -0,0 +1,257 @@
+// Hybrid token scorers - token level comparison with an inner character metric
+// Suited to multi-word names where word order and small typos both occur,
+// e.g. "Smith, John A." against "Jon Smith"
+
+use std::collections::HashMap;
+
+/// Split text into runs of alphanumeric characters
+pub fn tokenize(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Mean over the tokens of `a` of their best inner similarity to a token of `b`
+fn monge_elkan_directed(a: &[&str], b: &[&str], inner: &dyn Fn(&str, &str) -> f64) -> f64 {
+    let total: f64 = a
+        .iter()
+        .map(|ta| b.iter().map(|tb| inner(ta, tb)).fold(0.0, f64::max))
+        .sum();
+    total / a.len() as f64
+}
+
+/// Monge-Elkan similarity with the given inner similarity function
+///
+/// The plain variant is asymmetric; the symmetric variant averages both
+/// directions.
+pub fn monge_elkan(s1: &str, s2: &str, inner: &dyn Fn(&str, &str) -> f64, symmetric: bool) -> f64 {
+    let a = tokenize(s1);
+    let b = tokenize(s2);
+
+    if a.is_empty() || b.is_empty() {
+        return if a.is_empty() && b.is_empty() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let forward = monge_elkan_directed(&a, &b, inner);
+    if symmetric {
+        (forward + monge_elkan_directed(&b, &a, inner)) / 2.0
+    } else {
+        forward
+    }
+}
+
+/// Inverse document frequencies of the tokens of a corpus
+pub struct Idf {
+    documents: usize,
+    frequencies: HashMap<String, usize>,
+}
+
+impl Idf {
+    pub fn fit<S: AsRef<str>>(corpus: &[S]) -> Idf {
+        Idf::fit_features(corpus, |document| {
+            tokenize(document).into_iter().map(String::from).collect()
+        })
+    }
+
+    /// Document frequencies of the features `extract` finds in each document
+    pub fn fit_features<S: AsRef<str>>(corpus: &[S], extract: impl Fn(&str) -> Vec<String>) -> Idf {
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        for document in corpus {
+            let mut features = extract(document.as_ref());
+            features.sort_unstable();
+            features.dedup();
+            for feature in features {
+                *frequencies.entry(feature).or_insert(0) += 1;
+            }
+        }
+
+        Idf {
+            documents: corpus.len(),
+            frequencies,
+        }
+    }
+
+    /// Smoothed idf, `ln((N + 1) / (df + 1)) + 1`, so unseen tokens weigh the most
+    pub fn idf(&self, token: &str) -> f64 {
+        self.weight(token, true)
+    }
+
+    /// Idf of a feature; unsmoothed it is `ln(N / df) + 1`, infinite for unseen features
+    pub fn weight(&self, feature: &str, smooth: bool) -> f64 {
+        let df = self.frequencies.get(feature).copied().unwrap_or(0) as f64;
+        let n = self.documents as f64;
+        if smooth {
+            ((n + 1.0) / (df + 1.0)).ln() + 1.0
+        } else {
+            (n / df).ln() + 1.0
+        }
+    }
+
+    /// Features seen in the corpus in lexicographic order
+    pub fn features(&self) -> Vec<&str> {
+        let mut features: Vec<&str> = self.frequencies.keys().map(String::as_str).collect();
+        features.sort_unstable();
+        features
+    }
+
+    /// Unit length `ln(tf + 1) * idf` weights of the tokens of a text
+    fn weights<'a>(&self, tokens: &[&'a str]) -> Vec<(&'a str, f64)> {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for &token in tokens {
+            match counts.iter_mut().find(|(t, _)| *t == token) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((token, 1)),
+            }
+        }
+
+        let mut weights: Vec<(&str, f64)> = counts
+            .into_iter()
+            .map(|(token, tf)| (token, (tf as f64 + 1.0).ln() * self.idf(token)))
+            .collect();
+        let norm = weights.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            for (_, w) in weights.iter_mut() {
+                *w /= norm;
+            }
+        }
+        weights
+    }
+}
+
+/// Soft TF-IDF similarity of Cohen, Ravikumar and Fienberg
+///
+/// Tokens of `s1` contribute when their best inner similarity to a token of
+/// `s2` exceeds `threshold`, weighted by both tokens' TF-IDF weights and the
+/// inner similarity itself.
+pub fn soft_tfidf(
+    idf: &Idf,
+    s1: &str,
+    s2: &str,
+    inner: &dyn Fn(&str, &str) -> f64,
+    threshold: f64,
+) -> f64 {
+    let a = idf.weights(&tokenize(s1));
+    let b = idf.weights(&tokenize(s2));
+
+    if a.is_empty() || b.is_empty() {
+        return if a.is_empty() && b.is_empty() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let mut score = 0.0;
+    for &(ta, wa) in &a {
+        let mut best = (0.0, 0.0);
+        for &(tb, wb) in &b {
+            let sim = inner(ta, tb);
+            if sim > best.0 {
+                best = (sim, wb);
+            }
+        }
+        if best.0 > threshold {
+            score += wa * best.1 * best.0;
+        }
+    }
+
+    score.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jaro_winkler(a: &str, b: &str) -> f64 {
+        textdistance::nstr::jaro_winkler(a, b)
+    }
+
+    fn exact(a: &str, b: &str) -> f64 {
+        (a == b) as u8 as f64
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("Smith, John A."), vec!["Smith", "John", "A"]);
+        assert_eq!(tokenize("  "), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_monge_elkan() {
+        assert_eq!(monge_elkan("John Smith", "Smith John", &exact, false), 1.0);
+        assert_eq!(monge_elkan("", "", &exact, false), 1.0);
+        assert_eq!(monge_elkan("John", "", &exact, false), 0.0);
+
+        // Every token of the shorter name finds a close match
+        let score = monge_elkan("Jon Smith", "Smith, John A.", &jaro_winkler, false);
+        assert!(score > 0.9, "{}", score);
+    }
+
+    #[test]
+    fn test_monge_elkan_symmetric() {
+        let forward = monge_elkan("Jon Smith", "Smith, John A.", &jaro_winkler, false);
+        let backward = monge_elkan("Smith, John A.", "Jon Smith", &jaro_winkler, false);
+        let symmetric = monge_elkan("Jon Smith", "Smith, John A.", &jaro_winkler, true);
+
+        assert!(forward > backward);
+        assert!((symmetric - (forward + backward) / 2.0).abs() < 1e-12);
+        assert_eq!(
+            symmetric,
+            monge_elkan("Smith, John A.", "Jon Smith", &jaro_winkler, true)
+        );
+    }
+
+    #[test]
+    fn test_idf() {
+        let idf = Idf::fit(&["acme inc", "globex inc", "initech inc"]);
+        assert!(idf.idf("inc") < idf.idf("acme"));
+        assert!(idf.idf("acme") < idf.idf("unseen"));
+    }
+
+    #[test]
+    fn test_soft_tfidf() {
+        let corpus = ["acme inc", "globex inc", "initech inc", "acme corp"];
+        let idf = Idf::fit(&corpus);
+
+        let identical = soft_tfidf(&idf, "acme inc", "acme inc", &jaro_winkler, 0.9);
+        assert!((identical - 1.0).abs() < 1e-9);
+
+        // A typo in the rare token still matches through the inner metric
+        let typo = soft_tfidf(&idf, "acme inc", "acmee inc", &jaro_winkler, 0.9);
+        assert!(typo > 0.9, "{}", typo);
+
+        // Sharing only the common token scores low
+        let common = soft_tfidf(&idf, "acme inc", "globex inc", &jaro_winkler, 0.9);
+        assert!(common < 0.5, "{}", common);
+
+        assert_eq!(soft_tfidf(&idf, "", "acme", &jaro_winkler, 0.9), 0.0);
+    }
+
+    #[test]
+    fn test_edit_distance_inner_scorer() {
+        // Distance-valued inner algorithms must still reward matching words
+        let inner = Some("levenshtein".to_string());
+        assert_eq!(
+            crate::monge_elkan("John Smith", "Smith John", inner.clone(), None, None),
+            1.0
+        );
+        let me = crate::monge_elkan("Jon Smith", "John Smith", inner.clone(), None, None);
+        assert!((me - 0.875).abs() < 1e-9, "{}", me);
+
+        let corpus = vec!["acme inc".to_string(), "globex inc".to_string()];
+        let soft = crate::SoftTfIdf::new(corpus, inner, Some(0.75), None)
+            .ok()
+            .unwrap();
+        assert!((soft.similarity("acme inc", "acme inc") - 1.0).abs() < 1e-9);
+        let typo = soft.similarity("acme inc", "acmee inc");
+        assert!(typo > 0.7, "{}", typo);
+        assert!(soft.similarity("acme inc", "globex inc") < 0.5);
+
+        let corpus = vec!["acme inc".to_string()];
+        assert!(crate::SoftTfIdf::try_new(corpus, None, None, Some("lowercase".into())).is_err());
+    }
+}
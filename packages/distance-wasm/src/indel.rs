@@ -0,0 +1,195 @@
+// Indel distance - edit distance with insertions and deletions only
+// Computed from the length of the longest common subsequence using the
+// bit-parallel algorithm of Hyyrö, one 64-bit word per 64 pattern characters
+
+use std::collections::HashMap;
+
+const WORD_SIZE: usize = 64;
+
+/// Character bit masks of a pattern, built once and reused for many texts
+pub struct Pattern {
+    len: usize,
+    words: usize,
+    /// Masks for characters below U+0100, `words` entries per character
+    latin1: Vec<u64>,
+    /// Masks for all other characters
+    other: HashMap<char, Vec<u64>>,
+}
+
+impl Pattern {
+    pub fn new(pattern: &[char]) -> Pattern {
+        let len = pattern.len();
+        let words = len.div_ceil(WORD_SIZE).max(1);
+        let mut latin1 = vec![0u64; 256 * words];
+        let mut other: HashMap<char, Vec<u64>> = HashMap::new();
+
+        for (i, &c) in pattern.iter().enumerate() {
+            let (word, bit) = (i / WORD_SIZE, i % WORD_SIZE);
+            if (c as u32) < 256 {
+                latin1[c as usize * words + word] |= 1u64 << bit;
+            } else {
+                other.entry(c).or_insert_with(|| vec![0u64; words])[word] |= 1u64 << bit;
+            }
+        }
+
+        Pattern {
+            len,
+            words,
+            latin1,
+            other,
+        }
+    }
+
+    /// Whether the pattern contains the character
+    pub fn contains(&self, c: char) -> bool {
+        if (c as u32) < 256 {
+            let start = c as usize * self.words;
+            self.latin1[start..start + self.words]
+                .iter()
+                .any(|&w| w != 0)
+        } else {
+            self.other.contains_key(&c)
+        }
+    }
+
+    fn mask(&self, c: char, word: usize) -> u64 {
+        if (c as u32) < 256 {
+            self.latin1[c as usize * self.words + word]
+        } else {
+            self.other.get(&c).map_or(0, |masks| masks[word])
+        }
+    }
+
+    /// Length of the longest common subsequence of the pattern and `text`
+    pub fn lcs(&self, text: &[char]) -> usize {
+        if self.len == 0 || text.is_empty() {
+            return 0;
+        }
+
+        let mut s = vec![!0u64; self.words];
+        for &c in text {
+            let mut carry = 0u64;
+            for (word, sw) in s.iter_mut().enumerate() {
+                let u = *sw & self.mask(c, word);
+                let (sum, c1) = sw.overflowing_add(u);
+                let (sum, c2) = sum.overflowing_add(carry);
+                carry = (c1 || c2) as u64;
+                *sw = sum | (*sw & !u);
+            }
+        }
+
+        // Matched pattern positions are the cleared bits of s
+        let mut lcs = 0;
+        for (word, &sw) in s.iter().enumerate() {
+            let bits = (self.len - word * WORD_SIZE).min(WORD_SIZE);
+            let mask = if bits == WORD_SIZE {
+                !0u64
+            } else {
+                (1u64 << bits) - 1
+            };
+            lcs += (!sw & mask).count_ones() as usize;
+        }
+        lcs
+    }
+
+    /// Indel distance between the pattern and `text`
+    pub fn distance(&self, text: &[char]) -> usize {
+        self.len + text.len() - 2 * self.lcs(text)
+    }
+
+    /// Normalized indel similarity between the pattern and `text`
+    pub fn similarity(&self, text: &[char]) -> f64 {
+        normalized(self.distance(text), self.len + text.len())
+    }
+}
+
+/// Convert an indel distance to a similarity in [0, 1]
+pub fn normalized(distance: usize, len_sum: usize) -> f64 {
+    if len_sum == 0 {
+        1.0
+    } else {
+        1.0 - distance as f64 / len_sum as f64
+    }
+}
+
+/// Indel distance between two strings, counted in Unicode scalar values
+pub fn indel_distance(a: &[char], b: &[char]) -> usize {
+    Pattern::new(a).distance(b)
+}
+
+/// Normalized indel similarity (1.0 = identical, 0.0 = no common characters)
+pub fn indel_similarity(a: &[char], b: &[char]) -> f64 {
+    normalized(indel_distance(a, b), a.len() + b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn test_indel_distance() {
+        assert_eq!(indel_distance(&chars(""), &chars("")), 0);
+        assert_eq!(indel_distance(&chars("abc"), &chars("")), 3);
+        assert_eq!(indel_distance(&chars("kitten"), &chars("sitting")), 5);
+        assert_eq!(indel_distance(&chars("café"), &chars("cafe")), 2);
+    }
+
+    #[test]
+    fn test_lcs_multi_word() {
+        // Patterns longer than one word must carry between blocks
+        let a: String = "abcdefghij".repeat(20);
+        let b: String = "abcdefghi".repeat(20);
+        let pattern = Pattern::new(&chars(&a));
+        assert_eq!(pattern.lcs(&chars(&b)), 180);
+        assert_eq!(pattern.lcs(&chars(&a)), 200);
+    }
+
+    #[test]
+    fn test_lcs_matches_dynamic_programming() {
+        let cases = [
+            ("", "abc"),
+            ("abcbdab", "bdcaba"),
+            ("The quick brown fox", "The quack brown box"),
+            ("Москва", "Moskva"),
+            ("xabxcdxxefxgx", "1ab2cd34ef5g6"),
+        ];
+        for (a, b) in cases.iter() {
+            let (a, b) = (chars(a), chars(b));
+            let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    dp[i][j] = if a[i - 1] == b[j - 1] {
+                        dp[i - 1][j - 1] + 1
+                    } else {
+                        dp[i - 1][j].max(dp[i][j - 1])
+                    };
+                }
+            }
+            assert_eq!(Pattern::new(&a).lcs(&b), dp[a.len()][b.len()]);
+        }
+    }
+
+    #[test]
+    fn test_indel_similarity() {
+        assert_eq!(indel_similarity(&chars(""), &chars("")), 1.0);
+        assert_eq!(indel_similarity(&chars("abc"), &chars("xyz")), 0.0);
+        assert!(
+            (indel_similarity(&chars("this is a test"), &chars("this is a test!")) - 0.9655172)
+                .abs()
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let pattern = Pattern::new(&chars("aé北"));
+        assert!(pattern.contains('a'));
+        assert!(pattern.contains('é'));
+        assert!(pattern.contains('北'));
+        assert!(!pattern.contains('b'));
+    }
+}
@@ -0,0 +1,475 @@
+// Fuzzy join - match the rows of two CSV files on a text column each
+// The right file is loaded and indexed by its key column so every left row is
+// only scored against the right rows sharing a block with it. Left rows are
+// streamed; matches for a row are yielded as soon as it has been read
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Read;
+
+use csv::StringRecord;
+
+use crate::ngram_index::qgrams;
+use crate::preprocess::Preprocessor;
+
+/// Candidate generation strategy
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Blocking {
+    /// Score every pair of rows
+    None,
+    /// Rows sharing at least one padded q-gram of their keys
+    Ngram(usize),
+    /// Rows whose keys start with the same `n` characters
+    Prefix(usize),
+    /// Rows whose keys share a whitespace separated word
+    Token,
+}
+
+impl Blocking {
+    /// Parse "none", "token", "ngram[:q]" (q defaults to 3) or "prefix[:n]" (n defaults to 1)
+    pub fn parse(spec: &str) -> Result<Blocking, String> {
+        let spec = spec.trim().to_lowercase();
+        let (name, size) = match spec.split_once(':') {
+            Some((name, size)) => {
+                let size: usize = size
+                    .parse()
+                    .ok()
+                    .filter(|&size| size > 0)
+                    .ok_or_else(|| format!("invalid blocking size: {}", size))?;
+                (name, Some(size))
+            }
+            None => (spec.as_str(), None),
+        };
+        match (name, size) {
+            ("none", None) => Ok(Blocking::None),
+            ("token", None) => Ok(Blocking::Token),
+            ("ngram" | "qgram", size) => Ok(Blocking::Ngram(size.unwrap_or(3))),
+            ("prefix", size) => Ok(Blocking::Prefix(size.unwrap_or(1))),
+            _ => Err(format!("unsupported blocking: {}", spec)),
+        }
+    }
+
+    /// Block keys of a preprocessed join key
+    fn keys(&self, text: &str) -> Vec<String> {
+        match *self {
+            Blocking::None => vec![String::new()],
+            Blocking::Ngram(q) => {
+                let chars: Vec<char> = text.chars().collect();
+                qgrams(&chars, q).into_keys().collect()
+            }
+            Blocking::Prefix(n) => vec![text.chars().take(n).collect()],
+            Blocking::Token => {
+                let words: HashSet<&str> = text.split_whitespace().collect();
+                words.into_iter().map(str::to_string).collect()
+            }
+        }
+    }
+}
+
+pub struct JoinOptions {
+    pub left_col: String,
+    pub right_col: String,
+    /// Algorithm name accepted by compare(), with distances scored as 1 - normalized distance
+    pub algorithm: String,
+    /// Minimum score of a matched pair
+    pub threshold: f64,
+    pub blocking: Blocking,
+    /// Match every row at most once, taking pairs greedily from the best score down
+    pub one_to_one: bool,
+    pub preprocessor: Option<String>,
+}
+
+/// A matched pair of rows, numbered from 0 after the header
+#[derive(Clone, Debug, PartialEq)]
+pub struct JoinMatch {
+    pub left_row: usize,
+    pub right_row: usize,
+    pub score: f64,
+    /// Fields of the left row followed by those of the right row
+    pub fields: Vec<String>,
+}
+
+fn column(headers: &StringRecord, name: &str, side: &str) -> Result<usize, String> {
+    headers.iter().position(|h| h == name).ok_or_else(|| {
+        let columns: Vec<&str> = headers.iter().collect();
+        format!(
+            "{} file has no column `{}` (columns: {})",
+            side,
+            name,
+            columns.join(", ")
+        )
+    })
+}
+
+/// Iterator over the matches of a fuzzy join, created by [`fuzzy_join`]
+pub struct FuzzyJoin<L: Read> {
+    options: JoinOptions,
+    preprocessor: Preprocessor,
+    left: csv::Reader<L>,
+    left_col: usize,
+    left_row: usize,
+    headers: Vec<String>,
+    right: Vec<StringRecord>,
+    /// Preprocessed key of every right row
+    keys: Vec<String>,
+    /// Block key to the right rows in that block, ascending
+    blocks: HashMap<String, Vec<usize>>,
+    pending: VecDeque<JoinMatch>,
+    done: bool,
+}
+
+/// Join two CSV files with header rows on the similarity of one column each
+///
+/// The right file is read and indexed up front; left rows are read lazily as
+/// the returned iterator advances. Without `one_to_one` every pair scoring at
+/// least the threshold is yielded, grouped by left row with the best first.
+/// With it every row appears in at most one pair, so matches are only yielded
+/// once the whole left file has been read.
+pub fn fuzzy_join<L: Read, R: Read>(
+    left: L,
+    right: R,
+    options: JoinOptions,
+) -> Result<FuzzyJoin<L>, String> {
+    let preprocessor = options
+        .preprocessor
+        .as_deref()
+        .map(Preprocessor::try_parse)
+        .transpose()?
+        .unwrap_or_default();
+
+    if !crate::is_algorithm(&options.algorithm) {
+        return Err(format!("unknown algorithm \"{}\"", options.algorithm));
+    }
+
+    let mut left = csv::Reader::from_reader(left);
+    let left_headers = left.headers().map_err(|e| e.to_string())?.clone();
+    let left_col = column(&left_headers, &options.left_col, "left")?;
+
+    let mut right_reader = csv::Reader::from_reader(right);
+    let right_headers = right_reader.headers().map_err(|e| e.to_string())?.clone();
+    let right_col = column(&right_headers, &options.right_col, "right")?;
+
+    let mut right = Vec::new();
+    let mut keys = Vec::new();
+    let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+    for record in right_reader.records() {
+        let record = record.map_err(|e| format!("right file: {}", e))?;
+        let key = preprocessor.process(record.get(right_col).unwrap_or(""));
+        for block in options.blocking.keys(&key) {
+            blocks.entry(block).or_default().push(right.len());
+        }
+        keys.push(key);
+        right.push(record);
+    }
+
+    // Right columns sharing a name with a left column get a prefix
+    let mut headers: Vec<String> = left_headers.iter().map(str::to_string).collect();
+    for name in right_headers.iter() {
+        if left_headers.iter().any(|h| h == name) {
+            headers.push(format!("right_{}", name));
+        } else {
+            headers.push(name.to_string());
+        }
+    }
+
+    Ok(FuzzyJoin {
+        options,
+        preprocessor,
+        left,
+        left_col,
+        left_row: 0,
+        headers,
+        right,
+        keys,
+        blocks,
+        pending: VecDeque::new(),
+        done: false,
+    })
+}
+
+impl<L: Read> FuzzyJoin<L> {
+    /// Names of the fields of every match, left columns first
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Right rows scoring at least the threshold against a left key, best first
+    fn matches(&self, key: &str) -> Vec<(usize, f64)> {
+        let key = self.preprocessor.process(key);
+        let mut candidates: Vec<usize> = self
+            .options
+            .blocking
+            .keys(&key)
+            .iter()
+            .filter_map(|block| self.blocks.get(block))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut matches: Vec<(usize, f64)> = candidates
+            .into_iter()
+            .map(|row| {
+                let score =
+                    crate::similarity_by_name(&key, &self.keys[row], &self.options.algorithm);
+                (row, score)
+            })
+            .filter(|&(_, score)| score >= self.options.threshold)
+            .collect();
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        matches
+    }
+
+    fn joined(
+        &self,
+        left_row: usize,
+        left: &StringRecord,
+        right_row: usize,
+        score: f64,
+    ) -> JoinMatch {
+        JoinMatch {
+            left_row,
+            right_row,
+            score,
+            fields: left
+                .iter()
+                .chain(self.right[right_row].iter())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Read the next left row, returning false at the end of the file
+    fn read_row(&mut self) -> Result<Option<(usize, StringRecord)>, String> {
+        let mut record = StringRecord::new();
+        let more = self
+            .left
+            .read_record(&mut record)
+            .map_err(|e| format!("left file: {}", e))?;
+        if !more {
+            return Ok(None);
+        }
+        self.left_row += 1;
+        Ok(Some((self.left_row - 1, record)))
+    }
+
+    /// Queue the matches of the next left row, returning false at the end of the file
+    fn advance(&mut self) -> Result<bool, String> {
+        let (row, record) = match self.read_row()? {
+            Some(row) => row,
+            None => return Ok(false),
+        };
+        let key = record.get(self.left_col).unwrap_or("");
+        for (right_row, score) in self.matches(key) {
+            let joined = self.joined(row, &record, right_row, score);
+            self.pending.push_back(joined);
+        }
+        Ok(true)
+    }
+
+    /// Read the whole left file and queue the greedy one-to-one assignment
+    fn assign(&mut self) -> Result<(), String> {
+        let mut pairs: Vec<(usize, usize, f64)> = Vec::new();
+        let mut records: HashMap<usize, StringRecord> = HashMap::new();
+        while let Some((row, record)) = self.read_row()? {
+            let matches = self.matches(record.get(self.left_col).unwrap_or(""));
+            if !matches.is_empty() {
+                pairs.extend(
+                    matches
+                        .into_iter()
+                        .map(|(right_row, score)| (row, right_row, score)),
+                );
+                records.insert(row, record);
+            }
+        }
+
+        pairs.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+        let mut used_left = HashSet::new();
+        let mut used_right = HashSet::new();
+        let mut assigned: Vec<(usize, usize, f64)> = pairs
+            .into_iter()
+            .filter(|&(left, right, _)| {
+                !used_left.contains(&left)
+                    && !used_right.contains(&right)
+                    && used_left.insert(left)
+                    && used_right.insert(right)
+            })
+            .collect();
+        assigned.sort_by_key(|&(left, _, _)| left);
+
+        for (left, right, score) in assigned {
+            let joined = self.joined(left, &records[&left], right, score);
+            self.pending.push_back(joined);
+        }
+        Ok(())
+    }
+}
+
+impl<L: Read> Iterator for FuzzyJoin<L> {
+    type Item = Result<JoinMatch, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(joined) = self.pending.pop_front() {
+                return Some(Ok(joined));
+            }
+            if self.done {
+                return None;
+            }
+            let result = if self.options.one_to_one {
+                self.assign().map(|_| false)
+            } else {
+                self.advance()
+            };
+            match result {
+                Ok(more) => self.done = !more,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: &str = "id,name\n1,Jon Smith\n2,jane doe\n3,Acme Corp\n4,John Smyth\n";
+    const RIGHT: &str = "name,city\nJohn Smith,Boston\nJane Doe,Denver\nACME Corp.,Austin\n";
+
+    fn options(blocking: Blocking, one_to_one: bool) -> JoinOptions {
+        JoinOptions {
+            left_col: "name".to_string(),
+            right_col: "name".to_string(),
+            algorithm: "ratio".to_string(),
+            threshold: 0.8,
+            blocking,
+            one_to_one,
+            preprocessor: Some("case_fold".to_string()),
+        }
+    }
+
+    fn pairs(blocking: Blocking, one_to_one: bool) -> Vec<(usize, usize)> {
+        fuzzy_join(
+            LEFT.as_bytes(),
+            RIGHT.as_bytes(),
+            options(blocking, one_to_one),
+        )
+        .unwrap()
+        .map(|m| m.map(|m| (m.left_row, m.right_row)))
+        .collect::<Result<_, _>>()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blocking_parse() {
+        assert_eq!(Blocking::parse("none"), Ok(Blocking::None));
+        assert_eq!(Blocking::parse("ngram"), Ok(Blocking::Ngram(3)));
+        assert_eq!(Blocking::parse("NGRAM:2"), Ok(Blocking::Ngram(2)));
+        assert_eq!(Blocking::parse("prefix:4"), Ok(Blocking::Prefix(4)));
+        assert_eq!(Blocking::parse("token"), Ok(Blocking::Token));
+        assert!(Blocking::parse("prefix:0").is_err());
+        assert!(Blocking::parse("soundex").is_err());
+    }
+
+    #[test]
+    fn test_join() {
+        let join = fuzzy_join(
+            LEFT.as_bytes(),
+            RIGHT.as_bytes(),
+            options(Blocking::None, false),
+        )
+        .unwrap();
+        assert_eq!(join.headers(), ["id", "name", "right_name", "city"]);
+        let matches: Vec<JoinMatch> = join.collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            matches[0].fields,
+            ["1", "Jon Smith", "John Smith", "Boston"]
+        );
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.left_row, m.right_row))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2), (3, 0)]
+        );
+        assert_eq!(matches[1].score, 1.0);
+        assert!(matches.iter().all(|m| m.score >= 0.8));
+    }
+
+    #[test]
+    fn test_blocking_matches_full_scan() {
+        // Every pair above the threshold here shares a q-gram, a first letter and a word
+        let full = pairs(Blocking::None, false);
+        assert_eq!(pairs(Blocking::Ngram(3), false), full);
+        assert_eq!(pairs(Blocking::Prefix(1), false), full);
+        assert_eq!(pairs(Blocking::Token, false), full);
+        // "jon" and "joh" fall into different blocks
+        assert_eq!(
+            pairs(Blocking::Prefix(3), false),
+            vec![(1, 1), (2, 2), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn test_one_to_one() {
+        // "Jon Smith" and "John Smyth" both match "John Smith"; the better pair wins
+        assert_eq!(pairs(Blocking::None, true), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_edit_distance_algorithm() {
+        // Distance-valued algorithms are scored as 1 - normalized distance
+        for algorithm in ["levenshtein", "damerau_levenshtein"] {
+            let mut options = options(Blocking::None, false);
+            options.algorithm = algorithm.to_string();
+            let matches: Vec<JoinMatch> = fuzzy_join(LEFT.as_bytes(), RIGHT.as_bytes(), options)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            let pairs: Vec<(usize, usize, f64)> = matches
+                .iter()
+                .map(|m| (m.left_row, m.right_row, m.score))
+                .collect();
+            assert_eq!(
+                pairs,
+                vec![(0, 0, 0.9), (1, 1, 1.0), (2, 2, 0.9), (3, 0, 0.9)],
+                "{}",
+                algorithm
+            );
+        }
+
+        let mut options = options(Blocking::None, false);
+        options.algorithm = "levenstein".to_string();
+        let err = fuzzy_join(LEFT.as_bytes(), RIGHT.as_bytes(), options)
+            .err()
+            .unwrap();
+        assert!(err.contains("unknown algorithm"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_preprocessor() {
+        let mut options = options(Blocking::None, false);
+        options.preprocessor = Some("case_fold,trim".to_string());
+        let err = fuzzy_join(LEFT.as_bytes(), RIGHT.as_bytes(), options)
+            .err()
+            .unwrap();
+        assert!(
+            err.contains("unknown preprocessing option \"trim\""),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let mut options = options(Blocking::None, false);
+        options.right_col = "title".to_string();
+        let err = fuzzy_join(LEFT.as_bytes(), RIGHT.as_bytes(), options)
+            .err()
+            .unwrap();
+        assert!(err.contains("`title`"), "{}", err);
+    }
+}
@@ -0,0 +1,120 @@
+// Japanese kana and character width folding
+// Half-width and full-width forms, hiragana and katakana, and long vowel
+// spellings that users consider identical are folded to a single form
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Katakana-hiragana prolonged sound mark
+const LONG_VOWEL_MARK: char = 'ー';
+
+/// Map katakana onto the matching hiragana
+pub fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Hiragana, katakana or the long vowel mark
+pub fn is_kana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | LONG_VOWEL_MARK)
+}
+
+/// CJK ideographs and the iteration mark, which continue a word after kana
+fn is_ideograph(c: char) -> bool {
+    matches!(c, '々' | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Dashes that are commonly typed in place of the long vowel mark
+fn is_dash(c: char) -> bool {
+    matches!(c, '-' | '‐' | '—' | '―' | '−')
+}
+
+/// Fold full-width ASCII and half-width katakana to their usual width
+fn fold_width(c: char, out: &mut String) {
+    match c {
+        '！'..='～' => out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+        '\u{3000}' => out.push(' '),
+        // Half-width voiced marks become combining marks and compose below
+        '｡'..='ﾟ' => out.extend(c.to_string().nfkc()),
+        _ => out.push(c),
+    }
+}
+
+/// Fold Japanese text so that width and kana variants compare as equal
+///
+/// Full-width ASCII becomes ASCII, half-width katakana become full-width,
+/// katakana become hiragana and dashes between kana become the long vowel
+/// mark. A long vowel mark at the end of a word is dropped, following the
+/// JIS convention that makes `コンピューター` and `コンピュータ` the same word;
+/// before kanji it is kept, as in `ユーザー名`, since the word goes on.
+pub fn fold_kana(text: &str) -> String {
+    let mut widened = String::with_capacity(text.len());
+    for c in text.chars() {
+        fold_width(c, &mut widened);
+    }
+
+    let chars: Vec<char> = widened.nfc().map(to_hiragana).collect();
+    let mut out = String::with_capacity(widened.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        let prev_kana = i > 0 && is_kana(chars[i - 1]);
+        let next_kana = chars.get(i + 1).is_some_and(|&n| is_kana(n));
+        let next_in_word = next_kana || chars.get(i + 1).is_some_and(|&n| is_ideograph(n));
+
+        if c == LONG_VOWEL_MARK {
+            if !prev_kana || next_in_word {
+                out.push(c);
+            }
+        } else if is_dash(c) && prev_kana && next_kana {
+            out.push(LONG_VOWEL_MARK);
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_folding() {
+        assert_eq!(fold_kana("ＡＢＣ１２３"), "ABC123");
+        assert_eq!(fold_kana("Ｎｉｋｅ　シューズ"), "Nike しゅーず");
+        assert_eq!(fold_kana("ｶﾞﾝﾀﾞﾑ"), "がんだむ");
+        assert_eq!(fold_kana("ﾊﾟｿｺﾝ"), "ぱそこん");
+    }
+
+    #[test]
+    fn test_hiragana_katakana() {
+        assert_eq!(fold_kana("カタカナ"), fold_kana("かたかな"));
+        assert_eq!(fold_kana("ラーメン"), "らーめん");
+    }
+
+    #[test]
+    fn test_long_vowel_marks() {
+        assert_eq!(fold_kana("コンピューター"), fold_kana("コンピュータ"));
+        assert_eq!(fold_kana("サーバー 設定"), "さーば 設定");
+        assert_eq!(fold_kana("ラ-メン"), "らーめん");
+        assert_eq!(fold_kana("ｺｰﾋｰ"), "こーひ");
+        assert_eq!(fold_kana("a-b"), "a-b");
+    }
+
+    #[test]
+    fn test_long_vowel_mark_before_kanji() {
+        assert_eq!(fold_kana("ユーザー名"), "ゆーざー名");
+        assert_eq!(fold_kana("ｻｰﾊﾞｰ管理"), fold_kana("サーバー管理"));
+        assert_eq!(fold_kana("サーバー、管理"), "さーば、管理");
+    }
+
+    #[test]
+    fn test_to_hiragana() {
+        assert_eq!(to_hiragana('ア'), 'あ');
+        assert_eq!(to_hiragana('ヴ'), 'ゔ');
+        assert_eq!(to_hiragana('あ'), 'あ');
+        assert_eq!(to_hiragana('A'), 'A');
+    }
+}
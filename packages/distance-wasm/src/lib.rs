@@ -1,7 +1,39 @@
-use textdistance::Algorithm;
 use wasm_bindgen::prelude::*;
 
+mod automaton;
+mod batch;
+mod bktree;
+mod bm25;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fuzz;
+mod hnsw;
+mod hybrid;
+mod indel;
+#[cfg(feature = "join")]
+pub mod join;
+mod kana;
 mod myers;
+mod ngram_index;
+#[cfg(feature = "napi")]
+pub mod node;
+mod pinyin;
+mod pinyin_table;
+mod preprocess;
+#[cfg(feature = "python")]
+pub mod python;
+mod quantize;
+#[cfg(feature = "simd")]
+pub mod simd;
+mod symspell;
+mod tfidf;
+mod transliterate;
+mod vector;
+mod vptree;
+
+pub use preprocess::{Normalization, Preprocessor};
 
 // ============================================================================
 // Edit-based Algorithms
@@ -9,69 +41,81 @@ mod myers;
 
 // Levenshtein distance - Standard edit distance algorithm
 #[wasm_bindgen]
-pub fn levenshtein(s1: &str, s2: &str) -> u32 {
-    textdistance::str::levenshtein(s1, s2) as u32
+pub fn levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::levenshtein(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn levenshtein_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::levenshtein(s1, s2)
+pub fn levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::levenshtein(&s1, &s2)
 }
 
 // Damerau-Levenshtein distance - Edit distance with character transposition
 #[wasm_bindgen]
-pub fn damerau_levenshtein(s1: &str, s2: &str) -> u32 {
-    textdistance::str::damerau_levenshtein(s1, s2) as u32
+pub fn damerau_levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::damerau_levenshtein(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn damerau_levenshtein_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::damerau_levenshtein(s1, s2)
+pub fn damerau_levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::damerau_levenshtein(&s1, &s2)
 }
 
 // Jaro similarity - Suitable for short string matching
 #[wasm_bindgen]
-pub fn jaro(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::jaro(s1, s2)
+pub fn jaro(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::jaro(&s1, &s2)
 }
 
 // Jaro-Winkler similarity - Improved version of Jaro with prefix weighting
 #[wasm_bindgen]
-pub fn jarowinkler(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::jaro_winkler(s1, s2)
+pub fn jarowinkler(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::jaro_winkler(&s1, &s2)
 }
 
 // Hamming distance - Replacement distance for equal-length strings
 #[wasm_bindgen]
-pub fn hamming(s1: &str, s2: &str) -> u32 {
-    textdistance::str::hamming(s1, s2) as u32
+pub fn hamming(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::hamming(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn hamming_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::hamming(s1, s2)
+pub fn hamming_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::hamming(&s1, &s2)
 }
 
 // Sift4 algorithm - Fast approximate string comparison
 #[wasm_bindgen]
-pub fn sift4_simple(s1: &str, s2: &str) -> u32 {
-    textdistance::str::sift4_simple(s1, s2) as u32
+pub fn sift4_simple(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::sift4_simple(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn sift4_simple_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::sift4_simple(s1, s2)
+pub fn sift4_simple_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::sift4_simple(&s1, &s2)
 }
 
 // Myers algorithm - Efficient bit-parallel edit distance
 #[wasm_bindgen]
-pub fn myers_levenshtein(s1: &str, s2: &str) -> u32 {
-    myers::myers_distance(s1, s2)
+pub fn myers_levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    myers::myers_distance(&s1, &s2)
 }
 
 #[wasm_bindgen]
-pub fn myers_levenshtein_normalized(s1: &str, s2: &str) -> f64 {
-    myers::myers_similarity(s1, s2)
+pub fn myers_levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    myers::myers_similarity(&s1, &s2)
 }
 
 // ============================================================================
@@ -80,30 +124,35 @@ pub fn myers_levenshtein_normalized(s1: &str, s2: &str) -> f64 {
 
 // Longest Common Subsequence (LCS) - Non-contiguous
 #[wasm_bindgen]
-pub fn lcs_seq(s1: &str, s2: &str) -> u32 {
-    textdistance::str::lcsseq(s1, s2) as u32
+pub fn lcs_seq(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::lcsseq(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn lcs_seq_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::lcsseq(s1, s2)
+pub fn lcs_seq_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::lcsseq(&s1, &s2)
 }
 
 // Longest Common Substring (LCSstr) - Contiguous
 #[wasm_bindgen]
-pub fn lcs_str(s1: &str, s2: &str) -> u32 {
-    textdistance::str::lcsstr(s1, s2) as u32
+pub fn lcs_str(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::lcsstr(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn lcs_str_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::lcsstr(s1, s2)
+pub fn lcs_str_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::lcsstr(&s1, &s2)
 }
 
 // Ratcliff-Obershelp algorithm - Gestalt pattern matching
 #[wasm_bindgen]
-pub fn ratcliff_obershelp(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::ratcliff_obershelp(s1, s2)
+pub fn ratcliff_obershelp(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::ratcliff_obershelp(&s1, &s2)
 }
 
 // ============================================================================
@@ -112,43 +161,937 @@ pub fn ratcliff_obershelp(s1: &str, s2: &str) -> f64 {
 
 // Jaccard similarity - Set intersection/union
 #[wasm_bindgen]
-pub fn jaccard(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::jaccard(s1, s2)
+pub fn jaccard(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::jaccard(&s1, &s2)
 }
 
 // Cosine similarity - Vector angle cosine
 #[wasm_bindgen]
-pub fn cosine(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::cosine(s1, s2)
+pub fn cosine(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::cosine(&s1, &s2)
 }
 
 // Sorensen-Dice 相似度
 #[wasm_bindgen]
-pub fn sorensen(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::sorensen_dice(s1, s2)
+pub fn sorensen(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::sorensen_dice(&s1, &s2)
 }
 
 // Tversky index - Asymmetric similarity measure
 #[wasm_bindgen]
-pub fn tversky(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::tversky(s1, s2)
+pub fn tversky(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::tversky(&s1, &s2)
 }
 
 // Smith-Waterman algorithm - Local sequence alignment
 #[wasm_bindgen]
-pub fn smith_waterman(s1: &str, s2: &str) -> u32 {
-    textdistance::str::smith_waterman(s1, s2) as u32
+pub fn smith_waterman(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::str::smith_waterman(&s1, &s2) as u32
 }
 
 #[wasm_bindgen]
-pub fn smith_waterman_normalized(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::smith_waterman(s1, s2)
+pub fn smith_waterman_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::smith_waterman(&s1, &s2)
 }
 
 // Overlap coefficient - Overlap coefficient
 #[wasm_bindgen]
-pub fn overlap(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::overlap(s1, s2)
+pub fn overlap(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::overlap(&s1, &s2)
+}
+
+// ============================================================================
+// Fuzzy Matching Scorers - RapidFuzz-compatible ratios divided by 100
+// ============================================================================
+
+// Ratio - Normalized indel similarity
+#[wasm_bindgen]
+pub fn ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::ratio(&s1, &s2)
+}
+
+// Partial ratio - Best ratio of the shorter string against a substring of the longer
+#[wasm_bindgen]
+pub fn partial_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::partial_ratio(&s1, &s2)
+}
+
+// Partial ratio alignment - Score plus the matching ranges of both strings
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreAlignment {
+    pub score: f64,
+    pub src_start: u32,
+    pub src_end: u32,
+    pub dest_start: u32,
+    pub dest_end: u32,
+}
+
+// Offsets are counted in `unit`: "char" (code points, default), "utf16" or "utf8"
+#[wasm_bindgen]
+pub fn partial_ratio_alignment(s1: &str, s2: &str, unit: Option<String>) -> ScoreAlignment {
+    let res = fuzz::partial_ratio_alignment(s1, s2);
+    let offset = |s: &str, chars: usize| -> u32 {
+        let prefix = s.chars().take(chars);
+        let units = match unit.as_deref().map(str::to_lowercase).as_deref() {
+            Some("utf16") | Some("utf-16") => prefix.map(char::len_utf16).sum(),
+            Some("utf8") | Some("utf-8") | Some("byte") => prefix.map(char::len_utf8).sum(),
+            _ => chars,
+        };
+        units as u32
+    };
+
+    ScoreAlignment {
+        score: res.score,
+        src_start: offset(s1, res.src_start),
+        src_end: offset(s1, res.src_end),
+        dest_start: offset(s2, res.dest_start),
+        dest_end: offset(s2, res.dest_end),
+    }
+}
+
+// Token sort ratio - Ratio after sorting the words of both strings
+#[wasm_bindgen]
+pub fn token_sort_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::token_sort_ratio(&s1, &s2)
+}
+
+// Token set ratio - Ratio of the shared and differing word sets
+#[wasm_bindgen]
+pub fn token_set_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::token_set_ratio(&s1, &s2)
+}
+
+// Partial token sort ratio - Partial ratio after sorting the words of both strings
+#[wasm_bindgen]
+pub fn partial_token_sort_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::partial_token_sort_ratio(&s1, &s2)
+}
+
+// Weighted ratio - Combination of the ratios above weighted by length difference
+#[wasm_bindgen]
+pub fn wratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    fuzz::wratio(&s1, &s2)
+}
+
+// ============================================================================
+// Hybrid Token Scorers - Token level comparison with an inner character metric
+// ============================================================================
+
+fn jaro_winkler_inner(a: &str, b: &str) -> f64 {
+    textdistance::nstr::jaro_winkler(a, b)
+}
+
+// Monge-Elkan similarity - Mean best inner similarity of each word of s1 to the words of s2
+// `inner` is any algorithm name accepted by compare() (default "jaro_winkler");
+// `symmetric` averages both directions
+#[wasm_bindgen]
+pub fn monge_elkan(
+    s1: &str,
+    s2: &str,
+    inner: Option<String>,
+    symmetric: Option<bool>,
+    preprocessor: Option<String>,
+) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    let symmetric = symmetric.unwrap_or(false);
+    match inner {
+        Some(name) => {
+            hybrid::monge_elkan(&s1, &s2, &|a, b| similarity_by_name(a, b, &name), symmetric)
+        }
+        None => hybrid::monge_elkan(&s1, &s2, &jaro_winkler_inner, symmetric),
+    }
+}
+
+// Soft TF-IDF - TF-IDF cosine where words match through an inner similarity above a threshold
+// IDF weights are fitted on a corpus once, then reused for every comparison
+#[wasm_bindgen]
+pub struct SoftTfIdf {
+    idf: hybrid::Idf,
+    inner: Option<String>,
+    threshold: f64,
+    preprocessor: Preprocessor,
+}
+
+impl SoftTfIdf {
+    pub(crate) fn try_new(
+        corpus: Vec<String>,
+        inner: Option<String>,
+        threshold: Option<f64>,
+        preprocessor: Option<String>,
+    ) -> Result<SoftTfIdf, String> {
+        let preprocessor = parse_preprocessor(preprocessor.as_deref())?;
+        let corpus: Vec<String> = corpus.iter().map(|doc| preprocessor.process(doc)).collect();
+
+        Ok(SoftTfIdf {
+            idf: hybrid::Idf::fit(&corpus),
+            inner,
+            threshold: threshold.unwrap_or(0.9),
+            preprocessor,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl SoftTfIdf {
+    // `inner` defaults to "jaro_winkler" and `threshold` to 0.9
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        corpus: Vec<String>,
+        inner: Option<String>,
+        threshold: Option<f64>,
+        preprocessor: Option<String>,
+    ) -> Result<SoftTfIdf, JsError> {
+        SoftTfIdf::try_new(corpus, inner, threshold, preprocessor).map_err(|e| JsError::new(&e))
+    }
+
+    pub fn similarity(&self, s1: &str, s2: &str) -> f64 {
+        let (s1, s2) = (self.preprocessor.apply(s1), self.preprocessor.apply(s2));
+        match &self.inner {
+            Some(name) => hybrid::soft_tfidf(
+                &self.idf,
+                &s1,
+                &s2,
+                &|a, b| similarity_by_name(a, b, name),
+                self.threshold,
+            ),
+            None => hybrid::soft_tfidf(&self.idf, &s1, &s2, &jaro_winkler_inner, self.threshold),
+        }
+    }
+}
+
+// ============================================================================
+// Corpus Models - Feature weights fitted on a corpus of documents
+// ============================================================================
+
+// Sparse vector - Parallel arrays of feature indices and weights
+#[wasm_bindgen]
+pub struct SparseVector {
+    indices: Vec<u32>,
+    values: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl SparseVector {
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Vec<u32> {
+        self.indices.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn values(&self) -> Vec<f64> {
+        self.values.clone()
+    }
+}
+
+// TF-IDF model - Cosine similarity of TF-IDF vectors, so common words weigh less
+#[wasm_bindgen]
+pub struct TfIdfModel {
+    model: tfidf::TfIdf,
+}
+
+impl TfIdfModel {
+    pub(crate) fn try_new(
+        corpus: Vec<String>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        sublinear_tf: Option<bool>,
+        smooth_idf: Option<bool>,
+        preprocessor: Option<String>,
+    ) -> Result<TfIdfModel, String> {
+        let analyzer = tfidf::Analyzer::parse(analyzer.as_deref().unwrap_or("word"))?;
+        let ngram_min = ngram_min.map_or(analyzer.default_ngram(), |n| n.max(1) as usize);
+        let ngram_max = ngram_max.map_or(ngram_min, |n| (n as usize).max(ngram_min));
+        let options = tfidf::TfIdfOptions {
+            analyzer,
+            ngram_range: (ngram_min, ngram_max),
+            sublinear_tf: sublinear_tf.unwrap_or(false),
+            smooth_idf: smooth_idf.unwrap_or(true),
+            preprocessor: preprocessor.unwrap_or_default(),
+        };
+
+        Ok(TfIdfModel {
+            model: tfidf::TfIdf::fit(&corpus, options)?,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl TfIdfModel {
+    // `analyzer` is "word" (default) or "char"; n-grams default to single words or
+    // character trigrams, `sublinear_tf` to false and `smooth_idf` to true
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        corpus: Vec<String>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        sublinear_tf: Option<bool>,
+        smooth_idf: Option<bool>,
+        preprocessor: Option<String>,
+    ) -> Result<TfIdfModel, JsError> {
+        TfIdfModel::try_new(
+            corpus,
+            analyzer,
+            ngram_min,
+            ngram_max,
+            sublinear_tf,
+            smooth_idf,
+            preprocessor,
+        )
+        .map_err(|e| JsError::new(&e))
+    }
+
+    pub fn similarity(&self, s1: &str, s2: &str) -> f64 {
+        self.model.similarity(s1, s2)
+    }
+
+    pub fn vectorize(&self, text: &str) -> SparseVector {
+        let (indices, values) = self.model.vectorize(text).into_iter().unzip();
+        SparseVector { indices, values }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn vocabulary_size(&self) -> usize {
+        self.model.vocabulary_size()
+    }
+
+    pub fn feature_names(&self) -> Vec<String> {
+        self.model.feature_names()
+    }
+
+    // Serialize the fitted model to JSON
+    pub fn serialize(&self) -> String {
+        self.model.to_json()
+    }
+
+    pub fn deserialize(json: &str) -> Result<TfIdfModel, JsError> {
+        tfidf::TfIdf::from_json(json)
+            .map(|model| TfIdfModel { model })
+            .map_err(|e| JsError::new(&e))
+    }
+}
+
+// ============================================================================
+// Search Indexes - Ranked retrieval over a collection of documents
+// ============================================================================
+
+// Search hit - Id of a matched document and its score
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchHit {
+    pub id: u32,
+    pub score: f64,
+}
+
+// BM25 index - Okapi BM25, BM25+ or BM25L ranking over word or character n-grams
+#[wasm_bindgen]
+pub struct Bm25Index {
+    index: bm25::Bm25Index,
+}
+
+impl Bm25Index {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_new(
+        variant: Option<String>,
+        k1: Option<f64>,
+        b: Option<f64>,
+        delta: Option<f64>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        preprocessor: Option<String>,
+    ) -> Result<Bm25Index, String> {
+        let variant = bm25::Bm25Variant::parse(variant.as_deref().unwrap_or("bm25"))?;
+        let analyzer = tfidf::Analyzer::parse(analyzer.as_deref().unwrap_or("word"))?;
+        let ngram_min = ngram_min.map_or(analyzer.default_ngram(), |n| n.max(1) as usize);
+        let ngram_max = ngram_max.map_or(ngram_min, |n| (n as usize).max(ngram_min));
+        let params = bm25::Bm25Params {
+            variant,
+            k1: k1.unwrap_or(1.2),
+            b: b.unwrap_or(0.75),
+            delta: delta.unwrap_or_else(|| variant.default_delta()),
+            analyzer,
+            ngram_range: (ngram_min, ngram_max),
+            preprocessor: parse_preprocessor(preprocessor.as_deref())?,
+        };
+
+        Ok(Bm25Index {
+            index: bm25::Bm25Index::new(params),
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl Bm25Index {
+    // `variant` is "bm25" (default), "bm25+" or "bm25l"; `k1` defaults to 1.2, `b` to 0.75
+    // and `delta` to 1.0 for BM25+ and 0.5 for BM25L
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        variant: Option<String>,
+        k1: Option<f64>,
+        b: Option<f64>,
+        delta: Option<f64>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        preprocessor: Option<String>,
+    ) -> Result<Bm25Index, JsError> {
+        Bm25Index::try_new(
+            variant,
+            k1,
+            b,
+            delta,
+            analyzer,
+            ngram_min,
+            ngram_max,
+            preprocessor,
+        )
+        .map_err(|e| JsError::new(&e))
+    }
+
+    // Index a document and return its id
+    pub fn add(&mut self, text: &str) -> u32 {
+        self.index.add(text)
+    }
+
+    pub fn remove(&mut self, id: u32) -> bool {
+        self.index.remove(id)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.index.len()
+    }
+
+    // Top `k` documents (default 10) by descending score
+    pub fn query(&self, query: &str, k: Option<u32>) -> Vec<SearchHit> {
+        self.index
+            .query(query, k.map_or(10, |k| k as usize))
+            .into_iter()
+            .map(|(id, score)| SearchHit { id, score })
+            .collect()
+    }
+}
+
+// Term match - Indexed term and its distance to the query
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermMatch {
+    pub term: String,
+    pub distance: u32,
+}
+
+fn term_matches(matches: Vec<(&str, usize)>) -> Vec<TermMatch> {
+    matches
+        .into_iter()
+        .map(|(term, distance)| TermMatch {
+            term: term.to_string(),
+            distance: distance as u32,
+        })
+        .collect()
+}
+
+// Distance hit - Id of a matched string and its edit distance to the query
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DistanceHit {
+    pub id: u32,
+    pub distance: u32,
+}
+
+// N-gram index - Exact thresholded Levenshtein search pruned by the q-gram count lemma
+#[wasm_bindgen]
+pub struct NgramIndex {
+    index: ngram_index::NgramIndex,
+}
+
+#[wasm_bindgen]
+impl NgramIndex {
+    // `q` defaults to 2
+    #[wasm_bindgen(constructor)]
+    pub fn new(q: Option<u32>) -> NgramIndex {
+        NgramIndex {
+            index: ngram_index::NgramIndex::new(q.unwrap_or(2) as usize),
+        }
+    }
+
+    // Index a string and return its id
+    pub fn insert(&mut self, text: &str) -> u32 {
+        self.index.insert(text)
+    }
+
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.index.delete(id)
+    }
+
+    pub fn get(&self, id: u32) -> Option<String> {
+        self.index.get(id).map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.index.len()
+    }
+
+    // Every string within distance `k`, closest first
+    pub fn search(&self, query: &str, k: u32) -> Vec<DistanceHit> {
+        self.index
+            .search(query, k as usize)
+            .into_iter()
+            .map(|(id, distance)| DistanceHit {
+                id,
+                distance: distance as u32,
+            })
+            .collect()
+    }
+}
+
+// BK-tree - Terms within an edit distance of a query without scanning every term
+#[wasm_bindgen]
+pub struct BkTree {
+    tree: bktree::BkTree,
+}
+
+#[wasm_bindgen]
+impl BkTree {
+    // `metric` is "levenshtein" (default), "damerau_levenshtein", "hamming" or "myers"
+    #[wasm_bindgen(constructor)]
+    pub fn new(metric: Option<String>) -> Result<BkTree, JsError> {
+        let name = metric.as_deref().unwrap_or("levenshtein");
+        let metric = bktree::Metric::parse(name)
+            .ok_or_else(|| JsError::new(&format!("unsupported BK-tree metric: {}", name)))?;
+        Ok(BkTree {
+            tree: bktree::BkTree::new(metric),
+        })
+    }
+
+    // Insert a term, returning false if it is already present
+    pub fn insert(&mut self, term: &str) -> bool {
+        self.tree.insert(term)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.tree.len()
+    }
+
+    // All terms within distance `k`, closest first
+    pub fn within(&self, query: &str, k: u32) -> Vec<TermMatch> {
+        term_matches(self.tree.within(query, k as usize))
+    }
+
+    // The `n` closest terms, closest first
+    pub fn nearest(&self, query: &str, n: u32) -> Vec<TermMatch> {
+        term_matches(self.tree.nearest(query, n as usize))
+    }
+}
+
+// Term dictionary - Fuzzy term expansion with Levenshtein automata over an FST
+#[wasm_bindgen]
+pub struct TermDictionary {
+    terms: automaton::TermSet,
+}
+
+#[wasm_bindgen]
+impl TermDictionary {
+    #[wasm_bindgen(constructor)]
+    pub fn new(terms: Vec<String>) -> TermDictionary {
+        TermDictionary {
+            terms: automaton::TermSet::new(&terms),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn contains(&self, term: &str) -> bool {
+        self.terms.contains(term)
+    }
+
+    // All terms within distance `k` (at most 3) in lexicographic order; with
+    // `transpositions` an adjacent swap counts as one edit
+    pub fn fuzzy_search(
+        &self,
+        query: &str,
+        k: u32,
+        transpositions: Option<bool>,
+    ) -> Result<Vec<TermMatch>, JsError> {
+        let k = k.min(u8::MAX as u32) as u8;
+        let matches = self
+            .terms
+            .fuzzy(query, k, transpositions.unwrap_or(false))
+            .map_err(|e| JsError::new(&e))?;
+        Ok(matches
+            .into_iter()
+            .map(|(term, distance)| TermMatch {
+                term,
+                distance: distance as u32,
+            })
+            .collect())
+    }
+}
+
+// Neighbor - Indexed item, its position in the indexed list and its distance to the query
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Neighbor {
+    pub id: u32,
+    pub term: String,
+    pub distance: f64,
+}
+
+// VP-tree - Nearest neighbours under normalized metrics with float distances
+#[wasm_bindgen]
+pub struct VpTree {
+    tree: vptree::VpTree,
+}
+
+impl VpTree {
+    fn neighbors(&self, matches: Vec<(u32, f64)>) -> Vec<Neighbor> {
+        matches
+            .into_iter()
+            .map(|(id, distance)| Neighbor {
+                id,
+                term: self.tree.get(id).unwrap_or_default().to_string(),
+                distance,
+            })
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl VpTree {
+    // `metric` is "yujian_bo" (normalized Levenshtein, default), "jaccard" or "jaccard_bigram"
+    #[wasm_bindgen(constructor)]
+    pub fn new(items: Vec<String>, metric: Option<String>) -> Result<VpTree, JsError> {
+        let name = metric.as_deref().unwrap_or("yujian_bo");
+        let metric = vptree::FloatMetric::parse(name)
+            .ok_or_else(|| JsError::new(&format!("unsupported VP-tree metric: {}", name)))?;
+        Ok(VpTree {
+            tree: vptree::VpTree::new(items, metric),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.tree.len()
+    }
+
+    // All items within distance `radius`, closest first
+    pub fn within(&self, query: &str, radius: f64) -> Vec<Neighbor> {
+        self.neighbors(self.tree.within(query, radius))
+    }
+
+    // The `n` closest items, closest first
+    pub fn nearest(&self, query: &str, n: u32) -> Vec<Neighbor> {
+        self.neighbors(self.tree.nearest(query, n as usize))
+    }
+}
+
+// Vector hit - Id of a matched vector and its distance to the query
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorHit {
+    pub id: u32,
+    pub distance: f32,
+}
+
+fn vector_hits(hits: Vec<(u32, f32)>) -> Vec<VectorHit> {
+    hits.into_iter()
+        .map(|(id, distance)| VectorHit { id, distance })
+        .collect()
+}
+
+// HNSW index - Approximate nearest neighbours of Float32Array embeddings
+#[wasm_bindgen]
+pub struct HnswIndex {
+    index: hnsw::Hnsw,
+}
+
+#[wasm_bindgen]
+impl HnswIndex {
+    // `metric` is "cosine" (default), "l2" or "ip" (inner product); `m` is raised to at least 2
+    // and the beam widths to at least 1
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        dim: usize,
+        metric: Option<String>,
+        m: Option<usize>,
+        ef_construction: Option<usize>,
+        ef_search: Option<usize>,
+    ) -> Result<HnswIndex, JsError> {
+        let name = metric.as_deref().unwrap_or("cosine");
+        let metric = hnsw::HnswMetric::parse(name)
+            .ok_or_else(|| JsError::new(&format!("unsupported HNSW metric: {}", name)))?;
+        let defaults = hnsw::HnswParams::default();
+        let params = hnsw::HnswParams {
+            m: m.unwrap_or(defaults.m),
+            ef_construction: ef_construction.unwrap_or(defaults.ef_construction),
+            ef_search: ef_search.unwrap_or(defaults.ef_search),
+            ..defaults
+        };
+        Ok(HnswIndex {
+            index: hnsw::Hnsw::new(dim, metric, params),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.index.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn dim(&self) -> usize {
+        self.index.dim()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ef_search(&self) -> usize {
+        self.index.ef_search()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        self.index.set_ef_search(ef_search);
+    }
+
+    // Index a vector under an id that is not already present
+    pub fn insert(&mut self, id: u32, vector: &[f32]) -> Result<(), JsError> {
+        self.index.insert(id, vector).map_err(|e| JsError::new(&e))
+    }
+
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.index.delete(id)
+    }
+
+    // The `k` nearest vectors, closest first, optionally restricted to the ids in `allowed`
+    pub fn search(
+        &self,
+        query: &[f32],
+        k: u32,
+        allowed: Option<Vec<u32>>,
+    ) -> Result<Vec<VectorHit>, JsError> {
+        let allowed: Option<std::collections::HashSet<u32>> =
+            allowed.map(|ids| ids.into_iter().collect());
+        let filter = allowed.as_ref().map(|ids| move |id: u32| ids.contains(&id));
+        self.index
+            .search(
+                query,
+                k as usize,
+                filter.as_ref().map(|f| f as &dyn Fn(u32) -> bool),
+            )
+            .map(vector_hits)
+            .map_err(|e| JsError::new(&e))
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        self.index.to_bytes()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Result<HnswIndex, JsError> {
+        hnsw::Hnsw::from_bytes(bytes)
+            .map(|index| HnswIndex { index })
+            .map_err(|e| JsError::new(&e))
+    }
+}
+
+// Quantized vector store - Int8 and sign-bit codes searched with full-precision queries
+#[wasm_bindgen]
+pub struct QuantizedStore {
+    store: quantize::QuantizedStore,
+}
+
+#[wasm_bindgen]
+impl QuantizedStore {
+    // `metric` is "cosine" (default), "l2" or "ip" (inner product)
+    #[wasm_bindgen(constructor)]
+    pub fn new(dim: usize, metric: Option<String>) -> Result<QuantizedStore, JsError> {
+        let name = metric.as_deref().unwrap_or("cosine");
+        let metric = hnsw::HnswMetric::parse(name)
+            .ok_or_else(|| JsError::new(&format!("unsupported vector metric: {}", name)))?;
+        Ok(QuantizedStore {
+            store: quantize::QuantizedStore::new(dim, metric),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.store.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn dim(&self) -> usize {
+        self.store.dim()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn memory_bytes(&self) -> usize {
+        self.store.memory_bytes()
+    }
+
+    // Store a vector, replacing any vector with the same id
+    pub fn insert(&mut self, id: u32, vector: &[f32]) -> Result<(), JsError> {
+        self.store.insert(id, vector).map_err(|e| JsError::new(&e))
+    }
+
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.store.delete(id)
+    }
+
+    // The stored vector restored from its int8 codes
+    pub fn get(&self, id: u32) -> Option<Vec<f32>> {
+        self.store.get(id)
+    }
+
+    // The `k` closest vectors by scanning the int8 codes
+    pub fn search(&self, query: &[f32], k: u32) -> Result<Vec<VectorHit>, JsError> {
+        self.store
+            .search(query, k as usize)
+            .map(vector_hits)
+            .map_err(|e| JsError::new(&e))
+    }
+
+    // Shortlist `k * oversample` (default 4) vectors by Hamming distance, then rescore them
+    pub fn search_binary(
+        &self,
+        query: &[f32],
+        k: u32,
+        oversample: Option<u32>,
+    ) -> Result<Vec<VectorHit>, JsError> {
+        self.store
+            .search_binary(query, k as usize, oversample.unwrap_or(4) as usize)
+            .map(vector_hits)
+            .map_err(|e| JsError::new(&e))
+    }
+}
+
+// ============================================================================
+// Spelling Correction - Suggestions from a word frequency dictionary
+// ============================================================================
+
+// Suggestion - Dictionary word, its distance to the input and its frequency
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub term: String,
+    pub distance: u32,
+    pub count: f64,
+}
+
+impl From<symspell::Suggestion> for Suggestion {
+    fn from(suggestion: symspell::Suggestion) -> Suggestion {
+        Suggestion {
+            term: suggestion.term,
+            distance: suggestion.distance as u32,
+            count: suggestion.count as f64,
+        }
+    }
+}
+
+// Segmentation - Space-separated words as typed and as corrected
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segmentation {
+    pub segmented: String,
+    pub corrected: String,
+    pub distance_sum: u32,
+    pub log_probability_sum: f64,
+}
+
+// SymSpell - Symmetric delete index for fast spelling suggestions
+#[wasm_bindgen]
+pub struct SymSpell {
+    index: symspell::SymSpell,
+}
+
+impl SymSpell {
+    pub(crate) fn try_lookup(
+        &self,
+        input: &str,
+        verbosity: Option<String>,
+        max_edit_distance: Option<u32>,
+    ) -> Result<Vec<Suggestion>, String> {
+        let verbosity = symspell::Verbosity::parse(verbosity.as_deref().unwrap_or("top"))?;
+        let max_edit_distance = max_edit_distance.map_or(usize::MAX, |d| d as usize);
+        Ok(self
+            .index
+            .lookup(input, verbosity, max_edit_distance)
+            .into_iter()
+            .map(Suggestion::from)
+            .collect())
+    }
+}
+
+#[wasm_bindgen]
+impl SymSpell {
+    // `max_edit_distance` defaults to 2 and `prefix_length` to 7
+    #[wasm_bindgen(constructor)]
+    pub fn new(max_edit_distance: Option<u32>, prefix_length: Option<u32>) -> SymSpell {
+        SymSpell {
+            index: symspell::SymSpell::new(
+                max_edit_distance.unwrap_or(2) as usize,
+                prefix_length.unwrap_or(7) as usize,
+            ),
+        }
+    }
+
+    // Add a word or increase its frequency (default 1)
+    pub fn add_word(&mut self, word: &str, count: Option<f64>) {
+        self.index.add_word(word, count.unwrap_or(1.0) as u64);
+    }
+
+    // Load "word count" lines, returning the number of words loaded
+    pub fn load_dictionary(&mut self, text: &str) -> u32 {
+        self.index.load_dictionary(text) as u32
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.index.len()
+    }
+
+    // `verbosity` is "top" (default), "closest" or "all"; `max_edit_distance` defaults to
+    // and is capped by the index's
+    pub fn lookup(
+        &self,
+        input: &str,
+        verbosity: Option<String>,
+        max_edit_distance: Option<u32>,
+    ) -> Result<Vec<Suggestion>, JsError> {
+        self.try_lookup(input, verbosity, max_edit_distance)
+            .map_err(|e| JsError::new(&e))
+    }
+
+    // Split text without spaces into its most probable words; a `max_edit_distance`
+    // above 0 (the default) also corrects each word
+    pub fn word_segmentation(&self, input: &str, max_edit_distance: Option<u32>) -> Segmentation {
+        let max_edit_distance = max_edit_distance.map_or(0, |d| d as usize);
+        let result = self.index.word_segmentation(input, max_edit_distance);
+        Segmentation {
+            segmented: result.segments.join(" "),
+            corrected: result.corrected.join(" "),
+            distance_sum: result.distance_sum as u32,
+            log_probability_sum: result.log_probability_sum,
+        }
+    }
 }
 
 // ============================================================================
@@ -157,49 +1100,337 @@ pub fn overlap(s1: &str, s2: &str) -> f64 {
 
 // Prefix similarity - Prefix matching
 #[wasm_bindgen]
-pub fn prefix(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::prefix(s1, s2)
+pub fn prefix(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::prefix(&s1, &s2)
 }
 
 // Suffix similarity - Suffix matching
 #[wasm_bindgen]
-pub fn suffix(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::suffix(s1, s2)
+pub fn suffix(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::suffix(&s1, &s2)
 }
 
 // Length similarity - Length difference
 #[wasm_bindgen]
-pub fn length(s1: &str, s2: &str) -> f64 {
-    textdistance::nstr::length(s1, s2)
+pub fn length(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    textdistance::nstr::length(&s1, &s2)
 }
 
 // ============================================================================
 // Bigram Algorithms - Character pair based comparison
 // ============================================================================
 
+// With the `simd` feature the bigram multisets are intersected by the lane kernels
+#[cfg(feature = "simd")]
+use simd::{bigram_cosine, bigram_jaccard};
+
+#[cfg(not(feature = "simd"))]
+fn bigram_jaccard(s1: &str, s2: &str) -> f64 {
+    use textdistance::Algorithm;
+    textdistance::Jaccard::default().for_bigrams(s1, s2).nsim()
+}
+
+#[cfg(not(feature = "simd"))]
+fn bigram_cosine(s1: &str, s2: &str) -> f64 {
+    use textdistance::Algorithm;
+    textdistance::Cosine::default().for_bigrams(s1, s2).nsim()
+}
+
 // Jaccard Bigram similarity
 #[wasm_bindgen]
-pub fn jaccard_bigram(s1: &str, s2: &str) -> f64 {
-    let jaccard = textdistance::Jaccard::default();
-    let result = jaccard.for_bigrams(s1, s2);
-    result.nsim()
+pub fn jaccard_bigram(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    bigram_jaccard(&s1, &s2)
 }
 
 // Cosine Bigram similarity
 #[wasm_bindgen]
-pub fn cosine_bigram(s1: &str, s2: &str) -> f64 {
-    let cosine = textdistance::Cosine::default();
-    let result = cosine.for_bigrams(s1, s2);
-    result.nsim()
+pub fn cosine_bigram(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    bigram_cosine(&s1, &s2)
+}
+
+// ============================================================================
+// Preprocessing - Options shared by every scorer
+// ============================================================================
+
+// Preprocess - Apply normalization, case folding and cleanup options to a string
+#[wasm_bindgen]
+pub fn preprocess(text: &str, preprocessor: &str) -> String {
+    Preprocessor::parse(preprocessor).process(text)
+}
+
+// Validate preprocessor - Reject option names that scorers would silently ignore
+#[wasm_bindgen]
+pub fn validate_preprocessor(preprocessor: &str) -> Result<(), JsError> {
+    Preprocessor::try_parse(preprocessor)
+        .map(|_| ())
+        .map_err(|e| JsError::new(&e))
+}
+
+// Transliterate - Convert Cyrillic, Greek, Arabic, Hebrew, kana and Latin-extended text to ASCII
+#[wasm_bindgen]
+pub fn transliterate(text: &str) -> String {
+    transliterate::transliterate(text)
+}
+
+// Fold kana - Fold character width, katakana to hiragana and long vowel marks
+#[wasm_bindgen]
+pub fn fold_kana(text: &str) -> String {
+    kana::fold_kana(text)
+}
+
+// ============================================================================
+// Pinyin Algorithms - Chinese text compared by pronunciation
+// ============================================================================
+
+// Pinyin conversion - Render Hanzi as tone, toneless or initials pinyin, where
+// initials are first letters (zh, ch and sh become z, c and s)
+#[wasm_bindgen]
+pub fn to_pinyin(text: &str, style: &str) -> Result<String, JsError> {
+    try_to_pinyin(text, style).map_err(|e| JsError::new(&e))
+}
+
+pub(crate) fn try_to_pinyin(text: &str, style: &str) -> Result<String, String> {
+    let style = pinyin::PinyinStyle::parse(style)?;
+    Ok(pinyin::to_syllables(text, style).join(" "))
+}
+
+// Pinyin similarity - Normalized Myers similarity of the pinyin renderings
+#[wasm_bindgen]
+pub fn pinyin_similarity(
+    s1: &str,
+    s2: &str,
+    style: &str,
+    preprocessor: Option<String>,
+) -> Result<f64, JsError> {
+    try_pinyin_similarity(s1, s2, style, preprocessor).map_err(|e| JsError::new(&e))
+}
+
+pub(crate) fn try_pinyin_similarity(
+    s1: &str,
+    s2: &str,
+    style: &str,
+    preprocessor: Option<String>,
+) -> Result<f64, String> {
+    let style = pinyin::PinyinStyle::parse(style)?;
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    Ok(pinyin::pinyin_similarity(&s1, &s2, style))
+}
+
+// Pinyin initials match - Query types each Hanzi as its syllable or a prefix of it
+#[wasm_bindgen]
+pub fn pinyin_initials_match(text: &str, query: &str) -> bool {
+    pinyin::pinyin_initials_match(text, query)
+}
+
+// ============================================================================
+// Vector Similarity - Dense embeddings passed as Float32Array
+// ============================================================================
+
+fn vector_metric(name: &str) -> Result<vector::VectorMetric, String> {
+    vector::VectorMetric::parse(name).ok_or_else(|| format!("unsupported vector metric: {}", name))
+}
+
+// Vector cosine - Cosine similarity of two vectors
+#[wasm_bindgen]
+pub fn vector_cosine(a: &[f32], b: &[f32]) -> Result<f32, JsError> {
+    vector::pairwise(a, b, vector::VectorMetric::Cosine).map_err(|e| JsError::new(&e))
+}
+
+// Vector dot - Dot product of two vectors
+#[wasm_bindgen]
+pub fn vector_dot(a: &[f32], b: &[f32]) -> Result<f32, JsError> {
+    vector::pairwise(a, b, vector::VectorMetric::Dot).map_err(|e| JsError::new(&e))
+}
+
+// Vector Euclidean - L2 distance of two vectors
+#[wasm_bindgen]
+pub fn vector_euclidean(a: &[f32], b: &[f32]) -> Result<f32, JsError> {
+    vector::pairwise(a, b, vector::VectorMetric::Euclidean).map_err(|e| JsError::new(&e))
+}
+
+// Vector Manhattan - L1 distance of two vectors
+#[wasm_bindgen]
+pub fn vector_manhattan(a: &[f32], b: &[f32]) -> Result<f32, JsError> {
+    vector::pairwise(a, b, vector::VectorMetric::Manhattan).map_err(|e| JsError::new(&e))
+}
+
+// Vector one-to-many - Compare a query with every row of a flat row-major matrix
+// `metric` is "cosine", "dot", "euclidean" or "manhattan"
+#[wasm_bindgen]
+pub fn vector_one_to_many(
+    query: &[f32],
+    matrix: &[f32],
+    metric: &str,
+) -> Result<Vec<f32>, JsError> {
+    vector_metric(metric)
+        .and_then(|metric| vector::one_to_many(query, matrix, metric))
+        .map_err(|e| JsError::new(&e))
+}
+
+// Vector cdist - Compare every row of `queries` with every row of `choices`, row-major by query
+#[wasm_bindgen]
+pub fn vector_cdist(
+    queries: &[f32],
+    choices: &[f32],
+    dim: usize,
+    metric: &str,
+) -> Result<Vec<f32>, JsError> {
+    vector_metric(metric)
+        .and_then(|metric| vector::cdist(queries, choices, dim, metric))
+        .map_err(|e| JsError::new(&e))
 }
 
 // ============================================================================
 // Universal comparison function - Call different algorithms by name
 // ============================================================================
 
+// Cdist - Compare every query with every choice by algorithm name, row-major by query
 #[wasm_bindgen]
-pub fn compare(s1: &str, s2: &str, algorithm: &str) -> f64 {
-    match algorithm.to_lowercase().as_str() {
+pub fn cdist(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    algorithm: &str,
+    preprocessor: Option<String>,
+) -> Result<Vec<f64>, JsError> {
+    try_cdist(queries, choices, algorithm, preprocessor).map_err(|e| JsError::new(&e))
+}
+
+pub(crate) fn try_cdist(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    algorithm: &str,
+    preprocessor: Option<String>,
+) -> Result<Vec<f64>, String> {
+    let p = parse_preprocessor(preprocessor.as_deref())?;
+    let queries: Vec<String> = queries.iter().map(|q| p.process(q)).collect();
+    let choices: Vec<String> = choices.iter().map(|c| p.process(c)).collect();
+    Ok(batch::cdist(&queries, &choices, &|a, b| {
+        similarity_by_name(a, b, algorithm)
+    }))
+}
+
+// Extract - Choices scoring at least `score_cutoff` (default 0) against the query, best first
+#[wasm_bindgen]
+pub fn extract(
+    query: &str,
+    choices: Vec<String>,
+    algorithm: &str,
+    limit: Option<u32>,
+    score_cutoff: Option<f64>,
+    preprocessor: Option<String>,
+) -> Result<Vec<SearchHit>, JsError> {
+    try_extract(query, choices, algorithm, limit, score_cutoff, preprocessor)
+        .map_err(|e| JsError::new(&e))
+}
+
+pub(crate) fn try_extract(
+    query: &str,
+    choices: Vec<String>,
+    algorithm: &str,
+    limit: Option<u32>,
+    score_cutoff: Option<f64>,
+    preprocessor: Option<String>,
+) -> Result<Vec<SearchHit>, String> {
+    let p = parse_preprocessor(preprocessor.as_deref())?;
+    let query = p.process(query);
+    let choices: Vec<String> = choices.iter().map(|c| p.process(c)).collect();
+    let hits = batch::extract(
+        &query,
+        &choices,
+        &|a, b| similarity_by_name(a, b, algorithm),
+        limit.map(|l| l as usize),
+        score_cutoff.unwrap_or(0.0),
+    );
+    Ok(hits
+        .into_iter()
+        .map(|(id, score)| SearchHit {
+            id: id as u32,
+            score,
+        })
+        .collect())
+}
+
+// Dedupe - Index of the first earlier item each item scores at least `threshold` against, or its own
+#[wasm_bindgen]
+pub fn dedupe(
+    items: Vec<String>,
+    algorithm: &str,
+    threshold: f64,
+    preprocessor: Option<String>,
+) -> Result<Vec<u32>, JsError> {
+    try_dedupe(items, algorithm, threshold, preprocessor).map_err(|e| JsError::new(&e))
+}
+
+pub(crate) fn try_dedupe(
+    items: Vec<String>,
+    algorithm: &str,
+    threshold: f64,
+    preprocessor: Option<String>,
+) -> Result<Vec<u32>, String> {
+    let p = parse_preprocessor(preprocessor.as_deref())?;
+    let items: Vec<String> = items.iter().map(|item| p.process(item)).collect();
+    let groups = batch::dedupe(
+        &items,
+        &|a, b| similarity_by_name(a, b, algorithm),
+        threshold,
+    );
+    Ok(groups.into_iter().map(|index| index as u32).collect())
+}
+
+// Parse preprocessor - Optional spec of a constructor or batch export, rejecting unknown options
+fn parse_preprocessor(spec: Option<&str>) -> Result<Preprocessor, String> {
+    Ok(spec
+        .map(Preprocessor::try_parse)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+#[wasm_bindgen]
+pub fn compare(s1: &str, s2: &str, algorithm: &str, preprocessor: Option<String>) -> f64 {
+    let (s1, s2) = preprocess::prepare(s1, s2, preprocessor.as_deref());
+    // Default to Levenshtein
+    score_by_name(&s1, &s2, algorithm).unwrap_or_else(|| textdistance::nstr::levenshtein(&s1, &s2))
+}
+
+// Similarity by name - Higher is always more similar, shared by the batch, hybrid and join paths
+// Distance-valued algorithms are flipped to 1 - normalized distance; unknown names fall back
+// to Levenshtein similarity
+pub(crate) fn similarity_by_name(s1: &str, s2: &str, algorithm: &str) -> f64 {
+    match score_by_name(s1, s2, algorithm) {
+        Some(score) if is_distance(algorithm) => 1.0 - score,
+        Some(score) => score,
+        None => 1.0 - textdistance::nstr::levenshtein(s1, s2),
+    }
+}
+
+// Whether `algorithm` is a name known to compare() rather than the Levenshtein fallback
+#[cfg(feature = "join")]
+pub(crate) fn is_algorithm(algorithm: &str) -> bool {
+    score_by_name("", "", algorithm).is_some()
+}
+
+// Algorithms whose normalized value is a distance (0 for identical strings)
+fn is_distance(algorithm: &str) -> bool {
+    matches!(
+        algorithm.to_lowercase().as_str(),
+        "levenshtein"
+            | "damerau_levenshtein"
+            | "damerau-levenshtein"
+            | "hamming"
+            | "sift4"
+            | "sift4_simple"
+            | "length"
+    )
+}
+
+// Dispatch on algorithm name without preprocessing, as returned by compare()
+fn score_by_name(s1: &str, s2: &str, algorithm: &str) -> Option<f64> {
+    let score = match algorithm.to_lowercase().as_str() {
         "levenshtein" => textdistance::nstr::levenshtein(s1, s2),
         "damerau_levenshtein" | "damerau-levenshtein" => {
             textdistance::nstr::damerau_levenshtein(s1, s2)
@@ -222,15 +1453,20 @@ pub fn compare(s1: &str, s2: &str, algorithm: &str) -> f64 {
         "prefix" => textdistance::nstr::prefix(s1, s2),
         "suffix" => textdistance::nstr::suffix(s1, s2),
         "length" => textdistance::nstr::length(s1, s2),
-        "jaccard_bigram" | "jaccard-bigram" => {
-            let jaccard = textdistance::Jaccard::default();
-            jaccard.for_bigrams(s1, s2).nsim()
-        }
-        "cosine_bigram" | "cosine-bigram" => {
-            let cosine = textdistance::Cosine::default();
-            cosine.for_bigrams(s1, s2).nsim()
-        }
+        "jaccard_bigram" | "jaccard-bigram" => bigram_jaccard(s1, s2),
+        "cosine_bigram" | "cosine-bigram" => bigram_cosine(s1, s2),
         "smith_waterman" | "smith-waterman" => textdistance::nstr::smith_waterman(s1, s2),
-        _ => textdistance::nstr::levenshtein(s1, s2), // Default to Levenshtein
-    }
+        "ratio" | "indel" => fuzz::ratio(s1, s2),
+        "partial_ratio" | "partial-ratio" => fuzz::partial_ratio(s1, s2),
+        "token_sort_ratio" | "token-sort-ratio" => fuzz::token_sort_ratio(s1, s2),
+        "token_set_ratio" | "token-set-ratio" => fuzz::token_set_ratio(s1, s2),
+        "partial_token_sort_ratio" | "partial-token-sort-ratio" => {
+            fuzz::partial_token_sort_ratio(s1, s2)
+        }
+        "wratio" | "w_ratio" | "weighted_ratio" => fuzz::wratio(s1, s2),
+        "monge_elkan" | "monge-elkan" => hybrid::monge_elkan(s1, s2, &jaro_winkler_inner, true),
+        "pinyin" => pinyin::pinyin_similarity(s1, s2, pinyin::PinyinStyle::Toneless),
+        _ => return None,
+    };
+    Some(score)
 }
@@ -4,6 +4,8 @@
 use std::cell::RefCell;
 
 thread_local! {
+    // Character masks by UTF-16 code unit, all zero between calls: every user
+    // resets the entries it set instead of clearing the whole table
     static PEQ_CACHE: RefCell<Vec<u32>> = RefCell::new(vec![0u32; 0x10000]);
 }
 
@@ -25,11 +27,6 @@ fn myers_32(a: &str, b: &str) -> u32 {
     PEQ_CACHE.with(|peq_cache| {
         let mut peq = peq_cache.borrow_mut();
 
-        // Clear peq array efficiently
-        for val in peq.iter_mut() {
-            *val = 0;
-        }
-
         // Build character bit masks for string a
         for (i, &code) in a_encoded.iter().enumerate() {
             unsafe {
@@ -63,12 +60,18 @@ fn myers_32(a: &str, b: &str) -> u32 {
             mv &= xv;
         }
 
+        for &code in &a_encoded {
+            unsafe {
+                *peq.get_unchecked_mut(code as usize) = 0;
+            }
+        }
+
         sc
     })
 }
 
 /// Myers extended implementation for longer strings using block-based approach
-fn myers_x(a: &str, b: &str) -> u32 {
+fn myers_x(a: &str, b: &str, lanes: bool) -> u32 {
     let a_encoded: Vec<u16> = a.encode_utf16().collect();
     let b_encoded: Vec<u16> = b.encode_utf16().collect();
 
@@ -90,24 +93,26 @@ fn myers_x(a: &str, b: &str) -> u32 {
     let mut phc = vec![!0u32; hsize]; // Previous horizontal carry
     let mut mhc = vec![0u32; hsize]; // Previous horizontal mismatch
 
+    // Advance several vertical blocks at once when there are enough of them
+    #[cfg(feature = "simd")]
+    let lanes = lanes && vsize > 2;
+    #[cfg(feature = "simd")]
+    if lanes {
+        let full = (vsize - 1) * word_size;
+        crate::simd::myers_blocks(&a_encoded, &b_encoded[..full], &mut phc, &mut mhc);
+    }
+
     PEQ_CACHE.with(|peq_cache| {
         let mut peq = peq_cache.borrow_mut();
 
+        // Each block clears its own masks when done
         // Process all but the last vertical block
-        for block in 0..(vsize - 1) {
+        for block in 0..(if lanes { 0 } else { vsize - 1 }) {
             let mut mv = 0u32;
             let mut pv = !0u32;
             let start = block * word_size;
             let end = (block + 1) * word_size;
 
-            // Clear peq for this block range
-            for k in start..end.min(m) {
-                let code = unsafe { *b_encoded.get_unchecked(k) };
-                unsafe {
-                    *peq.get_unchecked_mut(code as usize) = 0;
-                }
-            }
-
             // Build character bit masks for this block of string b
             for k in start..end.min(m) {
                 let code = unsafe { *b_encoded.get_unchecked(k) };
@@ -148,6 +153,14 @@ fn myers_x(a: &str, b: &str) -> u32 {
                 pv = mh | !(xv | ph);
                 mv = ph & xv;
             }
+
+            // Clear this block's masks so they cannot leak into the next block
+            for k in start..end.min(m) {
+                let code = unsafe { *b_encoded.get_unchecked(k) };
+                unsafe {
+                    *peq.get_unchecked_mut(code as usize) = 0;
+                }
+            }
         }
 
         // Process the last vertical block and compute final score
@@ -156,14 +169,6 @@ fn myers_x(a: &str, b: &str) -> u32 {
         let start = (vsize - 1) * word_size;
         let vlen = m - start;
 
-        // Clear peq for the last block range
-        for k in start..m {
-            let code = unsafe { *b_encoded.get_unchecked(k) };
-            unsafe {
-                *peq.get_unchecked_mut(code as usize) = 0;
-            }
-        }
-
         // Build character bit masks for the last block
         for k in start..m {
             let code = unsafe { *b_encoded.get_unchecked(k) };
@@ -211,18 +216,29 @@ fn myers_x(a: &str, b: &str) -> u32 {
             mv = ph & xv;
         }
 
+        for &code in &b_encoded[start..] {
+            unsafe {
+                *peq.get_unchecked_mut(code as usize) = 0;
+            }
+        }
+
         score
     })
 }
 
 /// Main Myers distance function
 pub fn myers_distance(a: &str, b: &str) -> u32 {
+    myers_distance_with(a, b, cfg!(feature = "simd"))
+}
+
+/// Myers distance, optionally advancing the blocks of long strings in SIMD lanes
+pub(crate) fn myers_distance_with(a: &str, b: &str, lanes: bool) -> u32 {
     let a_len = a.encode_utf16().count();
     let b_len = b.encode_utf16().count();
 
     // Ensure a is the longer string
     if a_len < b_len {
-        return myers_distance(b, a);
+        return myers_distance_with(b, a, lanes);
     }
 
     if b_len == 0 {
@@ -233,7 +249,7 @@ pub fn myers_distance(a: &str, b: &str) -> u32 {
     if a_len <= 32 {
         myers_32(a, b)
     } else {
-        myers_x(a, b)
+        myers_x(a, b, lanes)
     }
 }
 
@@ -450,6 +466,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_myers_short_last_block() {
+        // The last block lacks characters of the first, whose masks must not linger
+        let a = "a".repeat(41);
+        let b = format!("{}{}", "a".repeat(32), "b".repeat(8));
+        assert_eq!(myers_distance(&a, &b), 9);
+        assert_eq!(myers_distance(&"ab".repeat(20), &"ba".repeat(20)), 2);
+    }
+
+    #[test]
+    fn test_myers_leaves_masks_cleared() {
+        // Calls only reset the masks they set, so each must leave the table empty
+        let long = "the quick brown fox jumps over the lazy dog ".repeat(3);
+        for (a, b) in [("kitten", "sitting"), (long.as_str(), "lazy dogs")] {
+            myers_distance(a, b);
+            PEQ_CACHE.with(|peq| assert!(peq.borrow().iter().all(|&mask| mask == 0)));
+        }
+        assert_eq!(myers_distance("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn test_myers_unicode_handling() {
         // Test with various Unicode characters
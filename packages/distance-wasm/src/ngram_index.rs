@@ -0,0 +1,201 @@
+// N-gram inverted index - exact thresholded edit distance search
+// Strings within edit distance k of the query share at least
+// `max(|s|, |t|) + q - 1 - k * q` padded q-grams (the q-gram count lemma), so
+// only strings passing that count and the length filter are verified
+
+use std::collections::HashMap;
+
+/// Padding before the first and after the last character
+const START: char = '\u{2}';
+const END: char = '\u{3}';
+
+/// Padded q-grams of a string with their multiplicities
+pub(crate) fn qgrams(chars: &[char], q: usize) -> HashMap<String, u32> {
+    let padded: Vec<char> = std::iter::repeat_n(START, q - 1)
+        .chain(chars.iter().copied())
+        .chain(std::iter::repeat_n(END, q - 1))
+        .collect();
+
+    let mut grams: HashMap<String, u32> = HashMap::new();
+    for gram in padded.windows(q) {
+        *grams.entry(gram.iter().collect()).or_insert(0) += 1;
+    }
+    grams
+}
+
+pub struct NgramIndex {
+    q: usize,
+    next_id: u32,
+    /// Indexed strings with their length in characters
+    strings: HashMap<u32, (String, usize)>,
+    /// Q-gram to the ids of the strings containing it and its multiplicity there
+    postings: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl NgramIndex {
+    pub fn new(q: usize) -> NgramIndex {
+        NgramIndex {
+            q: q.max(1),
+            next_id: 0,
+            strings: HashMap::new(),
+            postings: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&str> {
+        self.strings.get(&id).map(|(s, _)| s.as_str())
+    }
+
+    /// Index a string and return its id
+    pub fn insert(&mut self, text: &str) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let chars: Vec<char> = text.chars().collect();
+        for (gram, count) in qgrams(&chars, self.q) {
+            self.postings.entry(gram).or_default().push((id, count));
+        }
+        self.strings.insert(id, (text.to_string(), chars.len()));
+        id
+    }
+
+    /// Remove a string, returning whether it was indexed
+    pub fn delete(&mut self, id: u32) -> bool {
+        let (text, _) = match self.strings.remove(&id) {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        let chars: Vec<char> = text.chars().collect();
+        for gram in qgrams(&chars, self.q).keys() {
+            if let Some(ids) = self.postings.get_mut(gram) {
+                ids.retain(|&(other, _)| other != id);
+                if ids.is_empty() {
+                    self.postings.remove(gram);
+                }
+            }
+        }
+        true
+    }
+
+    /// Every string within Levenshtein distance `k` as `(id, distance)`, by distance then id
+    pub fn search(&self, query: &str, k: usize) -> Vec<(u32, usize)> {
+        let chars: Vec<char> = query.chars().collect();
+        let query_len = chars.len();
+
+        // Shared q-gram counts of every string sharing at least one q-gram
+        let mut shared: HashMap<u32, usize> = HashMap::new();
+        for (gram, count) in qgrams(&chars, self.q) {
+            if let Some(ids) = self.postings.get(&gram) {
+                for &(id, other) in ids {
+                    *shared.entry(id).or_insert(0) += count.min(other) as usize;
+                }
+            }
+        }
+
+        let passes = |len: usize, common: usize| {
+            let longest = len.max(query_len);
+            longest - len.min(query_len) <= k && common + k * self.q >= longest + self.q - 1
+        };
+
+        // When the lemma's bound is not positive a string may share no q-gram at all
+        let candidates: Vec<u32> = if query_len + self.q - 1 <= k * self.q {
+            self.strings
+                .iter()
+                .filter(|(id, &(_, len))| passes(len, shared.get(id).copied().unwrap_or(0)))
+                .map(|(&id, _)| id)
+                .collect()
+        } else {
+            shared
+                .iter()
+                .filter(|(id, &common)| passes(self.strings[id].1, common))
+                .map(|(&id, _)| id)
+                .collect()
+        };
+
+        let mut matches: Vec<(u32, usize)> = candidates
+            .into_iter()
+            .filter_map(|id| {
+                let distance = textdistance::str::levenshtein(query, &self.strings[&id].0);
+                if distance <= k {
+                    Some((id, distance))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        matches.sort_unstable_by_key(|&(id, distance)| (distance, id));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 10] = [
+        "search", "research", "searches", "seat", "sea", "reach", "starch", "serch", "", "séarch",
+    ];
+
+    fn index(q: usize) -> NgramIndex {
+        let mut index = NgramIndex::new(q);
+        for word in WORDS.iter() {
+            index.insert(word);
+        }
+        index
+    }
+
+    #[test]
+    fn test_qgrams() {
+        let grams = qgrams(&['a', 'a', 'a'], 2);
+        assert_eq!(grams.values().sum::<u32>(), 4);
+        assert_eq!(grams["aa"], 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let index = index(2);
+        assert_eq!(
+            index.search("search", 1),
+            vec![(0, 0), (6, 1), (7, 1), (9, 1)]
+        );
+        assert_eq!(index.search("xyz", 1), vec![]);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for q in 1..=3 {
+            let index = index(q);
+            for query in ["search", "serach", "se", "", "reseach", "x"].iter() {
+                for k in 0..=4 {
+                    let mut expected: Vec<(u32, usize)> = WORDS
+                        .iter()
+                        .enumerate()
+                        .map(|(id, w)| (id as u32, textdistance::str::levenshtein(query, w)))
+                        .filter(|&(_, d)| d <= k)
+                        .collect();
+                    expected.sort_unstable_by_key(|&(id, d)| (d, id));
+                    assert_eq!(index.search(query, k), expected, "q={} {} {}", q, query, k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_insert_delete() {
+        let mut index = index(2);
+        let id = index.insert("searcher");
+        assert_eq!(index.get(id), Some("searcher"));
+        assert!(index.search("searcher", 0).contains(&(id, 0)));
+
+        assert!(index.delete(id));
+        assert!(!index.delete(id));
+        assert_eq!(index.len(), WORDS.len());
+        assert!(index.search("searcher", 0).is_empty());
+        assert!(!index.postings.contains_key("r\u{3}"));
+    }
+}
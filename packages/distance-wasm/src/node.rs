@@ -0,0 +1,1161 @@
+// Node.js bindings - the wasm API as a native addon through N-API
+// Every export delegates to the function backing the wasm export of the same
+// name, so both packages share one implementation, and tests/node runs one JS
+// suite against each of them. Names are kept snake_case so the two builds are
+// drop-in replacements. Index classes wrap the same index types as their wasm
+// counterparts
+
+use napi::bindgen_prelude::{Buffer, Float32Array};
+use napi_derive::napi;
+
+fn napi_error(message: String) -> napi::Error {
+    napi::Error::from_reason(message)
+}
+
+// ============================================================================
+// Edit-based Algorithms
+// ============================================================================
+
+#[napi(js_name = "levenshtein")]
+pub fn levenshtein(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::levenshtein(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "levenshtein_normalized")]
+pub fn levenshtein_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::levenshtein_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "damerau_levenshtein")]
+pub fn damerau_levenshtein(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::damerau_levenshtein(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "damerau_levenshtein_normalized")]
+pub fn damerau_levenshtein_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::damerau_levenshtein_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "jaro")]
+pub fn jaro(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::jaro(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "jarowinkler")]
+pub fn jarowinkler(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::jarowinkler(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "hamming")]
+pub fn hamming(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::hamming(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "hamming_normalized")]
+pub fn hamming_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::hamming_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "sift4_simple")]
+pub fn sift4_simple(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::sift4_simple(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "sift4_simple_normalized")]
+pub fn sift4_simple_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::sift4_simple_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "myers_levenshtein")]
+pub fn myers_levenshtein(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::myers_levenshtein(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "myers_levenshtein_normalized")]
+pub fn myers_levenshtein_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::myers_levenshtein_normalized(&s1, &s2, preprocessor)
+}
+
+// ============================================================================
+// Sequence and Token Algorithms
+// ============================================================================
+
+#[napi(js_name = "lcs_seq")]
+pub fn lcs_seq(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::lcs_seq(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "lcs_seq_normalized")]
+pub fn lcs_seq_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::lcs_seq_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "lcs_str")]
+pub fn lcs_str(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::lcs_str(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "lcs_str_normalized")]
+pub fn lcs_str_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::lcs_str_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "ratcliff_obershelp")]
+pub fn ratcliff_obershelp(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::ratcliff_obershelp(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "jaccard")]
+pub fn jaccard(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::jaccard(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "cosine")]
+pub fn cosine(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::cosine(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "sorensen")]
+pub fn sorensen(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::sorensen(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "tversky")]
+pub fn tversky(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::tversky(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "smith_waterman")]
+pub fn smith_waterman(s1: String, s2: String, preprocessor: Option<String>) -> u32 {
+    crate::smith_waterman(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "smith_waterman_normalized")]
+pub fn smith_waterman_normalized(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::smith_waterman_normalized(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "overlap")]
+pub fn overlap(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::overlap(&s1, &s2, preprocessor)
+}
+
+// ============================================================================
+// Fuzzy Ratios
+// ============================================================================
+
+#[napi(js_name = "ratio")]
+pub fn ratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::ratio(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "partial_ratio")]
+pub fn partial_ratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::partial_ratio(&s1, &s2, preprocessor)
+}
+
+#[napi(object, js_name = "ScoreAlignment")]
+pub struct ScoreAlignment {
+    pub score: f64,
+    #[napi(js_name = "src_start")]
+    pub src_start: u32,
+    #[napi(js_name = "src_end")]
+    pub src_end: u32,
+    #[napi(js_name = "dest_start")]
+    pub dest_start: u32,
+    #[napi(js_name = "dest_end")]
+    pub dest_end: u32,
+}
+
+#[napi(js_name = "partial_ratio_alignment")]
+pub fn partial_ratio_alignment(s1: String, s2: String, unit: Option<String>) -> ScoreAlignment {
+    let res = crate::partial_ratio_alignment(&s1, &s2, unit);
+    ScoreAlignment {
+        score: res.score,
+        src_start: res.src_start,
+        src_end: res.src_end,
+        dest_start: res.dest_start,
+        dest_end: res.dest_end,
+    }
+}
+
+#[napi(js_name = "token_sort_ratio")]
+pub fn token_sort_ratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::token_sort_ratio(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "token_set_ratio")]
+pub fn token_set_ratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::token_set_ratio(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "partial_token_sort_ratio")]
+pub fn partial_token_sort_ratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::partial_token_sort_ratio(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "wratio")]
+pub fn wratio(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::wratio(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "monge_elkan")]
+pub fn monge_elkan(
+    s1: String,
+    s2: String,
+    inner: Option<String>,
+    symmetric: Option<bool>,
+    preprocessor: Option<String>,
+) -> f64 {
+    crate::monge_elkan(&s1, &s2, inner, symmetric, preprocessor)
+}
+
+// ============================================================================
+// Naive and Bigram Algorithms
+// ============================================================================
+
+#[napi(js_name = "prefix")]
+pub fn prefix(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::prefix(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "suffix")]
+pub fn suffix(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::suffix(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "length")]
+pub fn length(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::length(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "jaccard_bigram")]
+pub fn jaccard_bigram(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::jaccard_bigram(&s1, &s2, preprocessor)
+}
+
+#[napi(js_name = "cosine_bigram")]
+pub fn cosine_bigram(s1: String, s2: String, preprocessor: Option<String>) -> f64 {
+    crate::cosine_bigram(&s1, &s2, preprocessor)
+}
+
+// ============================================================================
+// Preprocessing and Pinyin
+// ============================================================================
+
+#[napi(js_name = "preprocess")]
+pub fn preprocess(text: String, preprocessor: String) -> String {
+    crate::preprocess(&text, &preprocessor)
+}
+
+#[napi(js_name = "validate_preprocessor")]
+pub fn validate_preprocessor(preprocessor: String) -> napi::Result<()> {
+    crate::preprocess::Preprocessor::try_parse(&preprocessor)
+        .map(|_| ())
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "transliterate")]
+pub fn transliterate(text: String) -> String {
+    crate::transliterate(&text)
+}
+
+#[napi(js_name = "fold_kana")]
+pub fn fold_kana(text: String) -> String {
+    crate::fold_kana(&text)
+}
+
+#[napi(js_name = "to_pinyin")]
+pub fn to_pinyin(text: String, style: String) -> napi::Result<String> {
+    crate::try_to_pinyin(&text, &style).map_err(napi_error)
+}
+
+#[napi(js_name = "pinyin_similarity")]
+pub fn pinyin_similarity(
+    s1: String,
+    s2: String,
+    style: String,
+    preprocessor: Option<String>,
+) -> napi::Result<f64> {
+    crate::try_pinyin_similarity(&s1, &s2, &style, preprocessor).map_err(napi_error)
+}
+
+#[napi(js_name = "pinyin_initials_match")]
+pub fn pinyin_initials_match(text: String, query: String) -> bool {
+    crate::pinyin_initials_match(&text, &query)
+}
+
+// ============================================================================
+// Vector Similarity
+// ============================================================================
+
+#[napi(js_name = "vector_cosine")]
+pub fn vector_cosine(a: Float32Array, b: Float32Array) -> napi::Result<f64> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Cosine)
+        .map(f64::from)
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "vector_dot")]
+pub fn vector_dot(a: Float32Array, b: Float32Array) -> napi::Result<f64> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Dot)
+        .map(f64::from)
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "vector_euclidean")]
+pub fn vector_euclidean(a: Float32Array, b: Float32Array) -> napi::Result<f64> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Euclidean)
+        .map(f64::from)
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "vector_manhattan")]
+pub fn vector_manhattan(a: Float32Array, b: Float32Array) -> napi::Result<f64> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Manhattan)
+        .map(f64::from)
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "vector_one_to_many")]
+pub fn vector_one_to_many(
+    query: Float32Array,
+    matrix: Float32Array,
+    metric: String,
+) -> napi::Result<Float32Array> {
+    crate::vector_metric(&metric)
+        .and_then(|metric| crate::vector::one_to_many(&query, &matrix, metric))
+        .map(Float32Array::new)
+        .map_err(napi_error)
+}
+
+#[napi(js_name = "vector_cdist")]
+pub fn vector_cdist(
+    queries: Float32Array,
+    choices: Float32Array,
+    dim: u32,
+    metric: String,
+) -> napi::Result<Float32Array> {
+    crate::vector_metric(&metric)
+        .and_then(|metric| crate::vector::cdist(&queries, &choices, dim as usize, metric))
+        .map(Float32Array::new)
+        .map_err(napi_error)
+}
+
+// ============================================================================
+// Universal comparison function
+// ============================================================================
+
+#[napi(object, js_name = "SearchHit")]
+pub struct SearchHit {
+    pub id: u32,
+    pub score: f64,
+}
+
+#[napi(js_name = "cdist")]
+pub fn cdist(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    algorithm: String,
+    preprocessor: Option<String>,
+) -> napi::Result<Vec<f64>> {
+    crate::try_cdist(queries, choices, &algorithm, preprocessor).map_err(napi_error)
+}
+
+#[napi(js_name = "extract")]
+pub fn extract(
+    query: String,
+    choices: Vec<String>,
+    algorithm: String,
+    limit: Option<u32>,
+    score_cutoff: Option<f64>,
+    preprocessor: Option<String>,
+) -> napi::Result<Vec<SearchHit>> {
+    let hits = crate::try_extract(
+        &query,
+        choices,
+        &algorithm,
+        limit,
+        score_cutoff,
+        preprocessor,
+    )
+    .map_err(napi_error)?;
+    Ok(hits
+        .into_iter()
+        .map(|hit| SearchHit {
+            id: hit.id,
+            score: hit.score,
+        })
+        .collect())
+}
+
+#[napi(js_name = "dedupe")]
+pub fn dedupe(
+    items: Vec<String>,
+    algorithm: String,
+    threshold: f64,
+    preprocessor: Option<String>,
+) -> napi::Result<Vec<u32>> {
+    crate::try_dedupe(items, &algorithm, threshold, preprocessor).map_err(napi_error)
+}
+
+#[napi(js_name = "compare")]
+pub fn compare(s1: String, s2: String, algorithm: String, preprocessor: Option<String>) -> f64 {
+    crate::compare(&s1, &s2, &algorithm, preprocessor)
+}
+
+// ============================================================================
+// Index classes
+// ============================================================================
+
+#[napi(object, js_name = "SparseVector")]
+pub struct SparseVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f64>,
+}
+
+#[napi(object, js_name = "TermMatch")]
+pub struct TermMatch {
+    pub term: String,
+    pub distance: u32,
+}
+
+#[napi(object, js_name = "DistanceHit")]
+pub struct DistanceHit {
+    pub id: u32,
+    pub distance: u32,
+}
+
+#[napi(object, js_name = "Neighbor")]
+pub struct Neighbor {
+    pub id: u32,
+    pub term: String,
+    pub distance: f64,
+}
+
+#[napi(object, js_name = "VectorHit")]
+pub struct VectorHit {
+    pub id: u32,
+    pub distance: f64,
+}
+
+#[napi(object, js_name = "Suggestion")]
+pub struct Suggestion {
+    pub term: String,
+    pub distance: u32,
+    pub count: f64,
+}
+
+#[napi(object, js_name = "Segmentation")]
+pub struct Segmentation {
+    pub segmented: String,
+    pub corrected: String,
+    #[napi(js_name = "distance_sum")]
+    pub distance_sum: u32,
+    #[napi(js_name = "log_probability_sum")]
+    pub log_probability_sum: f64,
+}
+
+fn term_matches(matches: Vec<crate::TermMatch>) -> Vec<TermMatch> {
+    matches
+        .into_iter()
+        .map(|m| TermMatch {
+            term: m.term,
+            distance: m.distance,
+        })
+        .collect()
+}
+
+fn neighbors(matches: Vec<crate::Neighbor>) -> Vec<Neighbor> {
+    matches
+        .into_iter()
+        .map(|m| Neighbor {
+            id: m.id,
+            term: m.term,
+            distance: m.distance,
+        })
+        .collect()
+}
+
+fn vector_hits(hits: Vec<(u32, f32)>) -> Vec<VectorHit> {
+    hits.into_iter()
+        .map(|(id, distance)| VectorHit {
+            id,
+            distance: distance as f64,
+        })
+        .collect()
+}
+
+#[napi(js_name = "SoftTfIdf")]
+pub struct SoftTfIdf {
+    inner: crate::SoftTfIdf,
+}
+
+#[napi]
+impl SoftTfIdf {
+    #[napi(constructor)]
+    pub fn new(
+        corpus: Vec<String>,
+        inner: Option<String>,
+        threshold: Option<f64>,
+        preprocessor: Option<String>,
+    ) -> napi::Result<SoftTfIdf> {
+        crate::SoftTfIdf::try_new(corpus, inner, threshold, preprocessor)
+            .map(|inner| SoftTfIdf { inner })
+            .map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn similarity(&self, s1: String, s2: String) -> f64 {
+        self.inner.similarity(&s1, &s2)
+    }
+}
+
+#[napi(js_name = "TfIdfModel")]
+pub struct TfIdfModel {
+    inner: crate::TfIdfModel,
+}
+
+#[napi]
+impl TfIdfModel {
+    #[napi(constructor)]
+    pub fn new(
+        corpus: Vec<String>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        sublinear_tf: Option<bool>,
+        smooth_idf: Option<bool>,
+        preprocessor: Option<String>,
+    ) -> napi::Result<TfIdfModel> {
+        crate::TfIdfModel::try_new(
+            corpus,
+            analyzer,
+            ngram_min,
+            ngram_max,
+            sublinear_tf,
+            smooth_idf,
+            preprocessor,
+        )
+        .map(|inner| TfIdfModel { inner })
+        .map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn similarity(&self, s1: String, s2: String) -> f64 {
+        self.inner.similarity(&s1, &s2)
+    }
+
+    #[napi]
+    pub fn vectorize(&self, text: String) -> SparseVector {
+        let vector = self.inner.vectorize(&text);
+        SparseVector {
+            indices: vector.indices(),
+            values: vector.values(),
+        }
+    }
+
+    #[napi(getter, js_name = "vocabulary_size")]
+    pub fn vocabulary_size(&self) -> u32 {
+        self.inner.vocabulary_size() as u32
+    }
+
+    #[napi(js_name = "feature_names")]
+    pub fn feature_names(&self) -> Vec<String> {
+        self.inner.feature_names()
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> String {
+        self.inner.serialize()
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(json: String) -> napi::Result<TfIdfModel> {
+        crate::tfidf::TfIdf::from_json(&json)
+            .map(|model| TfIdfModel {
+                inner: crate::TfIdfModel { model },
+            })
+            .map_err(napi_error)
+    }
+}
+
+#[napi(js_name = "Bm25Index")]
+pub struct Bm25Index {
+    inner: crate::Bm25Index,
+}
+
+#[napi]
+impl Bm25Index {
+    #[napi(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        variant: Option<String>,
+        k1: Option<f64>,
+        b: Option<f64>,
+        delta: Option<f64>,
+        analyzer: Option<String>,
+        ngram_min: Option<u32>,
+        ngram_max: Option<u32>,
+        preprocessor: Option<String>,
+    ) -> napi::Result<Bm25Index> {
+        crate::Bm25Index::try_new(
+            variant,
+            k1,
+            b,
+            delta,
+            analyzer,
+            ngram_min,
+            ngram_max,
+            preprocessor,
+        )
+        .map(|inner| Bm25Index { inner })
+        .map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn add(&mut self, text: String) -> u32 {
+        self.inner.add(&text)
+    }
+
+    #[napi]
+    pub fn remove(&mut self, id: u32) -> bool {
+        self.inner.remove(id)
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn query(&self, query: String, k: Option<u32>) -> Vec<SearchHit> {
+        self.inner
+            .query(&query, k)
+            .into_iter()
+            .map(|hit| SearchHit {
+                id: hit.id,
+                score: hit.score,
+            })
+            .collect()
+    }
+}
+
+#[napi(js_name = "NgramIndex")]
+pub struct NgramIndex {
+    inner: crate::NgramIndex,
+}
+
+#[napi]
+impl NgramIndex {
+    #[napi(constructor)]
+    pub fn new(q: Option<u32>) -> NgramIndex {
+        NgramIndex {
+            inner: crate::NgramIndex::new(q),
+        }
+    }
+
+    #[napi]
+    pub fn insert(&mut self, text: String) -> u32 {
+        self.inner.insert(&text)
+    }
+
+    #[napi]
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.inner.delete(id)
+    }
+
+    #[napi]
+    pub fn get(&self, id: u32) -> Option<String> {
+        self.inner.get(id)
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn search(&self, query: String, k: u32) -> Vec<DistanceHit> {
+        self.inner
+            .search(&query, k)
+            .into_iter()
+            .map(|hit| DistanceHit {
+                id: hit.id,
+                distance: hit.distance,
+            })
+            .collect()
+    }
+}
+
+#[napi(js_name = "BkTree")]
+pub struct BkTree {
+    inner: crate::BkTree,
+}
+
+#[napi]
+impl BkTree {
+    #[napi(constructor)]
+    pub fn new(metric: Option<String>) -> napi::Result<BkTree> {
+        let name = metric.as_deref().unwrap_or("levenshtein");
+        let metric = crate::bktree::Metric::parse(name)
+            .ok_or_else(|| napi_error(format!("unsupported BK-tree metric: {}", name)))?;
+        Ok(BkTree {
+            inner: crate::BkTree {
+                tree: crate::bktree::BkTree::new(metric),
+            },
+        })
+    }
+
+    #[napi]
+    pub fn insert(&mut self, term: String) -> bool {
+        self.inner.insert(&term)
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn within(&self, query: String, k: u32) -> Vec<TermMatch> {
+        term_matches(self.inner.within(&query, k))
+    }
+
+    #[napi]
+    pub fn nearest(&self, query: String, n: u32) -> Vec<TermMatch> {
+        term_matches(self.inner.nearest(&query, n))
+    }
+}
+
+#[napi(js_name = "TermDictionary")]
+pub struct TermDictionary {
+    inner: crate::TermDictionary,
+}
+
+#[napi]
+impl TermDictionary {
+    #[napi(constructor)]
+    pub fn new(terms: Vec<String>) -> TermDictionary {
+        TermDictionary {
+            inner: crate::TermDictionary::new(terms),
+        }
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn contains(&self, term: String) -> bool {
+        self.inner.contains(&term)
+    }
+
+    #[napi(js_name = "fuzzy_search")]
+    pub fn fuzzy_search(
+        &self,
+        query: String,
+        k: u32,
+        transpositions: Option<bool>,
+    ) -> napi::Result<Vec<TermMatch>> {
+        let k = k.min(u8::MAX as u32) as u8;
+        let matches = self
+            .inner
+            .terms
+            .fuzzy(&query, k, transpositions.unwrap_or(false))
+            .map_err(napi_error)?;
+        Ok(matches
+            .into_iter()
+            .map(|(term, distance)| TermMatch {
+                term,
+                distance: distance as u32,
+            })
+            .collect())
+    }
+}
+
+#[napi(js_name = "VpTree")]
+pub struct VpTree {
+    inner: crate::VpTree,
+}
+
+#[napi]
+impl VpTree {
+    #[napi(constructor)]
+    pub fn new(items: Vec<String>, metric: Option<String>) -> napi::Result<VpTree> {
+        let name = metric.as_deref().unwrap_or("yujian_bo");
+        let metric = crate::vptree::FloatMetric::parse(name)
+            .ok_or_else(|| napi_error(format!("unsupported VP-tree metric: {}", name)))?;
+        Ok(VpTree {
+            inner: crate::VpTree {
+                tree: crate::vptree::VpTree::new(items, metric),
+            },
+        })
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn within(&self, query: String, radius: f64) -> Vec<Neighbor> {
+        neighbors(self.inner.within(&query, radius))
+    }
+
+    #[napi]
+    pub fn nearest(&self, query: String, n: u32) -> Vec<Neighbor> {
+        neighbors(self.inner.nearest(&query, n))
+    }
+}
+
+#[napi(js_name = "HnswIndex")]
+pub struct HnswIndex {
+    index: crate::hnsw::Hnsw,
+}
+
+#[napi]
+impl HnswIndex {
+    #[napi(constructor)]
+    pub fn new(
+        dim: u32,
+        metric: Option<String>,
+        m: Option<u32>,
+        ef_construction: Option<u32>,
+        ef_search: Option<u32>,
+    ) -> napi::Result<HnswIndex> {
+        let name = metric.as_deref().unwrap_or("cosine");
+        let metric = crate::hnsw::HnswMetric::parse(name)
+            .ok_or_else(|| napi_error(format!("unsupported HNSW metric: {}", name)))?;
+        let defaults = crate::hnsw::HnswParams::default();
+        let params = crate::hnsw::HnswParams {
+            m: m.map_or(defaults.m, |m| m as usize),
+            ef_construction: ef_construction.map_or(defaults.ef_construction, |ef| ef as usize),
+            ef_search: ef_search.map_or(defaults.ef_search, |ef| ef as usize),
+            ..defaults
+        };
+        Ok(HnswIndex {
+            index: crate::hnsw::Hnsw::new(dim as usize, metric, params),
+        })
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.index.len() as u32
+    }
+
+    #[napi(getter)]
+    pub fn dim(&self) -> u32 {
+        self.index.dim() as u32
+    }
+
+    #[napi(getter, js_name = "ef_search")]
+    pub fn ef_search(&self) -> u32 {
+        self.index.ef_search() as u32
+    }
+
+    #[napi(setter, js_name = "ef_search")]
+    pub fn set_ef_search(&mut self, ef_search: u32) {
+        self.index.set_ef_search(ef_search as usize);
+    }
+
+    #[napi]
+    pub fn insert(&mut self, id: u32, vector: Float32Array) -> napi::Result<()> {
+        self.index.insert(id, &vector).map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.index.delete(id)
+    }
+
+    #[napi]
+    pub fn search(
+        &self,
+        query: Float32Array,
+        k: u32,
+        allowed: Option<Vec<u32>>,
+    ) -> napi::Result<Vec<VectorHit>> {
+        let allowed: Option<std::collections::HashSet<u32>> =
+            allowed.map(|ids| ids.into_iter().collect());
+        let filter = allowed.as_ref().map(|ids| move |id: u32| ids.contains(&id));
+        self.index
+            .search(
+                &query,
+                k as usize,
+                filter.as_ref().map(|f| f as &dyn Fn(u32) -> bool),
+            )
+            .map(vector_hits)
+            .map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn serialize(&self) -> Buffer {
+        self.index.to_bytes().into()
+    }
+
+    #[napi(factory)]
+    pub fn deserialize(bytes: Buffer) -> napi::Result<HnswIndex> {
+        crate::hnsw::Hnsw::from_bytes(&bytes)
+            .map(|index| HnswIndex { index })
+            .map_err(napi_error)
+    }
+}
+
+#[napi(js_name = "QuantizedStore")]
+pub struct QuantizedStore {
+    store: crate::quantize::QuantizedStore,
+}
+
+#[napi]
+impl QuantizedStore {
+    #[napi(constructor)]
+    pub fn new(dim: u32, metric: Option<String>) -> napi::Result<QuantizedStore> {
+        let name = metric.as_deref().unwrap_or("cosine");
+        let metric = crate::hnsw::HnswMetric::parse(name)
+            .ok_or_else(|| napi_error(format!("unsupported vector metric: {}", name)))?;
+        Ok(QuantizedStore {
+            store: crate::quantize::QuantizedStore::new(dim as usize, metric),
+        })
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.store.len() as u32
+    }
+
+    #[napi(getter)]
+    pub fn dim(&self) -> u32 {
+        self.store.dim() as u32
+    }
+
+    #[napi(getter, js_name = "memory_bytes")]
+    pub fn memory_bytes(&self) -> u32 {
+        self.store.memory_bytes() as u32
+    }
+
+    #[napi]
+    pub fn insert(&mut self, id: u32, vector: Float32Array) -> napi::Result<()> {
+        self.store.insert(id, &vector).map_err(napi_error)
+    }
+
+    #[napi]
+    pub fn delete(&mut self, id: u32) -> bool {
+        self.store.delete(id)
+    }
+
+    #[napi]
+    pub fn get(&self, id: u32) -> Option<Float32Array> {
+        self.store.get(id).map(Float32Array::new)
+    }
+
+    #[napi]
+    pub fn search(&self, query: Float32Array, k: u32) -> napi::Result<Vec<VectorHit>> {
+        self.store
+            .search(&query, k as usize)
+            .map(vector_hits)
+            .map_err(napi_error)
+    }
+
+    #[napi(js_name = "search_binary")]
+    pub fn search_binary(
+        &self,
+        query: Float32Array,
+        k: u32,
+        oversample: Option<u32>,
+    ) -> napi::Result<Vec<VectorHit>> {
+        self.store
+            .search_binary(&query, k as usize, oversample.unwrap_or(4) as usize)
+            .map(vector_hits)
+            .map_err(napi_error)
+    }
+}
+
+#[napi(js_name = "SymSpell")]
+pub struct SymSpell {
+    inner: crate::SymSpell,
+}
+
+#[napi]
+impl SymSpell {
+    #[napi(constructor)]
+    pub fn new(max_edit_distance: Option<u32>, prefix_length: Option<u32>) -> SymSpell {
+        SymSpell {
+            inner: crate::SymSpell::new(max_edit_distance, prefix_length),
+        }
+    }
+
+    #[napi(js_name = "add_word")]
+    pub fn add_word(&mut self, word: String, count: Option<f64>) {
+        self.inner.add_word(&word, count);
+    }
+
+    #[napi(js_name = "load_dictionary")]
+    pub fn load_dictionary(&mut self, text: String) -> u32 {
+        self.inner.load_dictionary(&text)
+    }
+
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.inner.size() as u32
+    }
+
+    #[napi]
+    pub fn lookup(
+        &self,
+        input: String,
+        verbosity: Option<String>,
+        max_edit_distance: Option<u32>,
+    ) -> napi::Result<Vec<Suggestion>> {
+        let found = self
+            .inner
+            .try_lookup(&input, verbosity, max_edit_distance)
+            .map_err(napi_error)?;
+        Ok(found
+            .into_iter()
+            .map(|s| Suggestion {
+                term: s.term,
+                distance: s.distance,
+                count: s.count,
+            })
+            .collect())
+    }
+
+    #[napi(js_name = "word_segmentation")]
+    pub fn word_segmentation(&self, input: String, max_edit_distance: Option<u32>) -> Segmentation {
+        let result = self.inner.word_segmentation(&input, max_edit_distance);
+        Segmentation {
+            segmented: result.segmented,
+            corrected: result.corrected,
+            distance_sum: result.distance_sum,
+            log_probability_sum: result.log_probability_sum,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIRS: [(&str, &str); 4] = [
+        ("kitten", "sitting"),
+        ("New York Mets", "new york meats"),
+        ("", "abc"),
+        ("Straße café", "strasse cafe"),
+    ];
+
+    #[test]
+    fn test_scorers_match_wasm_exports() {
+        let pre = || Some("case_fold,transliterate".to_string());
+        for &(a, b) in PAIRS.iter() {
+            let (s1, s2) = (a.to_string(), b.to_string());
+            assert_eq!(
+                levenshtein(s1.clone(), s2.clone(), None),
+                crate::levenshtein(a, b, None)
+            );
+            assert_eq!(
+                myers_levenshtein_normalized(s1.clone(), s2.clone(), pre()),
+                crate::myers_levenshtein_normalized(a, b, pre())
+            );
+            assert_eq!(
+                wratio(s1.clone(), s2.clone(), None),
+                crate::wratio(a, b, None)
+            );
+            assert_eq!(
+                partial_ratio_alignment(s1.clone(), s2.clone(), None).score,
+                crate::partial_ratio_alignment(a, b, None).score
+            );
+            assert_eq!(
+                compare(s1.clone(), s2.clone(), "jaro_winkler".into(), pre()),
+                crate::compare(a, b, "jaro_winkler", pre())
+            );
+            assert_eq!(
+                cosine_bigram(s1, s2, None),
+                crate::cosine_bigram(a, b, None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_index_classes_match_wasm_exports() {
+        let corpus: Vec<String> = ["acme inc", "globex inc", "initech inc"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut bm25 = Bm25Index::new(None, None, None, None, None, None, None, None)
+            .ok()
+            .unwrap();
+        let mut expected = crate::Bm25Index::new(None, None, None, None, None, None, None, None)
+            .ok()
+            .unwrap();
+        for doc in &corpus {
+            assert_eq!(bm25.add(doc.clone()), expected.add(doc));
+        }
+        let hits = bm25.query("acme".into(), None);
+        let expected = expected.query("acme", None);
+        assert_eq!(hits.len(), expected.len());
+        for (hit, expected) in hits.iter().zip(&expected) {
+            assert_eq!((hit.id, hit.score), (expected.id, expected.score));
+        }
+
+        let tfidf = TfIdfModel::new(corpus.clone(), None, None, None, None, None, None)
+            .ok()
+            .unwrap();
+        let vector = tfidf.vectorize("acme inc".into());
+        let restored = TfIdfModel::deserialize(tfidf.serialize()).ok().unwrap();
+        assert_eq!(restored.vectorize("acme inc".into()).values, vector.values);
+        assert_eq!(tfidf.vocabulary_size(), 4);
+
+        let mut spell = SymSpell::new(None, None);
+        spell.load_dictionary("hello 10\nworld 5".into());
+        let found = spell.lookup("helo".into(), None, None).unwrap();
+        assert_eq!(found[0].term, "hello");
+        assert_eq!(
+            spell.word_segmentation("helloworld".into(), None).segmented,
+            "hello world"
+        );
+
+        let tree = VpTree::new(corpus, None).ok().unwrap();
+        assert_eq!(tree.nearest("acme".into(), 1)[0].term, "acme inc");
+    }
+
+    #[test]
+    fn test_batch_matches_wasm_exports() {
+        let items: Vec<String> = ["apple", "apples", "banana", "Apple"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            cdist(items.clone(), items.clone(), "ratio".into(), None).ok(),
+            crate::cdist(items.clone(), items.clone(), "ratio", None).ok()
+        );
+        let hits = extract(
+            "apple".into(),
+            items.clone(),
+            "ratio".into(),
+            Some(2),
+            None,
+            None,
+        )
+        .ok()
+        .unwrap();
+        let expected = crate::extract("apple", items.clone(), "ratio", Some(2), None, None)
+            .ok()
+            .unwrap();
+        assert_eq!(hits.len(), expected.len());
+        for (hit, expected) in hits.iter().zip(&expected) {
+            assert_eq!((hit.id, hit.score), (expected.id, expected.score));
+        }
+        assert_eq!(
+            dedupe(
+                items.clone(),
+                "ratio".into(),
+                80.0,
+                Some("case_fold".into())
+            )
+            .ok(),
+            crate::dedupe(items, "ratio", 80.0, Some("case_fold".into())).ok()
+        );
+    }
+}
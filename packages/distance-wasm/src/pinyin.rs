@@ -0,0 +1,255 @@
+// Pinyin conversion and fuzzy matching for Chinese text
+// Hanzi are mapped to pinyin syllables so that characters, homophones, full
+// pinyin and initials typed by users can be compared with each other
+
+use crate::myers;
+use crate::pinyin_table::PINYIN_TABLE;
+
+/// How Hanzi are rendered before comparison
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PinyinStyle {
+    /// Full pinyin with numbered tones, e.g. `bei3jing1`
+    Tone,
+    /// Pinyin without tones, e.g. `beijing`
+    Toneless,
+    /// First letter of each syllable, e.g. `bj`, as typed in pinyin input
+    /// method abbreviations; the initials zh, ch and sh become z, c and s
+    Initials,
+}
+
+impl PinyinStyle {
+    /// Parse a style name, rejecting unknown ones
+    pub fn parse(style: &str) -> Result<PinyinStyle, String> {
+        match style.trim().to_lowercase().as_str() {
+            "tone" | "tones" | "full" => Ok(PinyinStyle::Tone),
+            "toneless" | "plain" | "normal" => Ok(PinyinStyle::Toneless),
+            "initials" | "initial" | "first_letter" | "first-letter" => Ok(PinyinStyle::Initials),
+            _ => Err(format!("unsupported pinyin style: {}", style)),
+        }
+    }
+}
+
+/// Look up the most common reading of a Hanzi in numbered-tone form
+pub fn lookup(c: char) -> Option<&'static str> {
+    PINYIN_TABLE
+        .binary_search_by_key(&c, |&(k, _)| k)
+        .ok()
+        .map(|i| PINYIN_TABLE[i].1)
+}
+
+fn strip_tone(syllable: &str) -> &str {
+    syllable.trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+fn render(syllable: &'static str, style: PinyinStyle) -> &'static str {
+    match style {
+        PinyinStyle::Tone => syllable,
+        PinyinStyle::Toneless => strip_tone(syllable),
+        PinyinStyle::Initials => &syllable[..1],
+    }
+}
+
+/// Fold a non-Hanzi character the way users type it next to pinyin
+fn fold_char(c: char) -> char {
+    match c {
+        'ü' | 'Ü' => 'v',
+        _ => c.to_lowercase().next().unwrap_or(c),
+    }
+}
+
+/// Convert text to pinyin syllables
+///
+/// Known Hanzi become one syllable each; runs of other non-whitespace
+/// characters are lowercased and kept as a single token.
+pub fn to_syllables(text: &str, style: PinyinStyle) -> Vec<String> {
+    let mut syllables = Vec::new();
+    let mut run = String::new();
+
+    for c in text.chars() {
+        if let Some(syllable) = lookup(c) {
+            if !run.is_empty() {
+                syllables.push(std::mem::take(&mut run));
+            }
+            syllables.push(render(syllable, style).to_string());
+        } else if c.is_whitespace() {
+            if !run.is_empty() {
+                syllables.push(std::mem::take(&mut run));
+            }
+        } else {
+            run.push(fold_char(c));
+        }
+    }
+    if !run.is_empty() {
+        syllables.push(run);
+    }
+
+    syllables
+}
+
+/// Convert text to a single pinyin string with whitespace removed
+pub fn to_pinyin(text: &str, style: PinyinStyle) -> String {
+    to_syllables(text, style).concat()
+}
+
+/// Normalized similarity of two texts compared by their pinyin
+pub fn pinyin_similarity(a: &str, b: &str, style: PinyinStyle) -> f64 {
+    myers::myers_similarity(&to_pinyin(a, style), &to_pinyin(b, style))
+}
+
+/// A unit of the text a query is matched against
+enum Unit {
+    /// Toneless syllable of a Hanzi, which may be abbreviated to any prefix
+    Syllable(&'static str),
+    /// Any other character, which must be typed as is
+    Literal(char),
+}
+
+/// Check whether `query` abbreviates the pinyin of `text`
+///
+/// Each Hanzi in `text` may be typed as its full toneless syllable or any
+/// prefix of it (`b`, `bei`, `zh`), so `北京` is matched by `bj`, `beijing`
+/// and `beij`. Hanzi in the query are converted to toneless pinyin first.
+pub fn pinyin_initials_match(text: &str, query: &str) -> bool {
+    let units: Vec<Unit> = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match lookup(c) {
+            Some(syllable) => Unit::Syllable(strip_tone(syllable)),
+            None => Unit::Literal(fold_char(c)),
+        })
+        .collect();
+    let query: Vec<char> = to_pinyin(query, PinyinStyle::Toneless).chars().collect();
+
+    let n = units.len();
+    let m = query.len();
+
+    // reachable[i][j]: the first i units can be typed as the first j query chars
+    let mut reachable = vec![vec![false; m + 1]; n + 1];
+    reachable[0][0] = true;
+
+    for i in 0..n {
+        for j in 0..=m {
+            if !reachable[i][j] {
+                continue;
+            }
+            match units[i] {
+                Unit::Literal(c) => {
+                    if j < m && query[j] == c {
+                        reachable[i + 1][j + 1] = true;
+                    }
+                }
+                Unit::Syllable(syllable) => {
+                    for (k, c) in syllable.chars().enumerate() {
+                        if j + k >= m || query[j + k] != c {
+                            break;
+                        }
+                        reachable[i + 1][j + k + 1] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    reachable[n][m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_sorted() {
+        // lookup relies on binary search over the table
+        for pair in PINYIN_TABLE.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{:?} >= {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_to_pinyin_styles() {
+        assert_eq!(to_pinyin("北京", PinyinStyle::Tone), "bei3jing1");
+        assert_eq!(to_pinyin("北京", PinyinStyle::Toneless), "beijing");
+        assert_eq!(to_pinyin("北京", PinyinStyle::Initials), "bj");
+        assert_eq!(to_pinyin("女的", PinyinStyle::Tone), "nv3de");
+    }
+
+    #[test]
+    fn test_initials_are_first_letters() {
+        // zh, ch and sh abbreviate to one letter like every other initial
+        assert_eq!(to_pinyin("中国长城", PinyinStyle::Initials), "zgcc");
+        assert_eq!(to_pinyin("上海", PinyinStyle::Initials), "sh");
+        assert_eq!(pinyin_similarity("中国", "zg", PinyinStyle::Initials), 1.0);
+        assert!(pinyin_similarity("中国", "zhg", PinyinStyle::Initials) < 1.0);
+        // The prefix matcher accepts both spellings
+        assert!(pinyin_initials_match("中国", "zg"));
+        assert!(pinyin_initials_match("中国", "zhg"));
+    }
+
+    #[test]
+    fn test_to_syllables_mixed_text() {
+        assert_eq!(
+            to_syllables("iPhone 手机", PinyinStyle::Toneless),
+            vec!["iphone", "shou", "ji"]
+        );
+        assert_eq!(
+            to_syllables("", PinyinStyle::Toneless),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_pinyin_similarity() {
+        assert_eq!(
+            pinyin_similarity("北京", "beijing", PinyinStyle::Toneless),
+            1.0
+        );
+        assert_eq!(
+            pinyin_similarity("北京", "Bei Jing", PinyinStyle::Toneless),
+            1.0
+        );
+        assert_eq!(pinyin_similarity("北京", "bj", PinyinStyle::Initials), 1.0);
+
+        // Homophones match without tones but not with them
+        assert_eq!(
+            pinyin_similarity("北京", "背景", PinyinStyle::Toneless),
+            1.0
+        );
+        assert!(pinyin_similarity("北京", "背景", PinyinStyle::Tone) < 1.0);
+
+        assert!(pinyin_similarity("北京", "上海", PinyinStyle::Toneless) < 0.5);
+    }
+
+    #[test]
+    fn test_pinyin_initials_match() {
+        assert!(pinyin_initials_match("北京", "bj"));
+        assert!(pinyin_initials_match("北京", "beijing"));
+        assert!(pinyin_initials_match("北京", "beij"));
+        assert!(pinyin_initials_match("北京", "BJ"));
+        assert!(pinyin_initials_match("中国", "zhg"));
+        assert!(pinyin_initials_match("北京", "背景"));
+        assert!(pinyin_initials_match("", ""));
+
+        assert!(!pinyin_initials_match("北京", "b"));
+        assert!(!pinyin_initials_match("北京", "sh"));
+        assert!(!pinyin_initials_match("北京", "beijingx"));
+    }
+
+    #[test]
+    fn test_pinyin_initials_match_literals() {
+        assert!(pinyin_initials_match("iPhone手机", "iphonesj"));
+        assert!(!pinyin_initials_match("iPhone手机", "isj"));
+    }
+
+    #[test]
+    fn test_style_parse() {
+        assert_eq!(PinyinStyle::parse("tone"), Ok(PinyinStyle::Tone));
+        assert_eq!(PinyinStyle::parse("INITIALS"), Ok(PinyinStyle::Initials));
+        assert_eq!(PinyinStyle::parse("toneless"), Ok(PinyinStyle::Toneless));
+        assert_eq!(
+            PinyinStyle::parse("unknown"),
+            Err("unsupported pinyin style: unknown".to_string())
+        );
+        assert!(crate::try_to_pinyin("北京", "tonal").is_err());
+        assert!(crate::try_pinyin_similarity("北京", "bj", "initial", None).is_ok());
+    }
+}
@@ -0,0 +1,1040 @@
+// Hanzi to pinyin lookup table
+// Covers the most frequently used characters, common surnames and place names.
+// Each entry maps a character to its most common reading in numbered-tone form;
+// the neutral tone has no digit and ü is written as v.
+
+pub static PINYIN_TABLE: &[(char, &str)] = &[
+    ('一', "yi1"),
+    ('丁', "ding1"),
+    ('七', "qi1"),
+    ('万', "wan4"),
+    ('三', "san1"),
+    ('上', "shang4"),
+    ('下', "xia4"),
+    ('不', "bu4"),
+    ('与', "yu3"),
+    ('专', "zhuan1"),
+    ('且', "qie3"),
+    ('世', "shi4"),
+    ('业', "ye4"),
+    ('东', "dong1"),
+    ('两', "liang3"),
+    ('严', "yan2"),
+    ('个', "ge4"),
+    ('中', "zhong1"),
+    ('丰', "feng1"),
+    ('为', "wei2"),
+    ('主', "zhu3"),
+    ('举', "ju3"),
+    ('久', "jiu3"),
+    ('义', "yi4"),
+    ('之', "zhi1"),
+    ('乐', "le4"),
+    ('乘', "cheng2"),
+    ('九', "jiu3"),
+    ('也', "ye3"),
+    ('习', "xi2"),
+    ('乡', "xiang1"),
+    ('书', "shu1"),
+    ('买', "mai3"),
+    ('乱', "luan4"),
+    ('了', "le"),
+    ('争', "zheng1"),
+    ('事', "shi4"),
+    ('二', "er4"),
+    ('于', "yu2"),
+    ('云', "yun2"),
+    ('互', "hu4"),
+    ('五', "wu3"),
+    ('些', "xie1"),
+    ('交', "jiao1"),
+    ('产', "chan3"),
+    ('京', "jing1"),
+    ('亮', "liang4"),
+    ('亲', "qin1"),
+    ('人', "ren2"),
+    ('亿', "yi4"),
+    ('什', "shen2"),
+    ('仅', "jin3"),
+    ('今', "jin1"),
+    ('介', "jie4"),
+    ('从', "cong2"),
+    ('他', "ta1"),
+    ('付', "fu4"),
+    ('代', "dai4"),
+    ('令', "ling4"),
+    ('以', "yi3"),
+    ('们', "men"),
+    ('件', "jian4"),
+    ('价', "jia4"),
+    ('任', "ren4"),
+    ('份', "fen4"),
+    ('休', "xiu1"),
+    ('众', "zhong4"),
+    ('优', "you1"),
+    ('会', "hui4"),
+    ('传', "chuan2"),
+    ('伤', "shang1"),
+    ('伯', "bo2"),
+    ('伴', "ban4"),
+    ('但', "dan4"),
+    ('位', "wei4"),
+    ('低', "di1"),
+    ('住', "zhu4"),
+    ('体', "ti3"),
+    ('何', "he2"),
+    ('余', "yu2"),
+    ('作', "zuo4"),
+    ('你', "ni3"),
+    ('使', "shi3"),
+    ('例', "li4"),
+    ('侧', "ce4"),
+    ('侯', "hou2"),
+    ('便', "bian4"),
+    ('保', "bao3"),
+    ('信', "xin4"),
+    ('倍', "bei4"),
+    ('倒', "dao4"),
+    ('候', "hou4"),
+    ('借', "jie4"),
+    ('值', "zhi2"),
+    ('假', "jia3"),
+    ('做', "zuo4"),
+    ('停', "ting2"),
+    ('健', "jian4"),
+    ('像', "xiang4"),
+    ('儿', "er2"),
+    ('元', "yuan2"),
+    ('兄', "xiong1"),
+    ('先', "xian1"),
+    ('光', "guang1"),
+    ('克', "ke4"),
+    ('入', "ru4"),
+    ('全', "quan2"),
+    ('八', "ba1"),
+    ('公', "gong1"),
+    ('六', "liu4"),
+    ('兰', "lan2"),
+    ('共', "gong4"),
+    ('关', "guan1"),
+    ('兵', "bing1"),
+    ('其', "qi2"),
+    ('具', "ju4"),
+    ('典', "dian3"),
+    ('内', "nei4"),
+    ('再', "zai4"),
+    ('写', "xie3"),
+    ('军', "jun1"),
+    ('农', "nong2"),
+    ('冬', "dong1"),
+    ('冯', "feng2"),
+    ('冰', "bing1"),
+    ('冲', "chong1"),
+    ('决', "jue2"),
+    ('况', "kuang4"),
+    ('冷', "leng3"),
+    ('净', "jing4"),
+    ('准', "zhun3"),
+    ('凉', "liang2"),
+    ('减', "jian3"),
+    ('几', "ji3"),
+    ('凡', "fan2"),
+    ('出', "chu1"),
+    ('击', "ji1"),
+    ('刀', "dao1"),
+    ('分', "fen1"),
+    ('划', "hua2"),
+    ('列', "lie4"),
+    ('刘', "liu2"),
+    ('则', "ze2"),
+    ('刚', "gang1"),
+    ('创', "chuang4"),
+    ('初', "chu1"),
+    ('利', "li4"),
+    ('别', "bie2"),
+    ('到', "dao4"),
+    ('制', "zhi4"),
+    ('刻', "ke4"),
+    ('前', "qian2"),
+    ('剧', "ju4"),
+    ('力', "li4"),
+    ('办', "ban4"),
+    ('功', "gong1"),
+    ('加', "jia1"),
+    ('务', "wu4"),
+    ('动', "dong4"),
+    ('助', "zhu4"),
+    ('努', "nu3"),
+    ('劳', "lao2"),
+    ('势', "shi4"),
+    ('包', "bao1"),
+    ('化', "hua4"),
+    ('北', "bei3"),
+    ('区', "qu1"),
+    ('医', "yi1"),
+    ('十', "shi2"),
+    ('千', "qian1"),
+    ('升', "sheng1"),
+    ('午', "wu3"),
+    ('半', "ban4"),
+    ('华', "hua2"),
+    ('单', "dan1"),
+    ('卖', "mai4"),
+    ('南', "nan2"),
+    ('博', "bo2"),
+    ('卢', "lu2"),
+    ('卫', "wei4"),
+    ('印', "yin4"),
+    ('危', "wei1"),
+    ('即', "ji2"),
+    ('却', "que4"),
+    ('厂', "chang3"),
+    ('厅', "ting1"),
+    ('历', "li4"),
+    ('压', "ya1"),
+    ('厚', "hou4"),
+    ('原', "yuan2"),
+    ('厦', "xia4"),
+    ('去', "qu4"),
+    ('县', "xian4"),
+    ('参', "can1"),
+    ('又', "you4"),
+    ('及', "ji2"),
+    ('友', "you3"),
+    ('双', "shuang1"),
+    ('反', "fan3"),
+    ('发', "fa1"),
+    ('取', "qu3"),
+    ('受', "shou4"),
+    ('变', "bian4"),
+    ('口', "kou3"),
+    ('古', "gu3"),
+    ('句', "ju4"),
+    ('另', "ling4"),
+    ('只', "zhi3"),
+    ('叫', "jiao4"),
+    ('可', "ke3"),
+    ('台', "tai2"),
+    ('史', "shi3"),
+    ('右', "you4"),
+    ('叶', "ye4"),
+    ('号', "hao4"),
+    ('司', "si1"),
+    ('吃', "chi1"),
+    ('各', "ge4"),
+    ('合', "he2"),
+    ('吉', "ji2"),
+    ('同', "tong2"),
+    ('名', "ming2"),
+    ('后', "hou4"),
+    ('向', "xiang4"),
+    ('吕', "lv3"),
+    ('吗', "ma"),
+    ('吧', "ba1"),
+    ('听', "ting1"),
+    ('吴', "wu2"),
+    ('呆', "dai1"),
+    ('告', "gao4"),
+    ('员', "yuan2"),
+    ('呢', "ne"),
+    ('周', "zhou1"),
+    ('味', "wei4"),
+    ('呼', "hu1"),
+    ('和', "he2"),
+    ('咖', "ka1"),
+    ('咱', "zan2"),
+    ('哀', "ai1"),
+    ('品', "pin3"),
+    ('响', "xiang3"),
+    ('哥', "ge1"),
+    ('哪', "na3"),
+    ('哭', "ku1"),
+    ('唐', "tang2"),
+    ('唱', "chang4"),
+    ('商', "shang1"),
+    ('啊', "a1"),
+    ('啡', "fei1"),
+    ('啤', "pi2"),
+    ('喜', "xi3"),
+    ('喝', "he1"),
+    ('器', "qi4"),
+    ('四', "si4"),
+    ('回', "hui2"),
+    ('因', "yin1"),
+    ('团', "tuan2"),
+    ('园', "yuan2"),
+    ('困', "kun4"),
+    ('围', "wei2"),
+    ('国', "guo2"),
+    ('图', "tu2"),
+    ('圆', "yuan2"),
+    ('土', "tu3"),
+    ('在', "zai4"),
+    ('地', "di4"),
+    ('圳', "zhen4"),
+    ('场', "chang3"),
+    ('均', "jun1"),
+    ('坏', "huai4"),
+    ('坐', "zuo4"),
+    ('块', "kuai4"),
+    ('坚', "jian1"),
+    ('埃', "ai1"),
+    ('城', "cheng2"),
+    ('基', "ji1"),
+    ('堂', "tang2"),
+    ('境', "jing4"),
+    ('增', "zeng1"),
+    ('壁', "bi4"),
+    ('声', "sheng1"),
+    ('处', "chu4"),
+    ('备', "bei4"),
+    ('复', "fu4"),
+    ('夏', "xia4"),
+    ('外', "wai4"),
+    ('多', "duo1"),
+    ('夜', "ye4"),
+    ('够', "gou4"),
+    ('大', "da4"),
+    ('天', "tian1"),
+    ('太', "tai4"),
+    ('夫', "fu1"),
+    ('失', "shi1"),
+    ('头', "tou2"),
+    ('奇', "qi2"),
+    ('奥', "ao4"),
+    ('女', "nv3"),
+    ('奶', "nai3"),
+    ('她', "ta1"),
+    ('好', "hao3"),
+    ('如', "ru2"),
+    ('妇', "fu4"),
+    ('妈', "ma1"),
+    ('妹', "mei4"),
+    ('妻', "qi1"),
+    ('始', "shi3"),
+    ('姐', "jie3"),
+    ('姓', "xing4"),
+    ('委', "wei3"),
+    ('姚', "yao2"),
+    ('姜', "jiang1"),
+    ('婚', "hun1"),
+    ('子', "zi3"),
+    ('孔', "kong3"),
+    ('字', "zi4"),
+    ('存', "cun2"),
+    ('孙', "sun1"),
+    ('孟', "meng4"),
+    ('季', "ji4"),
+    ('学', "xue2"),
+    ('孩', "hai2"),
+    ('它', "ta1"),
+    ('安', "an1"),
+    ('宋', "song4"),
+    ('完', "wan2"),
+    ('官', "guan1"),
+    ('定', "ding4"),
+    ('宝', "bao3"),
+    ('实', "shi2"),
+    ('客', "ke4"),
+    ('室', "shi4"),
+    ('害', "hai4"),
+    ('家', "jia1"),
+    ('容', "rong2"),
+    ('宾', "bin1"),
+    ('密', "mi4"),
+    ('富', "fu4"),
+    ('寒', "han2"),
+    ('对', "dui4"),
+    ('导', "dao3"),
+    ('封', "feng1"),
+    ('将', "jiang1"),
+    ('小', "xiao3"),
+    ('少', "shao3"),
+    ('尖', "jian1"),
+    ('就', "jiu4"),
+    ('尹', "yin3"),
+    ('尺', "chi3"),
+    ('局', "ju2"),
+    ('层', "ceng2"),
+    ('居', "ju1"),
+    ('屋', "wu1"),
+    ('山', "shan1"),
+    ('岁', "sui4"),
+    ('岛', "dao3"),
+    ('岸', "an4"),
+    ('崔', "cui1"),
+    ('川', "chuan1"),
+    ('州', "zhou1"),
+    ('工', "gong1"),
+    ('左', "zuo3"),
+    ('差', "cha1"),
+    ('己', "ji3"),
+    ('已', "yi3"),
+    ('巴', "ba1"),
+    ('币', "bi4"),
+    ('市', "shi4"),
+    ('布', "bu4"),
+    ('师', "shi1"),
+    ('希', "xi1"),
+    ('帝', "di4"),
+    ('带', "dai4"),
+    ('帮', "bang1"),
+    ('常', "chang2"),
+    ('帽', "mao4"),
+    ('干', "gan1"),
+    ('平', "ping2"),
+    ('年', "nian2"),
+    ('并', "bing4"),
+    ('幸', "xing4"),
+    ('广', "guang3"),
+    ('庆', "qing4"),
+    ('床', "chuang2"),
+    ('应', "ying1"),
+    ('底', "di3"),
+    ('店', "dian4"),
+    ('府', "fu3"),
+    ('度', "du4"),
+    ('座', "zuo4"),
+    ('廖', "liao4"),
+    ('建', "jian4"),
+    ('开', "kai1"),
+    ('式', "shi4"),
+    ('引', "yin3"),
+    ('弟', "di4"),
+    ('张', "zhang1"),
+    ('强', "qiang2"),
+    ('归', "gui1"),
+    ('当', "dang1"),
+    ('录', "lu4"),
+    ('形', "xing2"),
+    ('彩', "cai3"),
+    ('彭', "peng2"),
+    ('影', "ying3"),
+    ('彼', "bi3"),
+    ('往', "wang3"),
+    ('待', "dai4"),
+    ('很', "hen3"),
+    ('律', "lv4"),
+    ('徐', "xu2"),
+    ('得', "de2"),
+    ('微', "wei1"),
+    ('德', "de2"),
+    ('心', "xin1"),
+    ('必', "bi4"),
+    ('志', "zhi4"),
+    ('忘', "wang4"),
+    ('忙', "mang2"),
+    ('快', "kuai4"),
+    ('态', "tai4"),
+    ('怎', "zen3"),
+    ('怕', "pa4"),
+    ('思', "si1"),
+    ('急', "ji2"),
+    ('性', "xing4"),
+    ('总', "zong3"),
+    ('恨', "hen4"),
+    ('息', "xi1"),
+    ('恶', "e4"),
+    ('您', "nin2"),
+    ('悲', "bei1"),
+    ('情', "qing2"),
+    ('惊', "jing1"),
+    ('惯', "guan4"),
+    ('想', "xiang3"),
+    ('意', "yi4"),
+    ('感', "gan3"),
+    ('愿', "yuan4"),
+    ('慢', "man4"),
+    ('懂', "dong3"),
+    ('戏', "xi4"),
+    ('成', "cheng2"),
+    ('我', "wo3"),
+    ('或', "huo4"),
+    ('战', "zhan4"),
+    ('戴', "dai4"),
+    ('户', "hu4"),
+    ('房', "fang2"),
+    ('所', "suo3"),
+    ('手', "shou3"),
+    ('才', "cai2"),
+    ('打', "da3"),
+    ('找', "zhao3"),
+    ('承', "cheng2"),
+    ('技', "ji4"),
+    ('把', "ba3"),
+    ('抖', "dou3"),
+    ('抗', "kang4"),
+    ('护', "hu4"),
+    ('报', "bao4"),
+    ('抱', "bao4"),
+    ('担', "dan1"),
+    ('拉', "la1"),
+    ('拔', "ba2"),
+    ('拜', "bai4"),
+    ('拼', "pin1"),
+    ('拿', "na2"),
+    ('持', "chi2"),
+    ('挂', "gua4"),
+    ('指', "zhi3"),
+    ('按', "an4"),
+    ('捕', "bu3"),
+    ('换', "huan4"),
+    ('据', "ju4"),
+    ('掉', "diao4"),
+    ('掌', "zhang3"),
+    ('排', "pai2"),
+    ('接', "jie1"),
+    ('控', "kong4"),
+    ('推', "tui1"),
+    ('提', "ti2"),
+    ('插', "cha1"),
+    ('搬', "ban1"),
+    ('摆', "bai3"),
+    ('播', "bo1"),
+    ('操', "cao1"),
+    ('擦', "ca1"),
+    ('支', "zhi1"),
+    ('收', "shou1"),
+    ('改', "gai3"),
+    ('攻', "gong1"),
+    ('放', "fang4"),
+    ('政', "zheng4"),
+    ('故', "gu4"),
+    ('效', "xiao4"),
+    ('敌', "di2"),
+    ('救', "jiu4"),
+    ('教', "jiao4"),
+    ('敢', "gan3"),
+    ('数', "shu3"),
+    ('整', "zheng3"),
+    ('文', "wen2"),
+    ('斗', "dou4"),
+    ('斤', "jin1"),
+    ('断', "duan4"),
+    ('新', "xin1"),
+    ('方', "fang1"),
+    ('旁', "pang2"),
+    ('旅', "lv3"),
+    ('族', "zu2"),
+    ('无', "wu2"),
+    ('日', "ri4"),
+    ('旧', "jiu4"),
+    ('早', "zao3"),
+    ('时', "shi2"),
+    ('昂', "ang2"),
+    ('明', "ming2"),
+    ('易', "yi4"),
+    ('星', "xing1"),
+    ('春', "chun1"),
+    ('昨', "zuo2"),
+    ('是', "shi4"),
+    ('显', "xian3"),
+    ('晚', "wan3"),
+    ('晨', "chen2"),
+    ('景', "jing3"),
+    ('暖', "nuan3"),
+    ('暗', "an4"),
+    ('暴', "bao4"),
+    ('更', "geng4"),
+    ('曹', "cao2"),
+    ('曾', "ceng2"),
+    ('最', "zui4"),
+    ('月', "yue4"),
+    ('有', "you3"),
+    ('朋', "peng2"),
+    ('服', "fu2"),
+    ('望', "wang4"),
+    ('朝', "chao2"),
+    ('期', "qi1"),
+    ('木', "mu4"),
+    ('未', "wei4"),
+    ('末', "mo4"),
+    ('本', "ben3"),
+    ('术', "shu4"),
+    ('朱', "zhu1"),
+    ('朵', "duo3"),
+    ('机', "ji1"),
+    ('杀', "sha1"),
+    ('杂', "za2"),
+    ('李', "li3"),
+    ('材', "cai2"),
+    ('村', "cun1"),
+    ('杜', "du4"),
+    ('条', "tiao2"),
+    ('来', "lai2"),
+    ('杨', "yang2"),
+    ('杭', "hang2"),
+    ('杯', "bei1"),
+    ('板', "ban3"),
+    ('极', "ji2"),
+    ('林', "lin2"),
+    ('果', "guo3"),
+    ('架', "jia4"),
+    ('查', "cha2"),
+    ('标', "biao1"),
+    ('树', "shu4"),
+    ('校', "xiao4"),
+    ('样', "yang4"),
+    ('根', "gen1"),
+    ('格', "ge2"),
+    ('案', "an4"),
+    ('桌', "zhuo1"),
+    ('桥', "qiao2"),
+    ('梁', "liang2"),
+    ('梦', "meng4"),
+    ('检', "jian3"),
+    ('棒', "bang4"),
+    ('森', "sen1"),
+    ('楚', "chu3"),
+    ('楼', "lou2"),
+    ('概', "gai4"),
+    ('次', "ci4"),
+    ('欢', "huan1"),
+    ('歌', "ge1"),
+    ('正', "zheng4"),
+    ('此', "ci3"),
+    ('步', "bu4"),
+    ('武', "wu3"),
+    ('死', "si3"),
+    ('残', "can2"),
+    ('段', "duan4"),
+    ('母', "mu3"),
+    ('每', "mei3"),
+    ('比', "bi3"),
+    ('毕', "bi4"),
+    ('毛', "mao2"),
+    ('民', "min2"),
+    ('气', "qi4"),
+    ('水', "shui3"),
+    ('永', "yong3"),
+    ('求', "qiu2"),
+    ('汉', "han4"),
+    ('汗', "han4"),
+    ('江', "jiang1"),
+    ('池', "chi2"),
+    ('汤', "tang1"),
+    ('汪', "wang1"),
+    ('汽', "qi4"),
+    ('沈', "shen3"),
+    ('沉', "chen2"),
+    ('没', "mei2"),
+    ('沪', "hu4"),
+    ('河', "he2"),
+    ('油', "you2"),
+    ('治', "zhi4"),
+    ('法', "fa3"),
+    ('波', "bo1"),
+    ('注', "zhu4"),
+    ('洗', "xi3"),
+    ('洞', "dong4"),
+    ('津', "jin1"),
+    ('活', "huo2"),
+    ('流', "liu2"),
+    ('浅', "qian3"),
+    ('测', "ce4"),
+    ('济', "ji4"),
+    ('浙', "zhe4"),
+    ('浪', "lang4"),
+    ('海', "hai3"),
+    ('淘', "tao2"),
+    ('深', "shen1"),
+    ('清', "qing1"),
+    ('渐', "jian4"),
+    ('港', "gang3"),
+    ('游', "you2"),
+    ('湖', "hu2"),
+    ('湾', "wan1"),
+    ('满', "man3"),
+    ('滴', "di1"),
+    ('演', "yan3"),
+    ('潘', "pan1"),
+    ('潮', "chao2"),
+    ('激', "ji1"),
+    ('火', "huo3"),
+    ('灯', "deng1"),
+    ('灰', "hui1"),
+    ('点', "dian3"),
+    ('烂', "lan4"),
+    ('烦', "fan2"),
+    ('热', "re4"),
+    ('然', "ran2"),
+    ('照', "zhao4"),
+    ('熊', "xiong2"),
+    ('爱', "ai4"),
+    ('父', "fu4"),
+    ('爸', "ba4"),
+    ('爹', "die1"),
+    ('片', "pian4"),
+    ('版', "ban3"),
+    ('牛', "niu2"),
+    ('物', "wu4"),
+    ('特', "te4"),
+    ('犯', "fan4"),
+    ('状', "zhuang4"),
+    ('独', "du2"),
+    ('猜', "cai1"),
+    ('王', "wang2"),
+    ('玩', "wan2"),
+    ('环', "huan2"),
+    ('现', "xian4"),
+    ('班', "ban1"),
+    ('球', "qiu2"),
+    ('理', "li3"),
+    ('瓜', "gua1"),
+    ('甘', "gan1"),
+    ('生', "sheng1"),
+    ('用', "yong4"),
+    ('田', "tian2"),
+    ('由', "you2"),
+    ('电', "dian4"),
+    ('男', "nan2"),
+    ('画', "hua4"),
+    ('界', "jie4"),
+    ('留', "liu2"),
+    ('疑', "yi2"),
+    ('病', "bing4"),
+    ('痛', "tong4"),
+    ('登', "deng1"),
+    ('白', "bai2"),
+    ('百', "bai3"),
+    ('的', "de"),
+    ('皇', "huang2"),
+    ('皮', "pi2"),
+    ('益', "yi4"),
+    ('目', "mu4"),
+    ('直', "zhi2"),
+    ('相', "xiang1"),
+    ('省', "sheng3"),
+    ('看', "kan4"),
+    ('真', "zhen1"),
+    ('眼', "yan3"),
+    ('睡', "shui4"),
+    ('知', "zhi1"),
+    ('短', "duan3"),
+    ('矮', "ai3"),
+    ('石', "shi2"),
+    ('研', "yan2"),
+    ('破', "po4"),
+    ('础', "chu3"),
+    ('确', "que4"),
+    ('碍', "ai4"),
+    ('示', "shi4"),
+    ('礼', "li3"),
+    ('社', "she4"),
+    ('神', "shen2"),
+    ('票', "piao4"),
+    ('福', "fu2"),
+    ('离', "li2"),
+    ('私', "si1"),
+    ('秋', "qiu1"),
+    ('种', "zhong3"),
+    ('科', "ke1"),
+    ('秦', "qin2"),
+    ('积', "ji1"),
+    ('移', "yi2"),
+    ('程', "cheng2"),
+    ('究', "jiu1"),
+    ('空', "kong1"),
+    ('穿', "chuan1"),
+    ('窗', "chuang1"),
+    ('立', "li4"),
+    ('站', "zhan4"),
+    ('竟', "jing4"),
+    ('笑', "xiao4"),
+    ('笔', "bi3"),
+    ('第', "di4"),
+    ('等', "deng3"),
+    ('答', "da2"),
+    ('策', "ce4"),
+    ('简', "jian3"),
+    ('算', "suan4"),
+    ('管', "guan3"),
+    ('米', "mi3"),
+    ('类', "lei4"),
+    ('粉', "fen3"),
+    ('粤', "yue4"),
+    ('精', "jing1"),
+    ('系', "xi4"),
+    ('紧', "jin3"),
+    ('紫', "zi3"),
+    ('累', "lei4"),
+    ('红', "hong2"),
+    ('约', "yue1"),
+    ('级', "ji2"),
+    ('纸', "zhi3"),
+    ('线', "xian4"),
+    ('练', "lian4"),
+    ('组', "zu3"),
+    ('细', "xi4"),
+    ('经', "jing1"),
+    ('结', "jie2"),
+    ('给', "gei3"),
+    ('络', "luo4"),
+    ('绝', "jue2"),
+    ('继', "ji4"),
+    ('续', "xu4"),
+    ('绿', "lv4"),
+    ('编', "bian1"),
+    ('网', "wang3"),
+    ('罗', "luo2"),
+    ('罢', "ba4"),
+    ('美', "mei3"),
+    ('群', "qun2"),
+    ('翻', "fan1"),
+    ('老', "lao3"),
+    ('考', "kao3"),
+    ('者', "zhe3"),
+    ('而', "er2"),
+    ('耳', "er3"),
+    ('联', "lian2"),
+    ('聪', "cong1"),
+    ('肉', "rou4"),
+    ('肖', "xiao1"),
+    ('肠', "chang2"),
+    ('肯', "ken3"),
+    ('育', "yu4"),
+    ('背', "bei4"),
+    ('胜', "sheng4"),
+    ('胡', "hu2"),
+    ('能', "neng2"),
+    ('脑', "nao3"),
+    ('脚', "jiao3"),
+    ('腰', "yao1"),
+    ('腾', "teng2"),
+    ('自', "zi4"),
+    ('至', "zhi4"),
+    ('航', "hang2"),
+    ('般', "ban1"),
+    ('船', "chuan2"),
+    ('色', "se4"),
+    ('艺', "yi4"),
+    ('节', "jie2"),
+    ('花', "hua1"),
+    ('苏', "su1"),
+    ('苦', "ku3"),
+    ('英', "ying1"),
+    ('苹', "ping2"),
+    ('范', "fan4"),
+    ('茶', "cha2"),
+    ('草', "cao3"),
+    ('药', "yao4"),
+    ('莫', "mo4"),
+    ('获', "huo4"),
+    ('菜', "cai4"),
+    ('营', "ying2"),
+    ('落', "luo4"),
+    ('董', "dong3"),
+    ('蒋', "jiang3"),
+    ('蓝', "lan2"),
+    ('蔡', "cai4"),
+    ('蕉', "jiao1"),
+    ('薛', "xue1"),
+    ('藏', "cang2"),
+    ('虫', "chong2"),
+    ('虽', "sui1"),
+    ('蛇', "she2"),
+    ('蛋', "dan4"),
+    ('行', "xing2"),
+    ('街', "jie1"),
+    ('衣', "yi1"),
+    ('补', "bu3"),
+    ('表', "biao3"),
+    ('袁', "yuan2"),
+    ('袋', "dai4"),
+    ('被', "bei4"),
+    ('裤', "ku4"),
+    ('西', "xi1"),
+    ('要', "yao4"),
+    ('覃', "qin2"),
+    ('见', "jian4"),
+    ('观', "guan1"),
+    ('规', "gui1"),
+    ('视', "shi4"),
+    ('觉', "jue2"),
+    ('角', "jiao3"),
+    ('解', "jie3"),
+    ('言', "yan2"),
+    ('警', "jing3"),
+    ('计', "ji4"),
+    ('认', "ren4"),
+    ('讨', "tao3"),
+    ('让', "rang4"),
+    ('议', "yi4"),
+    ('讯', "xun4"),
+    ('记', "ji4"),
+    ('讲', "jiang3"),
+    ('许', "xu3"),
+    ('论', "lun4"),
+    ('设', "she4"),
+    ('访', "fang3"),
+    ('证', "zheng4"),
+    ('识', "shi2"),
+    ('词', "ci2"),
+    ('试', "shi4"),
+    ('诗', "shi1"),
+    ('诚', "cheng2"),
+    ('话', "hua4"),
+    ('该', "gai1"),
+    ('语', "yu3"),
+    ('误', "wu4"),
+    ('说', "shuo1"),
+    ('请', "qing3"),
+    ('读', "du2"),
+    ('课', "ke4"),
+    ('谁', "shei2"),
+    ('调', "tiao2"),
+    ('谈', "tan2"),
+    ('谢', "xie4"),
+    ('谭', "tan2"),
+    ('豆', "dou4"),
+    ('象', "xiang4"),
+    ('负', "fu4"),
+    ('财', "cai2"),
+    ('责', "ze2"),
+    ('败', "bai4"),
+    ('质', "zhi4"),
+    ('贵', "gui4"),
+    ('费', "fei4"),
+    ('贺', "he4"),
+    ('贾', "jia3"),
+    ('赢', "ying2"),
+    ('赤', "chi4"),
+    ('走', "zou3"),
+    ('赵', "zhao4"),
+    ('起', "qi3"),
+    ('超', "chao1"),
+    ('越', "yue4"),
+    ('足', "zu2"),
+    ('跑', "pao3"),
+    ('跟', "gen1"),
+    ('路', "lu4"),
+    ('身', "shen1"),
+    ('车', "che1"),
+    ('转', "zhuan3"),
+    ('轻', "qing1"),
+    ('较', "jiao4"),
+    ('辆', "liang4"),
+    ('辈', "bei4"),
+    ('输', "shu1"),
+    ('边', "bian1"),
+    ('辽', "liao2"),
+    ('过', "guo4"),
+    ('运', "yun4"),
+    ('近', "jin4"),
+    ('还', "hai2"),
+    ('这', "zhe4"),
+    ('进', "jin4"),
+    ('远', "yuan3"),
+    ('连', "lian2"),
+    ('迟', "chi2"),
+    ('送', "song4"),
+    ('选', "xuan3"),
+    ('通', "tong1"),
+    ('速', "su4"),
+    ('造', "zao4"),
+    ('逼', "bi1"),
+    ('遇', "yu4"),
+    ('遍', "bian4"),
+    ('道', "dao4"),
+    ('避', "bi4"),
+    ('邓', "deng4"),
+    ('那', "na4"),
+    ('邱', "qiu1"),
+    ('邵', "shao4"),
+    ('邹', "zou1"),
+    ('郊', "jiao1"),
+    ('郑', "zheng4"),
+    ('郝', "hao3"),
+    ('部', "bu4"),
+    ('郭', "guo1"),
+    ('都', "dou1"),
+    ('酒', "jiu3"),
+    ('采', "cai3"),
+    ('里', "li3"),
+    ('重', "zhong4"),
+    ('量', "liang4"),
+    ('金', "jin1"),
+    ('钟', "zhong1"),
+    ('钢', "gang1"),
+    ('钱', "qian2"),
+    ('铁', "tie3"),
+    ('银', "yin2"),
+    ('错', "cuo4"),
+    ('长', "chang2"),
+    ('门', "men2"),
+    ('闫', "yan2"),
+    ('闭', "bi4"),
+    ('问', "wen4"),
+    ('间', "jian1"),
+    ('闻', "wen2"),
+    ('闽', "min3"),
+    ('队', "dui4"),
+    ('防', "fang2"),
+    ('阳', "yang2"),
+    ('阶', "jie1"),
+    ('阿', "a1"),
+    ('附', "fu4"),
+    ('际', "ji4"),
+    ('陆', "lu4"),
+    ('陈', "chen2"),
+    ('院', "yuan4"),
+    ('除', "chu2"),
+    ('险', "xian3"),
+    ('陶', "tao2"),
+    ('难', "nan2"),
+    ('雄', "xiong2"),
+    ('集', "ji2"),
+    ('雨', "yu3"),
+    ('雪', "xue3"),
+    ('零', "ling2"),
+    ('雷', "lei2"),
+    ('需', "xu1"),
+    ('青', "qing1"),
+    ('静', "jing4"),
+    ('非', "fei1"),
+    ('面', "mian4"),
+    ('革', "ge2"),
+    ('鞋', "xie2"),
+    ('韦', "wei2"),
+    ('韩', "han2"),
+    ('音', "yin1"),
+    ('页', "ye4"),
+    ('顶', "ding3"),
+    ('项', "xiang4"),
+    ('须', "xu1"),
+    ('顾', "gu4"),
+    ('顿', "dun4"),
+    ('预', "yu4"),
+    ('领', "ling3"),
+    ('题', "ti2"),
+    ('颜', "yan2"),
+    ('额', "e2"),
+    ('风', "feng1"),
+    ('飞', "fei1"),
+    ('餐', "can1"),
+    ('饭', "fan4"),
+    ('饱', "bao3"),
+    ('饺', "jiao3"),
+    ('饼', "bing3"),
+    ('饿', "e4"),
+    ('馆', "guan3"),
+    ('首', "shou3"),
+    ('香', "xiang1"),
+    ('马', "ma3"),
+    ('验', "yan4"),
+    ('骑', "qi2"),
+    ('高', "gao1"),
+    ('魏', "wei4"),
+    ('鱼', "yu2"),
+    ('鲁', "lu3"),
+    ('鲜', "xian1"),
+    ('鸟', "niao3"),
+    ('鸡', "ji1"),
+    ('黄', "huang2"),
+    ('黎', "li2"),
+    ('黑', "hei1"),
+    ('鼻', "bi2"),
+    ('齐', "qi2"),
+    ('龙', "long2"),
+    ('龚', "gong1"),
+];
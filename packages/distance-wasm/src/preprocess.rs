@@ -0,0 +1,334 @@
+// Text preprocessing applied before any distance is computed
+// Lets visually identical strings ("café" in NFC and NFD, "Straße" and
+// "STRASSE") compare as equal without preparing them in JavaScript first
+
+use std::borrow::Cow;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategoryGroup, UnicodeGeneralCategory};
+
+use crate::kana::fold_kana;
+use crate::transliterate::transliterate;
+
+/// Unicode normalization form applied to the input
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalization {
+    #[default]
+    None,
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+/// Options applied to both inputs of a scorer before comparison
+///
+/// Steps run in a fixed order: kana folding, transliteration, diacritic
+/// stripping, case folding, normalization, punctuation removal and
+/// whitespace collapsing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Preprocessor {
+    /// Fold character width, katakana to hiragana and long vowel marks
+    pub fold_kana: bool,
+    /// Transliterate Cyrillic, Greek, Arabic, Hebrew, kana and Latin-extended letters to ASCII
+    pub transliterate: bool,
+    /// Unicode normalization form
+    pub normalization: Normalization,
+    /// Full Unicode case folding (`ß` folds to `ss`)
+    pub case_fold: bool,
+    /// Remove combining marks after canonical decomposition (`é` becomes `e`)
+    pub strip_diacritics: bool,
+    /// Trim and replace every run of whitespace with a single space
+    pub collapse_whitespace: bool,
+    /// Replace characters in the Unicode punctuation categories with a space,
+    /// so that "foo,bar" keeps two words
+    pub remove_punctuation: bool,
+}
+
+impl Preprocessor {
+    /// Parse a list of option names separated by commas, `|` or whitespace
+    ///
+    /// Recognised names are `nfc`, `nfd`, `nfkc`, `nfkd`, `fold_kana`,
+    /// `transliterate`, `case_fold`, `strip_diacritics`, `collapse_whitespace`
+    /// and `remove_punctuation`; unknown names are ignored, see `try_parse`.
+    pub fn parse(spec: &str) -> Preprocessor {
+        Preprocessor::parse_known(spec).0
+    }
+
+    /// Parse like `parse`, rejecting unknown option names
+    pub fn try_parse(spec: &str) -> Result<Preprocessor, String> {
+        match Preprocessor::parse_known(spec) {
+            (preprocessor, None) => Ok(preprocessor),
+            (_, Some(unknown)) => Err(format!("unknown preprocessing option \"{}\"", unknown)),
+        }
+    }
+
+    /// The known options of a spec and the first unknown name, if any
+    fn parse_known(spec: &str) -> (Preprocessor, Option<&str>) {
+        let mut preprocessor = Preprocessor::default();
+        let mut unknown = None;
+
+        for option in spec
+            .split(|c: char| c == ',' || c == '|' || c.is_whitespace())
+            .filter(|option| !option.is_empty())
+        {
+            match option.to_lowercase().as_str() {
+                "nfc" => preprocessor.normalization = Normalization::Nfc,
+                "nfd" => preprocessor.normalization = Normalization::Nfd,
+                "nfkc" => preprocessor.normalization = Normalization::Nfkc,
+                "nfkd" => preprocessor.normalization = Normalization::Nfkd,
+                "fold_kana" | "fold-kana" | "kana" => preprocessor.fold_kana = true,
+                "transliterate" | "ascii" => preprocessor.transliterate = true,
+                "case_fold" | "case-fold" | "casefold" => preprocessor.case_fold = true,
+                "strip_diacritics" | "strip-diacritics" | "diacritics" => {
+                    preprocessor.strip_diacritics = true
+                }
+                "collapse_whitespace" | "collapse-whitespace" | "whitespace" => {
+                    preprocessor.collapse_whitespace = true
+                }
+                "remove_punctuation" | "remove-punctuation" | "punctuation" => {
+                    preprocessor.remove_punctuation = true
+                }
+                _ => unknown = unknown.or(Some(option)),
+            }
+        }
+
+        (preprocessor, unknown)
+    }
+
+    /// Canonical spec naming the enabled options in the order they run, which
+    /// `try_parse` reads back to the same preprocessor
+    pub fn spec(&self) -> String {
+        let normalization = match self.normalization {
+            Normalization::None => None,
+            Normalization::Nfc => Some("nfc"),
+            Normalization::Nfd => Some("nfd"),
+            Normalization::Nfkc => Some("nfkc"),
+            Normalization::Nfkd => Some("nfkd"),
+        };
+        let options = [
+            (self.fold_kana, Some("fold_kana")),
+            (self.transliterate, Some("transliterate")),
+            (self.strip_diacritics, Some("strip_diacritics")),
+            (self.case_fold, Some("case_fold")),
+            (true, normalization),
+            (self.remove_punctuation, Some("remove_punctuation")),
+            (self.collapse_whitespace, Some("collapse_whitespace")),
+        ];
+        let names: Vec<&str> = options
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .filter_map(|&(_, name)| name)
+            .collect();
+        names.join(",")
+    }
+
+    /// Apply the configured steps to a single string
+    pub fn process(&self, text: &str) -> String {
+        self.apply(text).into_owned()
+    }
+
+    fn is_identity(&self) -> bool {
+        *self == Preprocessor::default()
+    }
+
+    /// Apply the configured steps, borrowing the input when nothing is enabled
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut text: Cow<str> = Cow::Borrowed(text);
+
+        if self.fold_kana {
+            text = Cow::Owned(fold_kana(&text));
+        }
+        if self.transliterate {
+            text = Cow::Owned(transliterate(&text));
+        }
+        if self.strip_diacritics {
+            text = Cow::Owned(text.nfd().filter(|&c| !is_combining_mark(c)).collect());
+        }
+        if self.case_fold {
+            text = Cow::Owned(caseless::default_case_fold_str(&text));
+        }
+        text = match self.normalization {
+            Normalization::None => text,
+            Normalization::Nfc => Cow::Owned(text.nfc().collect()),
+            Normalization::Nfd => Cow::Owned(text.nfd().collect()),
+            Normalization::Nfkc => Cow::Owned(text.nfkc().collect()),
+            Normalization::Nfkd => Cow::Owned(text.nfkd().collect()),
+        };
+        if self.remove_punctuation {
+            text = Cow::Owned(
+                text.chars()
+                    .map(|c| match c.general_category_group() {
+                        GeneralCategoryGroup::Punctuation => ' ',
+                        _ => c,
+                    })
+                    .collect(),
+            );
+        }
+        if self.collapse_whitespace {
+            text = Cow::Owned(text.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+
+        text
+    }
+}
+
+/// Preprocess both inputs of a scorer, borrowing them when no options are given
+pub fn prepare<'a>(
+    s1: &'a str,
+    s2: &'a str,
+    preprocessor: Option<&str>,
+) -> (Cow<'a, str>, Cow<'a, str>) {
+    match preprocessor {
+        Some(spec) => {
+            let p = Preprocessor::parse(spec);
+            (p.apply(s1), p.apply(s2))
+        }
+        None => (Cow::Borrowed(s1), Cow::Borrowed(s2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_identity() {
+        let p = Preprocessor::default();
+        assert!(matches!(p.apply("Café,  Bar"), Cow::Borrowed("Café,  Bar")));
+    }
+
+    #[test]
+    fn test_normalization_forms() {
+        let nfc = "caf\u{e9}";
+        let nfd = "cafe\u{301}";
+        let p = |normalization| Preprocessor {
+            normalization,
+            ..Preprocessor::default()
+        };
+
+        assert_eq!(p(Normalization::Nfc).process(nfd), nfc);
+        assert_eq!(p(Normalization::Nfd).process(nfc), nfd);
+        assert_eq!(p(Normalization::Nfkc).process("\u{fb01}le"), "file");
+        assert_eq!(p(Normalization::Nfkd).process("\u{2460}"), "1");
+    }
+
+    #[test]
+    fn test_case_fold() {
+        let p = Preprocessor {
+            case_fold: true,
+            ..Preprocessor::default()
+        };
+        assert_eq!(p.process("Straße"), "strasse");
+        assert_eq!(p.process("STRASSE"), "strasse");
+        assert_eq!(p.process("ΣΊΣΥΦΟΣ"), p.process("σίσυφος"));
+    }
+
+    #[test]
+    fn test_strip_diacritics() {
+        let p = Preprocessor {
+            strip_diacritics: true,
+            ..Preprocessor::default()
+        };
+        assert_eq!(p.process("résumé"), "resume");
+        assert_eq!(p.process("cafe\u{301}"), "cafe");
+        assert_eq!(p.process("naïve señor"), "naive senor");
+    }
+
+    #[test]
+    fn test_transliterate() {
+        let p = Preprocessor {
+            transliterate: true,
+            case_fold: true,
+            ..Preprocessor::default()
+        };
+        assert_eq!(p.process("Москва"), "moskva");
+        assert_eq!(p.process("Αθήνα"), p.process("Athina"));
+    }
+
+    #[test]
+    fn test_fold_kana() {
+        let p = Preprocessor::parse("fold_kana");
+        assert_eq!(p.process("ｺﾝﾋﾟｭｰﾀｰ"), p.process("コンピュータ"));
+        assert_eq!(p.process("ＳＯＮＹ　テレビ"), p.process("SONY てれび"));
+
+        // Folded kana transliterate the same way as full-width kana
+        let p = Preprocessor::parse("fold_kana,transliterate");
+        assert_eq!(p.process("ｽｼ"), "sushi");
+    }
+
+    #[test]
+    fn test_whitespace_and_punctuation() {
+        let p = Preprocessor {
+            collapse_whitespace: true,
+            remove_punctuation: true,
+            ..Preprocessor::default()
+        };
+        assert_eq!(p.process("  Hello,\t world!  "), "Hello world");
+        assert_eq!(p.process("«quoted» — text"), "quoted text");
+        assert_eq!(p.process("foo,bar/baz"), "foo bar baz");
+
+        // Punctuation becomes a space rather than joining its neighbours
+        let p = Preprocessor::parse("remove_punctuation");
+        assert_eq!(p.process("foo,bar"), "foo bar");
+        assert_eq!(p.process("don't"), "don t");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Preprocessor::parse(""), Preprocessor::default());
+        assert_eq!(
+            Preprocessor::parse("NFKC, case_fold|strip_diacritics unknown"),
+            Preprocessor {
+                normalization: Normalization::Nfkc,
+                case_fold: true,
+                strip_diacritics: true,
+                ..Preprocessor::default()
+            }
+        );
+        assert_eq!(
+            Preprocessor::parse("collapse_whitespace,remove_punctuation,transliterate"),
+            Preprocessor {
+                transliterate: true,
+                collapse_whitespace: true,
+                remove_punctuation: true,
+                ..Preprocessor::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_parse() {
+        assert_eq!(
+            Preprocessor::try_parse("nfkc, case_fold"),
+            Ok(Preprocessor::parse("nfkc,case_fold"))
+        );
+        assert_eq!(Preprocessor::try_parse(""), Ok(Preprocessor::default()));
+        assert_eq!(
+            Preprocessor::try_parse("case_fold,lowercase,trim"),
+            Err("unknown preprocessing option \"lowercase\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_spec_round_trip() {
+        assert_eq!(Preprocessor::default().spec(), "");
+        let p = Preprocessor::try_parse(" Case-Fold | NFKC,kana  punctuation").unwrap();
+        assert_eq!(p.spec(), "fold_kana,case_fold,nfkc,remove_punctuation");
+        assert_eq!(Preprocessor::try_parse(&p.spec()), Ok(p));
+    }
+
+    #[test]
+    fn test_prepare() {
+        let (a, b) = prepare("caf\u{e9}", "cafe\u{301}", Some("nfc"));
+        assert_eq!(a, b);
+
+        let (a, b) = prepare("caf\u{e9}", "cafe\u{301}", None);
+        assert_ne!(a, b);
+    }
+}
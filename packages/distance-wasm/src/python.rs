@@ -0,0 +1,542 @@
+// Python bindings - the wasm API as a CPython extension module through PyO3
+// Every function delegates to the one backing the wasm export of the same name,
+// so scores are bit-identical to the browser build. Batch functions take and
+// return plain Python lists, without a NumPy dependency
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// ============================================================================
+// Edit-based Algorithms
+// ============================================================================
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::levenshtein(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::levenshtein_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn damerau_levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::damerau_levenshtein(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn damerau_levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::damerau_levenshtein_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn jaro(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::jaro(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn jarowinkler(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::jarowinkler(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn hamming(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::hamming(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn hamming_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::hamming_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn sift4_simple(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::sift4_simple(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn sift4_simple_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::sift4_simple_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn myers_levenshtein(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::myers_levenshtein(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn myers_levenshtein_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::myers_levenshtein_normalized(s1, s2, preprocessor)
+}
+
+// ============================================================================
+// Sequence and Token Algorithms
+// ============================================================================
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn lcs_seq(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::lcs_seq(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn lcs_seq_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::lcs_seq_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn lcs_str(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::lcs_str(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn lcs_str_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::lcs_str_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn ratcliff_obershelp(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::ratcliff_obershelp(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn jaccard(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::jaccard(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn cosine(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::cosine(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn sorensen(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::sorensen(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn tversky(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::tversky(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn smith_waterman(s1: &str, s2: &str, preprocessor: Option<String>) -> u32 {
+    crate::smith_waterman(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn smith_waterman_normalized(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::smith_waterman_normalized(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn overlap(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::overlap(s1, s2, preprocessor)
+}
+
+// ============================================================================
+// Fuzzy Ratios
+// ============================================================================
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::ratio(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn partial_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::partial_ratio(s1, s2, preprocessor)
+}
+
+#[pyclass(get_all, frozen, skip_from_py_object)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreAlignment {
+    pub score: f64,
+    pub src_start: u32,
+    pub src_end: u32,
+    pub dest_start: u32,
+    pub dest_end: u32,
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, unit=None))]
+pub fn partial_ratio_alignment(s1: &str, s2: &str, unit: Option<String>) -> ScoreAlignment {
+    let res = crate::partial_ratio_alignment(s1, s2, unit);
+    ScoreAlignment {
+        score: res.score,
+        src_start: res.src_start,
+        src_end: res.src_end,
+        dest_start: res.dest_start,
+        dest_end: res.dest_end,
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn token_sort_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::token_sort_ratio(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn token_set_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::token_set_ratio(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn partial_token_sort_ratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::partial_token_sort_ratio(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn wratio(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::wratio(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, inner=None, symmetric=None, preprocessor=None))]
+pub fn monge_elkan(
+    s1: &str,
+    s2: &str,
+    inner: Option<String>,
+    symmetric: Option<bool>,
+    preprocessor: Option<String>,
+) -> f64 {
+    crate::monge_elkan(s1, s2, inner, symmetric, preprocessor)
+}
+
+// ============================================================================
+// Naive and Bigram Algorithms
+// ============================================================================
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn prefix(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::prefix(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn suffix(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::suffix(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn length(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::length(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn jaccard_bigram(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::jaccard_bigram(s1, s2, preprocessor)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, preprocessor=None))]
+pub fn cosine_bigram(s1: &str, s2: &str, preprocessor: Option<String>) -> f64 {
+    crate::cosine_bigram(s1, s2, preprocessor)
+}
+
+// ============================================================================
+// Preprocessing and Pinyin
+// ============================================================================
+
+#[pyfunction]
+pub fn preprocess(text: &str, preprocessor: &str) -> String {
+    crate::preprocess(text, preprocessor)
+}
+
+#[pyfunction]
+pub fn validate_preprocessor(preprocessor: &str) -> PyResult<()> {
+    crate::preprocess::Preprocessor::try_parse(preprocessor)
+        .map(|_| ())
+        .map_err(value_error)
+}
+
+#[pyfunction]
+pub fn transliterate(text: &str) -> String {
+    crate::transliterate(text)
+}
+
+#[pyfunction]
+pub fn fold_kana(text: &str) -> String {
+    crate::fold_kana(text)
+}
+
+#[pyfunction]
+pub fn to_pinyin(text: &str, style: &str) -> PyResult<String> {
+    crate::try_to_pinyin(text, style).map_err(value_error)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, style, preprocessor=None))]
+pub fn pinyin_similarity(
+    s1: &str,
+    s2: &str,
+    style: &str,
+    preprocessor: Option<String>,
+) -> PyResult<f64> {
+    crate::try_pinyin_similarity(s1, s2, style, preprocessor).map_err(value_error)
+}
+
+#[pyfunction]
+pub fn pinyin_initials_match(text: &str, query: &str) -> bool {
+    crate::pinyin_initials_match(text, query)
+}
+
+// ============================================================================
+// Vector Similarity - Embeddings passed as lists of floats
+// ============================================================================
+
+fn value_error(message: String) -> PyErr {
+    PyValueError::new_err(message)
+}
+
+#[pyfunction]
+pub fn vector_cosine(a: Vec<f32>, b: Vec<f32>) -> PyResult<f32> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Cosine).map_err(value_error)
+}
+
+#[pyfunction]
+pub fn vector_dot(a: Vec<f32>, b: Vec<f32>) -> PyResult<f32> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Dot).map_err(value_error)
+}
+
+#[pyfunction]
+pub fn vector_euclidean(a: Vec<f32>, b: Vec<f32>) -> PyResult<f32> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Euclidean).map_err(value_error)
+}
+
+#[pyfunction]
+pub fn vector_manhattan(a: Vec<f32>, b: Vec<f32>) -> PyResult<f32> {
+    crate::vector::pairwise(&a, &b, crate::vector::VectorMetric::Manhattan).map_err(value_error)
+}
+
+#[pyfunction]
+pub fn vector_one_to_many(query: Vec<f32>, matrix: Vec<f32>, metric: &str) -> PyResult<Vec<f32>> {
+    crate::vector_metric(metric)
+        .and_then(|metric| crate::vector::one_to_many(&query, &matrix, metric))
+        .map_err(value_error)
+}
+
+#[pyfunction]
+pub fn vector_cdist(
+    queries: Vec<f32>,
+    choices: Vec<f32>,
+    dim: usize,
+    metric: &str,
+) -> PyResult<Vec<f32>> {
+    crate::vector_metric(metric)
+        .and_then(|metric| crate::vector::cdist(&queries, &choices, dim, metric))
+        .map_err(value_error)
+}
+
+// ============================================================================
+// Universal comparison function
+// ============================================================================
+
+#[pyclass(get_all, frozen, skip_from_py_object)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchHit {
+    pub id: u32,
+    pub score: f64,
+}
+
+#[pyfunction]
+#[pyo3(signature = (queries, choices, algorithm, preprocessor=None))]
+pub fn cdist(
+    queries: Vec<String>,
+    choices: Vec<String>,
+    algorithm: &str,
+    preprocessor: Option<String>,
+) -> PyResult<Vec<f64>> {
+    crate::try_cdist(queries, choices, algorithm, preprocessor).map_err(value_error)
+}
+
+#[pyfunction]
+#[pyo3(signature = (query, choices, algorithm, limit=None, score_cutoff=None, preprocessor=None))]
+pub fn extract(
+    query: &str,
+    choices: Vec<String>,
+    algorithm: &str,
+    limit: Option<u32>,
+    score_cutoff: Option<f64>,
+    preprocessor: Option<String>,
+) -> PyResult<Vec<SearchHit>> {
+    let hits = crate::try_extract(query, choices, algorithm, limit, score_cutoff, preprocessor)
+        .map_err(value_error)?;
+    Ok(hits
+        .into_iter()
+        .map(|hit| SearchHit {
+            id: hit.id,
+            score: hit.score,
+        })
+        .collect())
+}
+
+#[pyfunction]
+#[pyo3(signature = (items, algorithm, threshold, preprocessor=None))]
+pub fn dedupe(
+    items: Vec<String>,
+    algorithm: &str,
+    threshold: f64,
+    preprocessor: Option<String>,
+) -> PyResult<Vec<u32>> {
+    crate::try_dedupe(items, algorithm, threshold, preprocessor).map_err(value_error)
+}
+
+#[pyfunction]
+#[pyo3(signature = (s1, s2, algorithm, preprocessor=None))]
+pub fn compare(s1: &str, s2: &str, algorithm: &str, preprocessor: Option<String>) -> f64 {
+    crate::compare(s1, s2, algorithm, preprocessor)
+}
+
+#[pymodule]
+fn nlptools_distance(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ScoreAlignment>()?;
+    m.add_class::<SearchHit>()?;
+    m.add_function(wrap_pyfunction!(levenshtein, m)?)?;
+    m.add_function(wrap_pyfunction!(levenshtein_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(damerau_levenshtein, m)?)?;
+    m.add_function(wrap_pyfunction!(damerau_levenshtein_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(jaro, m)?)?;
+    m.add_function(wrap_pyfunction!(jarowinkler, m)?)?;
+    m.add_function(wrap_pyfunction!(hamming, m)?)?;
+    m.add_function(wrap_pyfunction!(hamming_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(sift4_simple, m)?)?;
+    m.add_function(wrap_pyfunction!(sift4_simple_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(myers_levenshtein, m)?)?;
+    m.add_function(wrap_pyfunction!(myers_levenshtein_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(lcs_seq, m)?)?;
+    m.add_function(wrap_pyfunction!(lcs_seq_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(lcs_str, m)?)?;
+    m.add_function(wrap_pyfunction!(lcs_str_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(ratcliff_obershelp, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard, m)?)?;
+    m.add_function(wrap_pyfunction!(cosine, m)?)?;
+    m.add_function(wrap_pyfunction!(sorensen, m)?)?;
+    m.add_function(wrap_pyfunction!(tversky, m)?)?;
+    m.add_function(wrap_pyfunction!(smith_waterman, m)?)?;
+    m.add_function(wrap_pyfunction!(smith_waterman_normalized, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap, m)?)?;
+    m.add_function(wrap_pyfunction!(ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(partial_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(partial_ratio_alignment, m)?)?;
+    m.add_function(wrap_pyfunction!(token_sort_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(token_set_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(partial_token_sort_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(wratio, m)?)?;
+    m.add_function(wrap_pyfunction!(monge_elkan, m)?)?;
+    m.add_function(wrap_pyfunction!(prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(suffix, m)?)?;
+    m.add_function(wrap_pyfunction!(length, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_bigram, m)?)?;
+    m.add_function(wrap_pyfunction!(cosine_bigram, m)?)?;
+    m.add_function(wrap_pyfunction!(preprocess, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_preprocessor, m)?)?;
+    m.add_function(wrap_pyfunction!(transliterate, m)?)?;
+    m.add_function(wrap_pyfunction!(fold_kana, m)?)?;
+    m.add_function(wrap_pyfunction!(to_pinyin, m)?)?;
+    m.add_function(wrap_pyfunction!(pinyin_similarity, m)?)?;
+    m.add_function(wrap_pyfunction!(pinyin_initials_match, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_cosine, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_dot, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_euclidean, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_manhattan, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_one_to_many, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_cdist, m)?)?;
+    m.add_function(wrap_pyfunction!(cdist, m)?)?;
+    m.add_function(wrap_pyfunction!(extract, m)?)?;
+    m.add_function(wrap_pyfunction!(dedupe, m)?)?;
+    m.add_function(wrap_pyfunction!(compare, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIRS: [(&str, &str); 4] = [
+        ("kitten", "sitting"),
+        ("New York Mets", "new york meats"),
+        ("", "abc"),
+        ("Straße café", "strasse cafe"),
+    ];
+
+    #[test]
+    fn test_scorers_match_wasm_exports() {
+        let pre = || Some("case_fold,transliterate".to_string());
+        for &(a, b) in PAIRS.iter() {
+            assert_eq!(
+                damerau_levenshtein(a, b, None),
+                crate::damerau_levenshtein(a, b, None)
+            );
+            assert_eq!(
+                token_set_ratio(a, b, pre()),
+                crate::token_set_ratio(a, b, pre())
+            );
+            assert_eq!(
+                partial_ratio_alignment(a, b, Some("utf16".into())).dest_end,
+                crate::partial_ratio_alignment(a, b, Some("utf16".into())).dest_end
+            );
+            assert_eq!(
+                compare(a, b, "monge_elkan", pre()),
+                crate::compare(a, b, "monge_elkan", pre())
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_matches_wasm_exports() {
+        let items: Vec<String> = ["apple", "apples", "banana", "Apple"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            cdist(items.clone(), items.clone(), "jaro", None).ok(),
+            crate::cdist(items.clone(), items.clone(), "jaro", None).ok()
+        );
+        let hits = extract("apple", items.clone(), "ratio", None, Some(0.5), None).unwrap();
+        let expected = crate::extract("apple", items.clone(), "ratio", None, Some(0.5), None)
+            .ok()
+            .unwrap();
+        assert_eq!(hits.len(), expected.len());
+        for (hit, expected) in hits.iter().zip(&expected) {
+            assert_eq!((hit.id, hit.score), (expected.id, expected.score));
+        }
+        assert_eq!(
+            dedupe(items.clone(), "ratio", 0.8, None).ok(),
+            crate::dedupe(items, "ratio", 0.8, None).ok()
+        );
+    }
+}
@@ -0,0 +1,353 @@
+// Quantized vector store - compact embeddings with asymmetric distances
+// Each vector is kept as int8 codes with a per-vector scale (a quarter of the
+// f32 size) plus its sign bits (1/32 of it); queries stay in full precision
+
+use std::collections::HashMap;
+
+use crate::hnsw::HnswMetric;
+use crate::vector::dot;
+
+/// Sign bits of a vector, packed little-endian into 64-bit words
+fn sign_bits(vector: &[f32]) -> Vec<u64> {
+    let mut bits = vec![0u64; vector.len().div_ceil(64)];
+    for (i, &x) in vector.iter().enumerate() {
+        if x > 0.0 {
+            bits[i / 64] |= 1 << (i % 64);
+        }
+    }
+    bits
+}
+
+fn hamming(a: &[u64], b: &[u64]) -> u32 {
+    a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+/// Symmetric int8 quantization, returning the codes and the scale restoring them
+fn quantize(vector: &[f32]) -> (Vec<i8>, f32) {
+    let max = vector.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+    if max == 0.0 {
+        return (vec![0; vector.len()], 0.0);
+    }
+    let scale = max / 127.0;
+    let codes = vector
+        .iter()
+        .map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+        .collect();
+    (codes, scale)
+}
+
+pub struct QuantizedStore {
+    metric: HnswMetric,
+    dim: usize,
+    words: usize,
+    ids: Vec<u32>,
+    /// Int8 codes, one row of `dim` per vector
+    codes: Vec<i8>,
+    scales: Vec<f32>,
+    /// Sign bits, one row of `words` per vector
+    bits: Vec<u64>,
+    slots: HashMap<u32, usize>,
+}
+
+impl QuantizedStore {
+    pub fn new(dim: usize, metric: HnswMetric) -> QuantizedStore {
+        QuantizedStore {
+            metric,
+            dim,
+            words: dim.div_ceil(64),
+            ids: Vec::new(),
+            codes: Vec::new(),
+            scales: Vec::new(),
+            bits: Vec::new(),
+            slots: HashMap::new(),
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Bytes used by the stored codes, excluding the id map
+    pub fn memory_bytes(&self) -> usize {
+        self.ids.len() * (4 + self.dim + 4 + self.words * 8)
+    }
+
+    fn prepare(&self, vector: &[f32]) -> Result<Vec<f32>, String> {
+        if vector.len() != self.dim {
+            return Err(format!(
+                "vector has length {}, expected {}",
+                vector.len(),
+                self.dim
+            ));
+        }
+        let mut vector = vector.to_vec();
+        if self.metric == HnswMetric::Cosine {
+            let norm = dot(&vector, &vector).sqrt();
+            if norm > 0.0 {
+                vector.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+        Ok(vector)
+    }
+
+    /// Insert a vector, replacing any vector stored under the same id
+    pub fn insert(&mut self, id: u32, vector: &[f32]) -> Result<(), String> {
+        let vector = self.prepare(vector)?;
+        self.delete(id);
+
+        let (codes, scale) = quantize(&vector);
+        self.slots.insert(id, self.ids.len());
+        self.ids.push(id);
+        self.codes.extend_from_slice(&codes);
+        self.scales.push(scale);
+        self.bits.extend_from_slice(&sign_bits(&vector));
+        Ok(())
+    }
+
+    /// Remove a vector, moving the last one into its slot
+    pub fn delete(&mut self, id: u32) -> bool {
+        let slot = match self.slots.remove(&id) {
+            Some(slot) => slot,
+            None => return false,
+        };
+        let last = self.ids.len() - 1;
+        if slot != last {
+            self.ids[slot] = self.ids[last];
+            self.slots.insert(self.ids[slot], slot);
+            self.scales[slot] = self.scales[last];
+            self.codes
+                .copy_within(last * self.dim..(last + 1) * self.dim, slot * self.dim);
+            self.bits.copy_within(
+                last * self.words..(last + 1) * self.words,
+                slot * self.words,
+            );
+        }
+        self.ids.pop();
+        self.scales.pop();
+        self.codes.truncate(last * self.dim);
+        self.bits.truncate(last * self.words);
+        true
+    }
+
+    /// The vector stored under an id, restored from its int8 codes
+    pub fn get(&self, id: u32) -> Option<Vec<f32>> {
+        let slot = *self.slots.get(&id)?;
+        let scale = self.scales[slot];
+        Some(self.row(slot).iter().map(|&c| c as f32 * scale).collect())
+    }
+
+    fn row(&self, slot: usize) -> &[i8] {
+        &self.codes[slot * self.dim..(slot + 1) * self.dim]
+    }
+
+    /// Distance between a full-precision query and the int8 codes of a slot
+    fn asymmetric_distance(&self, query: &[f32], slot: usize) -> f32 {
+        let scale = self.scales[slot];
+        let codes = self.row(slot);
+        match self.metric {
+            HnswMetric::L2 => query
+                .iter()
+                .zip(codes)
+                .map(|(q, &c)| {
+                    let d = q - c as f32 * scale;
+                    d * d
+                })
+                .sum::<f32>()
+                .sqrt(),
+            HnswMetric::Cosine | HnswMetric::InnerProduct => {
+                let product = scale
+                    * query
+                        .iter()
+                        .zip(codes)
+                        .map(|(q, &c)| q * c as f32)
+                        .sum::<f32>();
+                if self.metric == HnswMetric::Cosine {
+                    1.0 - product
+                } else {
+                    -product
+                }
+            }
+        }
+    }
+
+    fn top_k(
+        &self,
+        query: &[f32],
+        slots: impl Iterator<Item = usize>,
+        k: usize,
+    ) -> Vec<(u32, f32)> {
+        let mut hits: Vec<(u32, f32)> = slots
+            .map(|slot| (self.ids[slot], self.asymmetric_distance(query, slot)))
+            .collect();
+        hits.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        hits.truncate(k);
+        hits
+    }
+
+    /// The `k` closest vectors by asymmetric int8 distance as `(id, distance)`, closest first
+    pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<(u32, f32)>, String> {
+        let query = self.prepare(query)?;
+        Ok(self.top_k(&query, 0..self.len(), k))
+    }
+
+    /// Shortlist `k * oversample` vectors by Hamming distance of their sign bits,
+    /// then rescore the shortlist against the full-precision query
+    pub fn search_binary(
+        &self,
+        query: &[f32],
+        k: usize,
+        oversample: usize,
+    ) -> Result<Vec<(u32, f32)>, String> {
+        let query = self.prepare(query)?;
+        let query_bits = sign_bits(&query);
+
+        let mut shortlist: Vec<(u32, usize)> = (0..self.len())
+            .map(|slot| {
+                let bits = &self.bits[slot * self.words..(slot + 1) * self.words];
+                (hamming(&query_bits, bits), slot)
+            })
+            .collect();
+        let size = k.saturating_mul(oversample.max(1)).min(shortlist.len());
+        if size < shortlist.len() {
+            shortlist.select_nth_unstable(size);
+            shortlist.truncate(size);
+        }
+        Ok(self.top_k(&query, shortlist.into_iter().map(|(_, slot)| slot), k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vectors(count: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        };
+        (0..count)
+            .map(|_| (0..dim).map(|_| next()).collect())
+            .collect()
+    }
+
+    fn store(metric: HnswMetric, data: &[Vec<f32>]) -> QuantizedStore {
+        let mut store = QuantizedStore::new(data[0].len(), metric);
+        for (id, vector) in data.iter().enumerate() {
+            store.insert(id as u32, vector).unwrap();
+        }
+        store
+    }
+
+    fn exact(metric: HnswMetric, data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<u32> {
+        let store = QuantizedStore::new(query.len(), metric);
+        let query = store.prepare(query).unwrap();
+        let mut all: Vec<(f32, u32)> = data
+            .iter()
+            .enumerate()
+            .map(|(id, v)| {
+                let v = store.prepare(v).unwrap();
+                let d = match metric {
+                    HnswMetric::Cosine => 1.0 - dot(&query, &v),
+                    HnswMetric::L2 => crate::vector::euclidean(&query, &v),
+                    HnswMetric::InnerProduct => -dot(&query, &v),
+                };
+                (d, id as u32)
+            })
+            .collect();
+        all.sort_by(|a, b| a.0.total_cmp(&b.0));
+        all.iter().take(k).map(|&(_, id)| id).collect()
+    }
+
+    fn recall(found: &[(u32, f32)], expected: &[u32]) -> usize {
+        found.iter().filter(|(id, _)| expected.contains(id)).count()
+    }
+
+    #[test]
+    fn test_quantize_round_trip() {
+        let vector = [0.5, -1.0, 0.25, 0.0];
+        let (codes, scale) = quantize(&vector);
+        assert_eq!(codes, vec![64, -127, 32, 0]);
+        for (x, &c) in vector.iter().zip(&codes) {
+            assert!((x - c as f32 * scale).abs() <= scale / 2.0);
+        }
+        assert_eq!(quantize(&[0.0, 0.0]), (vec![0, 0], 0.0));
+    }
+
+    #[test]
+    fn test_sign_bits() {
+        let vector: Vec<f32> = (0..70)
+            .map(|i| if i % 3 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let bits = sign_bits(&vector);
+        assert_eq!(bits.len(), 2);
+        assert_eq!(bits[0].count_ones() + bits[1].count_ones(), 24);
+        assert_eq!(hamming(&bits, &sign_bits(&vec![1.0; 70])), 46);
+    }
+
+    #[test]
+    fn test_int8_search_recall() {
+        let data = vectors(400, 32, 3);
+        let queries = vectors(10, 32, 17);
+        for &metric in [HnswMetric::Cosine, HnswMetric::L2, HnswMetric::InnerProduct].iter() {
+            let store = store(metric, &data);
+            let found: usize = queries
+                .iter()
+                .map(|q| recall(&store.search(q, 10).unwrap(), &exact(metric, &data, q, 10)))
+                .sum();
+            assert!(found >= 90, "{:?} found {}", metric, found);
+        }
+    }
+
+    #[test]
+    fn test_binary_search_rescoring() {
+        let data = vectors(400, 64, 5);
+        let queries = vectors(10, 64, 23);
+        let store = store(HnswMetric::Cosine, &data);
+
+        // With a shortlist as large as the store the result equals the int8 scan
+        for query in &queries {
+            assert_eq!(
+                store.search_binary(query, 10, 40).unwrap(),
+                store.search(query, 10).unwrap()
+            );
+        }
+
+        let found: usize = queries
+            .iter()
+            .map(|q| {
+                recall(
+                    &store.search_binary(q, 10, 10).unwrap(),
+                    &exact(HnswMetric::Cosine, &data, q, 10),
+                )
+            })
+            .sum();
+        assert!(found >= 70, "found {}", found);
+    }
+
+    #[test]
+    fn test_insert_delete() {
+        let data = vectors(5, 8, 9);
+        let mut store = store(HnswMetric::L2, &data);
+        assert_eq!(store.memory_bytes(), 5 * (4 + 8 + 4 + 8));
+
+        assert!(store.delete(1));
+        assert!(!store.delete(1));
+        assert_eq!(store.len(), 4);
+        assert!(store.get(1).is_none());
+        let restored = store.get(4).unwrap();
+        assert!(crate::vector::euclidean(&restored, &data[4]) < 0.05);
+        assert_eq!(store.search(&data[4], 1).unwrap()[0].0, 4);
+
+        // Inserting an existing id replaces its vector
+        store.insert(0, &data[3]).unwrap();
+        assert_eq!(store.len(), 4);
+        assert!(store.insert(9, &[1.0]).is_err());
+    }
+}
@@ -0,0 +1,451 @@
+// SIMD kernels - lane-parallel versions of the hottest loops with scalar fallbacks
+// On wasm32 built with `-C target-feature=+simd128` the lane versions use simd128
+// intrinsics; elsewhere they are fixed-width lane loops left to LLVM, which only
+// vectorizes them at higher optimization levels than the crate's `opt-level = "s"`.
+// The module is only built with the `simd` feature; without it the scorers keep
+// their textdistance and scalar code
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use core::arch::wasm32::*;
+
+const LANES: usize = 4;
+
+/// Whether simd128 instructions are available
+const SIMD128: bool = cfg!(all(target_arch = "wasm32", target_feature = "simd128"));
+
+// ============================================================================
+// Dot product
+// ============================================================================
+
+/// Dot product over the common prefix of both vectors
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    if SIMD128 {
+        dot_lanes(a, b)
+    } else {
+        dot_scalar(a, b)
+    }
+}
+
+pub fn dot_scalar(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Four running sums combined pairwise, so every target rounds the same way
+pub fn dot_lanes(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);
+    let (head_a, head_b) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let tail = dot_scalar(head_a.remainder(), head_b.remainder());
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    let sums = {
+        let mut acc = f32x4_splat(0.0);
+        for (x, y) in head_a.zip(head_b) {
+            let x = f32x4(x[0], x[1], x[2], x[3]);
+            let y = f32x4(y[0], y[1], y[2], y[3]);
+            acc = f32x4_add(acc, f32x4_mul(x, y));
+        }
+        [
+            f32x4_extract_lane::<0>(acc),
+            f32x4_extract_lane::<1>(acc),
+            f32x4_extract_lane::<2>(acc),
+            f32x4_extract_lane::<3>(acc),
+        ]
+    };
+
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+    let sums = {
+        let mut acc = [0.0f32; LANES];
+        for (x, y) in head_a.zip(head_b) {
+            for lane in 0..LANES {
+                acc[lane] += x[lane] * y[lane];
+            }
+        }
+        acc
+    };
+
+    (sums[0] + sums[1]) + (sums[2] + sums[3]) + tail
+}
+
+// ============================================================================
+// Q-gram sets
+// ============================================================================
+
+/// Character bigrams as sorted, unique keys
+///
+/// Repeated bigrams are told apart by their occurrence number in the low 22
+/// bits, so multiset intersections become plain set intersections.
+pub fn bigram_keys(text: &str) -> Vec<u64> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut grams: Vec<u64> = chars
+        .windows(2)
+        .map(|pair| (pair[0] as u64) << 21 | pair[1] as u64)
+        .collect();
+    grams.sort_unstable();
+
+    let mut keys = Vec::with_capacity(grams.len());
+    let mut occurrence = 0;
+    for (i, &gram) in grams.iter().enumerate() {
+        occurrence = if i > 0 && grams[i - 1] == gram {
+            occurrence + 1
+        } else {
+            0
+        };
+        keys.push(gram << 22 | occurrence);
+    }
+    keys
+}
+
+/// Number of keys in both sorted, unique slices
+pub fn intersection_count(a: &[u64], b: &[u64]) -> usize {
+    if SIMD128 {
+        intersection_count_lanes(a, b)
+    } else {
+        intersection_count_scalar(a, b)
+    }
+}
+
+pub fn intersection_count_scalar(a: &[u64], b: &[u64]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] < b[j] {
+            i += 1;
+        } else if a[i] > b[j] {
+            j += 1;
+        } else {
+            count += 1;
+            i += 1;
+            j += 1;
+        }
+    }
+    count
+}
+
+/// Compare blocks of four keys all against all, advancing the block with the smaller maximum
+pub fn intersection_count_lanes(a: &[u64], b: &[u64]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i + LANES <= a.len() && j + LANES <= b.len() {
+        let (x, y) = (&a[i..i + LANES], &b[j..j + LANES]);
+        count += block_matches(x, y);
+        if x[LANES - 1] <= y[LANES - 1] {
+            i += LANES;
+        }
+        if y[LANES - 1] <= x[LANES - 1] {
+            j += LANES;
+        }
+    }
+    count + intersection_count_scalar(&a[i..], &b[j..])
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn block_matches(x: &[u64], y: &[u64]) -> usize {
+    let (x0, x1) = (u64x2(x[0], x[1]), u64x2(x[2], x[3]));
+    let (y0, y1) = (u64x2(y[0], y[1]), u64x2(y[2], y[3]));
+    let (y0s, y1s) = (i64x2_shuffle::<1, 0>(y0, y0), i64x2_shuffle::<1, 0>(y1, y1));
+    let matches = |x: v128| {
+        let low = v128_or(i64x2_eq(x, y0), i64x2_eq(x, y0s));
+        let high = v128_or(i64x2_eq(x, y1), i64x2_eq(x, y1s));
+        i64x2_bitmask(v128_or(low, high)).count_ones() as usize
+    };
+    matches(x0) + matches(x1)
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+fn block_matches(x: &[u64], y: &[u64]) -> usize {
+    let mut matched = [0usize; LANES];
+    for lane in 0..LANES {
+        for &key in y {
+            matched[lane] |= (x[lane] == key) as usize;
+        }
+    }
+    matched.iter().sum()
+}
+
+/// Jaccard similarity of the character bigram multisets, 1 when both have none
+pub fn bigram_jaccard(s1: &str, s2: &str) -> f64 {
+    let (a, b) = (bigram_keys(s1), bigram_keys(s2));
+    let common = intersection_count(&a, &b);
+    let union = a.len() + b.len() - common;
+    if union == 0 {
+        1.0
+    } else {
+        common as f64 / union as f64
+    }
+}
+
+/// Cosine similarity of the character bigram multisets, 1 when both have none
+pub fn bigram_cosine(s1: &str, s2: &str) -> f64 {
+    let (a, b) = (bigram_keys(s1), bigram_keys(s2));
+    match (a.len(), b.len()) {
+        (0, 0) => 1.0,
+        (0, _) | (_, 0) => 0.0,
+        (n1, n2) => intersection_count(&a, &b) as f64 / ((n1 * n2) as f64).sqrt(),
+    }
+}
+
+// ============================================================================
+// Myers block loop
+// ============================================================================
+
+/// Levenshtein distance with the bit-parallel Myers algorithm
+///
+/// The blocks of long strings advance four at a time on every target, as the
+/// independent lanes pay off even without vector instructions. On simd128 builds each step is a handful of `u32x4` operations.
+pub fn myers_distance(a: &str, b: &str) -> u32 {
+    crate::myers::myers_distance(a, b)
+}
+
+/// Levenshtein distance with the Myers block loop run one block at a time
+pub fn myers_distance_scalar(a: &str, b: &str) -> u32 {
+    crate::myers::myers_distance_with(a, b, false)
+}
+
+/// Levenshtein distance with the Myers blocks advanced four at a time
+pub fn myers_distance_lanes(a: &str, b: &str) -> u32 {
+    crate::myers::myers_distance_with(a, b, true)
+}
+
+/// Advance full 32-character blocks of `b` over every character of `a`
+///
+/// Block `k` depends on block `k - 1` only through the horizontal carries of
+/// the same column, so four blocks run in lockstep along an anti-diagonal:
+/// at step `t` lane `j` processes column `t - j`, taking its carry from lane
+/// `j - 1` of the previous step. Carries into the first lane and out of the
+/// last one go through `phc` and `mhc` exactly as in the scalar loop.
+pub(crate) fn myers_blocks(a: &[u16], b: &[u16], phc: &mut [u32], mhc: &mut [u32]) {
+    let n = a.len();
+    let mut alphabet = b.to_vec();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    // Characters of `a` missing from `b` map to the all-zero entry at the end
+    let symbols: Vec<usize> = a
+        .iter()
+        .map(|c| alphabet.binary_search(c).unwrap_or(alphabet.len()))
+        .collect();
+    let mut peq = vec![[0u32; LANES]; alphabet.len() + 1];
+
+    for group in b.chunks(32 * LANES) {
+        let blocks = group.len() / 32;
+        let last = blocks - 1;
+        peq.iter_mut().for_each(|masks| *masks = [0; LANES]);
+        for (k, c) in group.iter().enumerate() {
+            let symbol = alphabet.binary_search(c).unwrap_or(alphabet.len());
+            peq[symbol][k / 32] |= 1 << (k % 32);
+        }
+
+        let mut lanes = BlockLanes {
+            pv: [!0; LANES],
+            mv: [0; LANES],
+            ph_carry: [0; LANES],
+            mh_carry: [0; LANES],
+        };
+        // Steps where every lane is inside the string need no masking
+        let steady = if blocks == LANES && n > last {
+            last..n
+        } else {
+            0..0
+        };
+
+        for t in 0..n + last {
+            let mut eq = [0u32; LANES];
+            let mut pb = [0u32; LANES];
+            let mut mb = [0u32; LANES];
+            if steady.contains(&t) {
+                for lane in 0..LANES {
+                    eq[lane] = peq[symbols[t - lane]][lane];
+                }
+                pb[0] = (phc[t / 32] >> (t % 32)) & 1;
+                mb[0] = (mhc[t / 32] >> (t % 32)) & 1;
+                pb[1..].copy_from_slice(&lanes.ph_carry[..LANES - 1]);
+                mb[1..].copy_from_slice(&lanes.mh_carry[..LANES - 1]);
+                lanes.advance(eq, pb, mb, [!0; LANES]);
+            } else {
+                let mut active = [0u32; LANES];
+                for lane in 0..blocks {
+                    if t < lane || t - lane >= n {
+                        continue;
+                    }
+                    let column = t - lane;
+                    active[lane] = !0;
+                    eq[lane] = peq[symbols[column]][lane];
+                    if lane == 0 {
+                        pb[0] = (phc[column / 32] >> (column % 32)) & 1;
+                        mb[0] = (mhc[column / 32] >> (column % 32)) & 1;
+                    } else {
+                        pb[lane] = lanes.ph_carry[lane - 1];
+                        mb[lane] = lanes.mh_carry[lane - 1];
+                    }
+                }
+                lanes.advance(eq, pb, mb, active);
+            }
+
+            if t >= last && t - last < n {
+                let column = t - last;
+                let bit = 1u32 << (column % 32);
+                let (ph, mh) = (lanes.ph_carry[last], lanes.mh_carry[last]);
+                phc[column / 32] = (phc[column / 32] & !bit) | (ph << (column % 32));
+                mhc[column / 32] = (mhc[column / 32] & !bit) | (mh << (column % 32));
+            }
+        }
+    }
+}
+
+/// Vertical deltas of four Myers blocks and their horizontal carries out
+struct BlockLanes {
+    pv: [u32; LANES],
+    mv: [u32; LANES],
+    ph_carry: [u32; LANES],
+    mh_carry: [u32; LANES],
+}
+
+impl BlockLanes {
+    /// One column step of every lane; lanes outside `active` keep their state
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    #[inline(always)]
+    fn advance(
+        &mut self,
+        eq: [u32; LANES],
+        pb: [u32; LANES],
+        mb: [u32; LANES],
+        active: [u32; LANES],
+    ) {
+        let load = |v: [u32; LANES]| u32x4(v[0], v[1], v[2], v[3]);
+        let store = |v: v128| {
+            [
+                u32x4_extract_lane::<0>(v),
+                u32x4_extract_lane::<1>(v),
+                u32x4_extract_lane::<2>(v),
+                u32x4_extract_lane::<3>(v),
+            ]
+        };
+        let (pv, mv) = (load(self.pv), load(self.mv));
+        let (eq, pb, mb, active) = (load(eq), load(pb), load(mb), load(active));
+        let xv = v128_or(eq, mv);
+        let eq_mb = v128_or(eq, mb);
+        let xh = v128_or(v128_xor(i32x4_add(v128_and(eq_mb, pv), pv), pv), eq_mb);
+        let ph = v128_or(mv, v128_not(v128_or(xh, pv)));
+        let mh = v128_and(pv, xh);
+        self.ph_carry = store(u32x4_shr(ph, 31));
+        self.mh_carry = store(u32x4_shr(mh, 31));
+
+        let ph = v128_or(i32x4_shl(ph, 1), pb);
+        let mh = v128_or(i32x4_shl(mh, 1), mb);
+        let pv_next = v128_or(mh, v128_not(v128_or(xv, ph)));
+        self.pv = store(v128_bitselect(pv_next, pv, active));
+        self.mv = store(v128_bitselect(v128_and(ph, xv), mv, active));
+    }
+
+    /// One column step of every lane; lanes outside `active` keep their state
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+    #[inline(always)]
+    fn advance(
+        &mut self,
+        eq: [u32; LANES],
+        pb: [u32; LANES],
+        mb: [u32; LANES],
+        active: [u32; LANES],
+    ) {
+        for lane in 0..LANES {
+            let (pv, mv) = (self.pv[lane], self.mv[lane]);
+            let xv = eq[lane] | mv;
+            let xh = ((((eq[lane] | mb[lane]) & pv).wrapping_add(pv)) ^ pv) | eq[lane] | mb[lane];
+            let ph = mv | !(xh | pv);
+            let mh = pv & xh;
+            self.ph_carry[lane] = ph >> 31;
+            self.mh_carry[lane] = mh >> 31;
+
+            let ph = (ph << 1) | pb[lane];
+            let mh = (mh << 1) | mb[lane];
+            let keep = !active[lane];
+            self.pv[lane] = ((mh | !(xv | ph)) & active[lane]) | (pv & keep);
+            self.mv[lane] = (ph & xv & active[lane]) | (mv & keep);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(seed: u64, len: usize, alphabet: &[char]) -> String {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                alphabet[(state % alphabet.len() as u64) as usize]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dot_lanes() {
+        let a: Vec<f32> = (0..11).map(|i| i as f32).collect();
+        let b: Vec<f32> = (0..11).map(|i| (i % 3) as f32).collect();
+        assert_eq!(dot_lanes(&a, &b), dot_scalar(&a, &b));
+        assert_eq!(dot_lanes(&a, &b[..5]), dot_scalar(&a, &b[..5]));
+        assert_eq!(dot_lanes(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_intersection_count() {
+        let a: Vec<u64> = (0..40).map(|i| i * 3).collect();
+        let b: Vec<u64> = (0..50).map(|i| i * 2).collect();
+        assert_eq!(intersection_count_scalar(&a, &b), 17);
+        assert_eq!(intersection_count_lanes(&a, &b), 17);
+        assert_eq!(intersection_count_lanes(&b, &a), 17);
+        assert_eq!(intersection_count_lanes(&a[..3], &a), 3);
+    }
+
+    #[test]
+    fn test_bigrams_match_textdistance() {
+        use textdistance::{Algorithm, Cosine, Jaccard};
+
+        let words = [
+            "",
+            "a",
+            "aa",
+            "aaaa",
+            "abab",
+            "nelson",
+            "neilsen",
+            "night",
+            "nacht",
+            "mississippi",
+            "missisippi",
+            "日本語",
+            "日本",
+        ];
+        for s1 in words.iter() {
+            for s2 in words.iter() {
+                let jaccard = Jaccard::default().for_bigrams(s1, s2).nsim();
+                let cosine = Cosine::default().for_bigrams(s1, s2).nsim();
+                assert!(
+                    (bigram_jaccard(s1, s2) - jaccard).abs() < 1e-12,
+                    "{} {}",
+                    s1,
+                    s2
+                );
+                assert!(
+                    (bigram_cosine(s1, s2) - cosine).abs() < 1e-12,
+                    "{} {}",
+                    s1,
+                    s2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_myers_lanes_match_scalar() {
+        let alphabet = ['a', 'b', 'c', 'd', 'é', '語'];
+        for seed in 1..40u64 {
+            let a = text(seed, 20 + (seed as usize * 37) % 300, &alphabet);
+            let b = text(seed * 7919, 30 + (seed as usize * 53) % 400, &alphabet);
+            let expected = textdistance::str::levenshtein(&a, &b) as u32;
+            assert_eq!(myers_distance_scalar(&a, &b), expected, "seed {}", seed);
+            assert_eq!(myers_distance_lanes(&a, &b), expected, "seed {}", seed);
+        }
+    }
+}
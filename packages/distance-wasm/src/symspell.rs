@@ -0,0 +1,531 @@
+// SymSpell - spelling suggestions with the symmetric delete algorithm
+// Dictionary words are indexed by every string reachable from their prefix
+// with up to `max_edit_distance` deletions, so a lookup only generates deletes
+// of the input instead of all edits
+
+use std::collections::{HashMap, HashSet};
+
+/// Which suggestions a lookup returns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verbosity {
+    /// The single most frequent suggestion with the smallest distance
+    Top,
+    /// Every suggestion with the smallest distance
+    Closest,
+    /// Every suggestion within the maximum edit distance
+    All,
+}
+
+impl Verbosity {
+    /// Parse a verbosity name, rejecting unknown ones
+    pub fn parse(name: &str) -> Result<Verbosity, String> {
+        match name.trim().to_lowercase().as_str() {
+            "top" => Ok(Verbosity::Top),
+            "closest" => Ok(Verbosity::Closest),
+            "all" => Ok(Verbosity::All),
+            _ => Err(format!("unsupported verbosity: {}", name)),
+        }
+    }
+}
+
+/// A dictionary word, its Damerau-Levenshtein distance to the input and its frequency
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    pub term: String,
+    pub distance: usize,
+    pub count: u64,
+}
+
+/// Words of a segmented text, as typed and as corrected
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Segmentation {
+    pub segments: Vec<String>,
+    pub corrected: Vec<String>,
+    pub distance_sum: usize,
+    pub log_probability_sum: f64,
+}
+
+pub struct SymSpell {
+    max_edit_distance: usize,
+    prefix_length: usize,
+    words: Vec<(String, u64)>,
+    ids: HashMap<String, u32>,
+    /// Delete variants of word prefixes to the ids of the words producing them
+    deletes: HashMap<String, Vec<u32>>,
+    /// Longest word in characters
+    max_length: usize,
+    /// Sum of the frequencies of every word
+    total_count: u64,
+}
+
+impl SymSpell {
+    pub fn new(max_edit_distance: usize, prefix_length: usize) -> SymSpell {
+        SymSpell {
+            max_edit_distance,
+            prefix_length: prefix_length.max(max_edit_distance + 1),
+            words: Vec::new(),
+            ids: HashMap::new(),
+            deletes: HashMap::new(),
+            max_length: 0,
+            total_count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Frequency of a dictionary word
+    pub fn count(&self, word: &str) -> Option<u64> {
+        self.ids.get(word).map(|&id| self.words[id as usize].1)
+    }
+
+    /// Add a word or increase the frequency of an existing one
+    pub fn add_word(&mut self, word: &str, count: u64) {
+        self.total_count += count;
+        if let Some(&id) = self.ids.get(word) {
+            self.words[id as usize].1 += count;
+            return;
+        }
+
+        let id = self.words.len() as u32;
+        self.words.push((word.to_string(), count));
+        self.ids.insert(word.to_string(), id);
+
+        let chars: Vec<char> = word.chars().collect();
+        self.max_length = self.max_length.max(chars.len());
+        let prefix = &chars[..chars.len().min(self.prefix_length)];
+        for delete in deletes(prefix, self.max_edit_distance) {
+            self.deletes.entry(delete).or_default().push(id);
+        }
+    }
+
+    /// Load a frequency dictionary with one `word count` pair per line
+    ///
+    /// Lines without a valid count are skipped. Returns the number of lines loaded.
+    pub fn load_dictionary(&mut self, text: &str) -> usize {
+        let mut loaded = 0;
+        for line in text.lines() {
+            let mut fields = line.split_whitespace();
+            if let (Some(word), Some(Ok(count))) = (fields.next(), fields.next().map(str::parse)) {
+                self.add_word(word, count);
+                loaded += 1;
+            }
+        }
+        loaded
+    }
+
+    /// Suggestions for `input` within `max_edit_distance` (at most the index's)
+    ///
+    /// Sorted by distance, then descending frequency, then term.
+    pub fn lookup(
+        &self,
+        input: &str,
+        verbosity: Verbosity,
+        max_edit_distance: usize,
+    ) -> Vec<Suggestion> {
+        let max_edit_distance = max_edit_distance.min(self.max_edit_distance);
+        let input_chars: Vec<char> = input.chars().collect();
+        let input_len = input_chars.len();
+        let mut suggestions: Vec<Suggestion> = Vec::new();
+
+        if input_len > self.max_length + max_edit_distance {
+            return suggestions;
+        }
+
+        if let Some(count) = self.count(input) {
+            suggestions.push(Suggestion {
+                term: input.to_string(),
+                distance: 0,
+                count,
+            });
+            if verbosity != Verbosity::All {
+                return suggestions;
+            }
+        }
+        if max_edit_distance == 0 {
+            return suggestions;
+        }
+
+        // Shrinks to the best distance found unless every suggestion is wanted
+        let mut max_distance = max_edit_distance;
+        let mut considered_deletes: HashSet<String> = HashSet::new();
+        let mut considered_words: HashSet<u32> = HashSet::new();
+        if let Some(&id) = self.ids.get(input) {
+            considered_words.insert(id);
+        }
+
+        let input_prefix: String = input_chars[..input_len.min(self.prefix_length)]
+            .iter()
+            .collect();
+        let input_prefix_len = input_prefix.chars().count();
+        let mut candidates = vec![input_prefix];
+        let mut next = 0;
+
+        // Candidates are generated in order of increasing number of deletions
+        while next < candidates.len() {
+            let candidate: Vec<char> = candidates[next].chars().collect();
+            let deletions = input_prefix_len - candidate.len();
+            if deletions > max_distance {
+                break;
+            }
+
+            if let Some(ids) = self.deletes.get(&candidates[next]) {
+                for &id in ids {
+                    if !considered_words.insert(id) {
+                        continue;
+                    }
+                    let (term, count) = &self.words[id as usize];
+                    let term_len = term.chars().count();
+                    if term_len.max(input_len) - term_len.min(input_len) > max_distance {
+                        continue;
+                    }
+
+                    let distance = textdistance::str::damerau_levenshtein(input, term);
+                    if distance > max_distance {
+                        continue;
+                    }
+                    let suggestion = Suggestion {
+                        term: term.clone(),
+                        distance,
+                        count: *count,
+                    };
+
+                    match verbosity {
+                        Verbosity::Top => {
+                            let better = suggestions.first().is_none_or(|best| {
+                                distance < best.distance
+                                    || (distance == best.distance && *count > best.count)
+                            });
+                            if better {
+                                suggestions = vec![suggestion];
+                            }
+                            max_distance = distance;
+                        }
+                        Verbosity::Closest => {
+                            if distance < max_distance {
+                                suggestions.clear();
+                            }
+                            suggestions.push(suggestion);
+                            max_distance = distance;
+                        }
+                        Verbosity::All => suggestions.push(suggestion),
+                    }
+                }
+            }
+
+            if deletions < max_edit_distance {
+                if verbosity != Verbosity::All && deletions >= max_distance {
+                    next += 1;
+                    continue;
+                }
+                for i in 0..candidate.len() {
+                    let delete: String = candidate[..i].iter().chain(&candidate[i + 1..]).collect();
+                    if considered_deletes.insert(delete.clone()) {
+                        candidates.push(delete);
+                    }
+                }
+            }
+            next += 1;
+        }
+
+        suggestions.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then(b.count.cmp(&a.count))
+                .then(a.term.cmp(&b.term))
+        });
+        suggestions
+    }
+
+    /// Log10 probability of a word, with unknown words penalised by their length
+    fn log_probability(&self, word: &str, count: Option<u64>) -> f64 {
+        let total = self.total_count.max(1) as f64;
+        match count {
+            Some(count) if count > 0 => (count as f64 / total).log10(),
+            _ => 1.0 - total.log10() - word.chars().count() as f64,
+        }
+    }
+
+    /// Split text written without spaces into its most probable words
+    ///
+    /// Existing whitespace is kept as a word boundary. With `max_edit_distance`
+    /// above zero each segment may also be corrected to a dictionary word;
+    /// segmentations then minimise the total edit distance before maximising
+    /// the probability, an unknown segment costing its length in edits.
+    pub fn word_segmentation(&self, input: &str, max_edit_distance: usize) -> Segmentation {
+        let max_edit_distance = max_edit_distance.min(self.max_edit_distance);
+        let mut result = Segmentation::default();
+        for chunk in input.split_whitespace() {
+            let part = self.segment_chunk(chunk, max_edit_distance);
+            for (segment, corrected) in part.segments.iter().zip(&part.corrected) {
+                result.segments.push(segment.clone());
+                result.corrected.push(corrected.clone());
+            }
+            result.distance_sum += part.distance_sum;
+            result.log_probability_sum += part.log_probability_sum;
+        }
+        result
+    }
+
+    fn segment_chunk(&self, chunk: &str, max_edit_distance: usize) -> Segmentation {
+        let chars: Vec<char> = chunk.chars().collect();
+        let n = chars.len();
+        let max_word_length = (self.max_length + max_edit_distance).max(1);
+
+        // best[j] is the best segmentation of the first j characters as
+        // (distance sum, log probability sum, start of its last word, correction)
+        let mut best: Vec<Option<(usize, f64, usize, String)>> = vec![None; n + 1];
+        best[0] = Some((0, 0.0, 0, String::new()));
+
+        for i in 0..n {
+            let (distance, log_probability) = match &best[i] {
+                Some((d, p, _, _)) => (*d, *p),
+                None => continue,
+            };
+            for j in i + 1..=n.min(i + max_word_length) {
+                let part: String = chars[i..j].iter().collect();
+                let (word, edits, count) = match self.count(&part) {
+                    Some(count) => (part.clone(), 0, Some(count)),
+                    None if max_edit_distance > 0 => {
+                        match self.lookup(&part, Verbosity::Top, max_edit_distance).pop() {
+                            Some(top) => (top.term, top.distance, Some(top.count)),
+                            None => (part.clone(), j - i, None),
+                        }
+                    }
+                    None => (part.clone(), 0, None),
+                };
+
+                let candidate = (
+                    distance + edits,
+                    log_probability + self.log_probability(&word, count),
+                );
+                let better = best[j].as_ref().is_none_or(|(d, p, _, _)| {
+                    candidate.0 < *d || (candidate.0 == *d && candidate.1 > *p)
+                });
+                if better {
+                    best[j] = Some((candidate.0, candidate.1, i, word));
+                }
+            }
+        }
+
+        let mut result = Segmentation::default();
+        if let Some((distance, log_probability, _, _)) = &best[n] {
+            result.distance_sum = *distance;
+            result.log_probability_sum = *log_probability;
+        }
+        let mut end = n;
+        while end > 0 {
+            let (_, _, start, word) = best[end].as_ref().expect("every position is reachable");
+            result.segments.push(chars[*start..end].iter().collect());
+            result.corrected.push(word.clone());
+            end = *start;
+        }
+        result.segments.reverse();
+        result.corrected.reverse();
+        result
+    }
+}
+
+/// The string itself and every string reachable with up to `max_deletes` deletions
+fn deletes(chars: &[char], max_deletes: usize) -> HashSet<String> {
+    let mut result: HashSet<String> = HashSet::new();
+    result.insert(chars.iter().collect());
+
+    let mut frontier: Vec<Vec<char>> = vec![chars.to_vec()];
+    for _ in 0..max_deletes {
+        let mut next = Vec::new();
+        for word in &frontier {
+            for i in 0..word.len() {
+                let delete: Vec<char> = word[..i].iter().chain(&word[i + 1..]).copied().collect();
+                if result.insert(delete.iter().collect()) {
+                    next.push(delete);
+                }
+            }
+        }
+        frontier = next;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICTIONARY: &str = "the 23135851162
+of 13151942776
+and 12997637966
+steam 2181
+steams 89
+stem 1047
+sea 4711
+seam 316
+team 5210
+members 6821
+member 7201
+hello 7000
+help 9000
+";
+
+    fn symspell() -> SymSpell {
+        let mut symspell = SymSpell::new(2, 7);
+        assert_eq!(symspell.load_dictionary(DICTIONARY), 13);
+        symspell
+    }
+
+    fn terms(suggestions: &[Suggestion]) -> Vec<(&str, usize)> {
+        suggestions
+            .iter()
+            .map(|s| (s.term.as_str(), s.distance))
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let symspell = symspell();
+        let suggestions = symspell.lookup("steam", Verbosity::Top, 2);
+        assert_eq!(terms(&suggestions), vec![("steam", 0)]);
+        assert_eq!(suggestions[0].count, 2181);
+    }
+
+    #[test]
+    fn test_verbosity() {
+        let symspell = symspell();
+        assert_eq!(
+            terms(&symspell.lookup("steem", Verbosity::Top, 2)),
+            vec![("steam", 1)]
+        );
+        assert_eq!(
+            terms(&symspell.lookup("steem", Verbosity::Closest, 2)),
+            vec![("steam", 1), ("stem", 1)]
+        );
+        assert_eq!(
+            terms(&symspell.lookup("steem", Verbosity::All, 2)),
+            vec![
+                ("steam", 1),
+                ("stem", 1),
+                ("team", 2),
+                ("seam", 2),
+                ("steams", 2)
+            ]
+        );
+        assert_eq!(Verbosity::parse("Closest"), Ok(Verbosity::Closest));
+        assert_eq!(
+            Verbosity::parse("best"),
+            Err("unsupported verbosity: best".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transposition_and_prefix() {
+        let symspell = symspell();
+        assert_eq!(
+            terms(&symspell.lookup("teh", Verbosity::Top, 2)),
+            vec![("the", 1)]
+        );
+        // Words longer than the prefix are still verified on their full length
+        assert_eq!(
+            terms(&symspell.lookup("membres", Verbosity::Top, 2)),
+            vec![("members", 1)]
+        );
+        assert_eq!(
+            terms(&symspell.lookup("memebrs", Verbosity::Top, 1)),
+            vec![("members", 1)]
+        );
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut symspell = symspell();
+        // Longer than the prefix of 7, so only their prefixes are indexed
+        for word in [
+            "knowledge",
+            "membership",
+            "information",
+            "informational",
+            "international",
+        ] {
+            symspell.add_word(word, 100);
+        }
+        let inputs = [
+            "stea",
+            "hel",
+            "xyz",
+            "member",
+            "tha",
+            "s",
+            "seams",
+            // Edits inside the prefix
+            "kmowledge",
+            "infromation",
+            "nformation",
+            "memebrship",
+            // Edits beyond the prefix
+            "knowledgg",
+            "informatoin",
+            "internationl",
+            "informationals",
+            "membershi",
+            // Edits at the prefix boundary or on both sides of it
+            "informtion",
+            "infromatoin",
+            "interantinoal",
+            "knwoledeg",
+        ];
+        for input in inputs.iter() {
+            for max in 0..=2 {
+                let mut expected: Vec<(&str, usize)> = symspell
+                    .words
+                    .iter()
+                    .map(|(w, _)| (w.as_str(), textdistance::str::damerau_levenshtein(input, w)))
+                    .filter(|&(_, d)| d <= max)
+                    .collect();
+                expected.sort_by_key(|&(w, d)| (d, std::cmp::Reverse(symspell.count(w)), w));
+                let found = symspell.lookup(input, Verbosity::All, max);
+                assert_eq!(terms(&found), expected, "{} {}", input, max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_word_accumulates() {
+        let mut symspell = SymSpell::new(1, 7);
+        symspell.add_word("word", 2);
+        symspell.add_word("word", 3);
+        assert_eq!(symspell.len(), 1);
+        assert_eq!(symspell.count("word"), Some(5));
+        assert!(symspell.lookup("wordss", Verbosity::All, 1).is_empty());
+    }
+
+    #[test]
+    fn test_word_segmentation() {
+        let mut symspell = SymSpell::new(2, 7);
+        symspell.load_dictionary(
+            "new 1000\nyork 300\nhotels 200\nhot 400\nels 5\nnewyork 1\nin 5000\nthe 9000\nstadt 50\nhaus 80\n",
+        );
+
+        let result = symspell.word_segmentation("newyorkhotels", 0);
+        assert_eq!(result.segments, vec!["new", "york", "hotels"]);
+        assert_eq!(result.corrected, result.segments);
+        assert_eq!(result.distance_sum, 0);
+
+        // Existing spaces are kept and unknown words stay whole
+        let result = symspell.word_segmentation("stadthaus inthe xq", 0);
+        assert_eq!(result.segments, vec!["stadt", "haus", "in", "the", "xq"]);
+    }
+
+    #[test]
+    fn test_word_segmentation_with_correction() {
+        let mut symspell = SymSpell::new(2, 7);
+        symspell.load_dictionary("new 1000\nyork 300\nhotels 200\nin 5000\n");
+
+        let result = symspell.word_segmentation("newyrokhotles", 2);
+        assert_eq!(result.segments, vec!["new", "yrok", "hotles"]);
+        assert_eq!(result.corrected, vec!["new", "york", "hotels"]);
+        assert_eq!(result.distance_sum, 2);
+
+        // Without correction the misspelled words stay as typed
+        let plain = symspell.word_segmentation("newyrokhotles", 0);
+        assert_eq!(plain.corrected, plain.segments);
+    }
+}
@@ -0,0 +1,419 @@
+// TF-IDF model - corpus-aware term weighting for cosine similarity
+// Fitted once on a corpus so that features shared by many documents ("the",
+// "inc") weigh less than the distinctive ones
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hybrid::{tokenize, Idf};
+use crate::preprocess::Preprocessor;
+
+/// Features extracted from a text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Analyzer {
+    /// Word n-grams, words being runs of alphanumeric characters
+    Word,
+    /// Character n-grams with whitespace runs collapsed to a single space
+    Char,
+}
+
+impl Analyzer {
+    /// Parse an analyzer name, rejecting unknown ones
+    pub fn parse(name: &str) -> Result<Analyzer, String> {
+        match name.trim().to_lowercase().as_str() {
+            "word" | "words" => Ok(Analyzer::Word),
+            "char" | "chars" | "char_ngram" | "char-ngram" => Ok(Analyzer::Char),
+            _ => Err(format!("unsupported analyzer: {}", name)),
+        }
+    }
+
+    /// Default n-gram length: single words or character trigrams
+    pub fn default_ngram(self) -> usize {
+        match self {
+            Analyzer::Word => 1,
+            Analyzer::Char => 3,
+        }
+    }
+
+    /// N-gram features of a text for every length in `ngram_range`, with repetitions
+    pub fn features(self, text: &str, ngram_range: (usize, usize)) -> Vec<String> {
+        let (min_n, max_n) = ngram_range;
+        let min_n = min_n.max(1);
+        let mut features = Vec::new();
+
+        match self {
+            Analyzer::Word => {
+                let words = tokenize(text);
+                for n in min_n..=max_n {
+                    features.extend(words.windows(n).map(|gram| gram.join(" ")));
+                }
+            }
+            Analyzer::Char => {
+                let chars: Vec<char> = text
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .chars()
+                    .collect();
+                for n in min_n..=max_n {
+                    features.extend(chars.windows(n).map(|gram| gram.iter().collect::<String>()));
+                }
+            }
+        }
+
+        features
+    }
+}
+
+/// Feature extraction and weighting options
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TfIdfOptions {
+    pub analyzer: Analyzer,
+    /// Inclusive range of n-gram lengths
+    pub ngram_range: (usize, usize),
+    /// Replace term frequency `tf` with `1 + ln(tf)`
+    pub sublinear_tf: bool,
+    /// Add one to document frequencies as if an extra document contained every feature
+    pub smooth_idf: bool,
+    /// Preprocessing options applied to the corpus and every input, stored in
+    /// the canonical form of `Preprocessor::spec` once fitted
+    pub preprocessor: String,
+}
+
+impl Default for TfIdfOptions {
+    fn default() -> TfIdfOptions {
+        TfIdfOptions {
+            analyzer: Analyzer::Word,
+            ngram_range: (1, 1),
+            sublinear_tf: false,
+            smooth_idf: true,
+            preprocessor: String::new(),
+        }
+    }
+}
+
+/// TF-IDF weights fitted on a corpus
+///
+/// Features are indexed in lexicographic order; features unseen during
+/// fitting are ignored when vectorizing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TfIdf {
+    options: TfIdfOptions,
+    vocabulary: HashMap<String, u32>,
+    idf: Vec<f64>,
+    /// `options.preprocessor` parsed once
+    #[serde(skip)]
+    preprocessor: Preprocessor,
+}
+
+impl TfIdf {
+    /// Fit on a corpus, rejecting unknown preprocessing options
+    pub fn fit<S: AsRef<str>>(corpus: &[S], mut options: TfIdfOptions) -> Result<TfIdf, String> {
+        let preprocessor = Preprocessor::try_parse(&options.preprocessor)?;
+        options.preprocessor = preprocessor.spec();
+        let frequencies = Idf::fit_features(corpus, |document| {
+            extract(document, &preprocessor, &options)
+        });
+
+        let features = frequencies.features();
+        let mut vocabulary = HashMap::with_capacity(features.len());
+        let mut idf = Vec::with_capacity(features.len());
+        for (index, feature) in features.into_iter().enumerate() {
+            idf.push(frequencies.weight(feature, options.smooth_idf));
+            vocabulary.insert(feature.to_string(), index as u32);
+        }
+
+        Ok(TfIdf {
+            options,
+            vocabulary,
+            idf,
+            preprocessor,
+        })
+    }
+
+    /// Number of distinct features seen during fitting
+    pub fn vocabulary_size(&self) -> usize {
+        self.idf.len()
+    }
+
+    /// Features in index order
+    pub fn feature_names(&self) -> Vec<String> {
+        let mut names = vec![String::new(); self.idf.len()];
+        for (feature, &index) in &self.vocabulary {
+            names[index as usize] = feature.clone();
+        }
+        names
+    }
+
+    /// Unit length sparse TF-IDF vector as `(index, weight)` pairs sorted by index
+    pub fn vectorize(&self, text: &str) -> Vec<(u32, f64)> {
+        self.weights(&extract(text, &self.preprocessor, &self.options))
+    }
+
+    fn weights(&self, features: &[String]) -> Vec<(u32, f64)> {
+        let mut counts: HashMap<u32, usize> = HashMap::new();
+        for feature in features {
+            if let Some(&index) = self.vocabulary.get(feature) {
+                *counts.entry(index).or_insert(0) += 1;
+            }
+        }
+
+        let mut vector: Vec<(u32, f64)> = counts
+            .into_iter()
+            .map(|(index, tf)| {
+                let tf = if self.options.sublinear_tf {
+                    1.0 + (tf as f64).ln()
+                } else {
+                    tf as f64
+                };
+                (index, tf * self.idf[index as usize])
+            })
+            .collect();
+        vector.sort_unstable_by_key(|&(index, _)| index);
+
+        let norm = vector.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            for (_, w) in vector.iter_mut() {
+                *w /= norm;
+            }
+        }
+        vector
+    }
+
+    /// Cosine similarity of the TF-IDF vectors of two texts
+    pub fn similarity(&self, s1: &str, s2: &str) -> f64 {
+        let f1 = extract(s1, &self.preprocessor, &self.options);
+        let f2 = extract(s2, &self.preprocessor, &self.options);
+        if f1.is_empty() && f2.is_empty() {
+            return 1.0;
+        }
+
+        dot(&self.weights(&f1), &self.weights(&f2)).min(1.0)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("TF-IDF model is always serializable")
+    }
+
+    /// Deserialize a model, checking that every feature has its own idf weight
+    pub fn from_json(json: &str) -> Result<TfIdf, String> {
+        let mut model: TfIdf =
+            serde_json::from_str(json).map_err(|e| format!("invalid TF-IDF model: {}", e))?;
+        if model.vocabulary.len() != model.idf.len() {
+            return Err(format!(
+                "invalid TF-IDF model: {} features but {} idf weights",
+                model.vocabulary.len(),
+                model.idf.len()
+            ));
+        }
+        let mut seen = vec![false; model.idf.len()];
+        for (feature, &index) in &model.vocabulary {
+            match seen.get_mut(index as usize) {
+                Some(seen) if !*seen => *seen = true,
+                _ => {
+                    return Err(format!(
+                        "invalid TF-IDF model: feature {:?} has index {} out of range or repeated",
+                        feature, index
+                    ))
+                }
+            }
+        }
+
+        model.preprocessor = Preprocessor::try_parse(&model.options.preprocessor)
+            .map_err(|e| format!("invalid TF-IDF model: {}", e))?;
+        Ok(model)
+    }
+}
+
+/// Dot product of two sparse vectors sorted by index
+fn dot(a: &[(u32, f64)], b: &[(u32, f64)]) -> f64 {
+    let (mut i, mut j, mut sum) = (0, 0, 0.0);
+    while i < a.len() && j < b.len() {
+        match a[i].0.cmp(&b[j].0) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                sum += a[i].1 * b[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    sum
+}
+
+/// Preprocess a text and extract its features under the given options
+fn extract(text: &str, preprocessor: &Preprocessor, options: &TfIdfOptions) -> Vec<String> {
+    let text = preprocessor.apply(text);
+    options.analyzer.features(&text, options.ngram_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: [&str; 4] = [
+        "Acme Inc",
+        "Globex Inc",
+        "Initech Inc",
+        "Acme Anvils and Rockets",
+    ];
+
+    #[test]
+    fn test_vocabulary() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        assert_eq!(
+            model.feature_names(),
+            vec!["Acme", "Anvils", "Globex", "Inc", "Initech", "Rockets", "and"]
+        );
+    }
+
+    #[test]
+    fn test_idf_weighting() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+
+        // Sharing the rare word counts for more than sharing the common one
+        let rare = model.similarity("Acme Inc", "Acme Corp");
+        let common = model.similarity("Acme Inc", "Globex Inc");
+        assert!(rare > common, "{} <= {}", rare, common);
+
+        assert!((model.similarity("Acme Inc", "Inc Acme") - 1.0).abs() < 1e-12);
+        assert_eq!(model.similarity("", ""), 1.0);
+        assert_eq!(model.similarity("Acme", "Unseen"), 0.0);
+    }
+
+    #[test]
+    fn test_vectorize() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        let vector = model.vectorize("Inc Acme Acme unseen");
+
+        let indices: Vec<u32> = vector.iter().map(|&(i, _)| i).collect();
+        assert_eq!(indices, vec![0, 3]);
+        let norm: f64 = vector.iter().map(|(_, w)| w * w).sum();
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_idf_values() {
+        // Matches scikit-learn's TfidfVectorizer: "Inc" in 3 of 4 documents
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        assert!((model.idf[3] - ((5.0f64 / 4.0).ln() + 1.0)).abs() < 1e-12);
+
+        let options = TfIdfOptions {
+            smooth_idf: false,
+            ..TfIdfOptions::default()
+        };
+        let model = TfIdf::fit(&CORPUS, options).unwrap();
+        assert!((model.idf[3] - ((4.0f64 / 3.0).ln() + 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sublinear_tf() {
+        let options = TfIdfOptions {
+            sublinear_tf: true,
+            ..TfIdfOptions::default()
+        };
+        let sublinear = TfIdf::fit(&CORPUS, options).unwrap();
+        let linear = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+
+        let text = "Acme Acme Acme Acme Inc";
+        let weight = |model: &TfIdf| model.vectorize(text)[1].1;
+        assert!(weight(&sublinear) > weight(&linear));
+    }
+
+    #[test]
+    fn test_char_ngrams() {
+        let options = TfIdfOptions {
+            analyzer: Analyzer::Char,
+            ngram_range: (2, 3),
+            preprocessor: "case_fold".to_string(),
+            ..TfIdfOptions::default()
+        };
+        let model = TfIdf::fit(&CORPUS, options).unwrap();
+
+        assert!(model.feature_names().contains(&"ac".to_string()));
+        assert!(model.feature_names().contains(&"acm".to_string()));
+        assert!(model.similarity("ACME inc", "acmee inc") > 0.8);
+    }
+
+    #[test]
+    fn test_parse_analyzer() {
+        assert_eq!(Analyzer::parse("word"), Ok(Analyzer::Word));
+        assert_eq!(Analyzer::parse("Char"), Ok(Analyzer::Char));
+        assert_eq!(
+            Analyzer::parse("char_wb"),
+            Err("unsupported analyzer: char_wb".to_string())
+        );
+        let corpus = vec!["acme inc".to_string()];
+        let analyzer = Some("chars_wb".to_string());
+        assert!(
+            crate::TfIdfModel::try_new(corpus, analyzer, None, None, None, None, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        let restored = TfIdf::from_json(&model.to_json()).unwrap();
+        assert_eq!(restored, model);
+        assert!(TfIdf::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_json_preprocessor_is_restored() {
+        let options = TfIdfOptions {
+            preprocessor: "case_fold".to_string(),
+            ..TfIdfOptions::default()
+        };
+        let model = TfIdf::fit(&CORPUS, options).unwrap();
+        let restored = TfIdf::from_json(&model.to_json()).unwrap();
+        assert_eq!(restored, model);
+        assert!((restored.similarity("ACME INC", "acme inc") - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_preprocessor_is_validated_and_normalized() {
+        let options = |preprocessor: &str| TfIdfOptions {
+            preprocessor: preprocessor.to_string(),
+            ..TfIdfOptions::default()
+        };
+        let error = TfIdf::fit(&CORPUS, options("case_fold,lowercase")).unwrap_err();
+        assert_eq!(error, "unknown preprocessing option \"lowercase\"");
+
+        // Aliases are stored canonically, so every fitted model reloads
+        let model = TfIdf::fit(&CORPUS, options("NFKC | case-fold")).unwrap();
+        assert_eq!(model.options.preprocessor, "case_fold,nfkc");
+        assert_eq!(TfIdf::from_json(&model.to_json()).unwrap(), model);
+    }
+
+    #[test]
+    fn test_json_rejects_bad_indices() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        let corrupt = |edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut json: serde_json::Value = serde_json::from_str(&model.to_json()).unwrap();
+            edit(&mut json);
+            TfIdf::from_json(&json.to_string())
+        };
+
+        // Out of range indices would panic when vectorizing
+        let error = corrupt(&|json| json["vocabulary"]["Acme"] = 99.into()).unwrap_err();
+        assert!(error.contains("out of range"), "{}", error);
+        let error = corrupt(&|json| json["vocabulary"]["Acme"] = 3.into()).unwrap_err();
+        assert!(error.contains("repeated"), "{}", error);
+        let error = corrupt(&|json| {
+            json["idf"].as_array_mut().unwrap().pop();
+        })
+        .unwrap_err();
+        assert!(error.contains("7 features but 6 idf weights"), "{}", error);
+    }
+
+    #[test]
+    fn test_idf_matches_hybrid() {
+        let model = TfIdf::fit(&CORPUS, TfIdfOptions::default()).unwrap();
+        let idf = Idf::fit(&CORPUS);
+        for (index, feature) in model.feature_names().iter().enumerate() {
+            assert_eq!(model.idf[index], idf.idf(feature));
+        }
+    }
+}
@@ -0,0 +1,470 @@
+// Transliteration to ASCII for cross-script comparison
+// Covers Latin-extended, Cyrillic, Greek, Arabic, Hebrew and Japanese kana so
+// that "Москва" and "Moskva" become comparable; other scripts pass through
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::kana::{is_kana, to_hiragana};
+
+/// Latin letters that do not decompose into an ASCII base letter
+fn latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'ł' => "l",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ı' => "i",
+        'ŋ' => "ng",
+        'ħ' => "h",
+        'ſ' => "s",
+        'ƒ' => "f",
+        'ə' | 'ǝ' => "e",
+        _ => return None,
+    })
+}
+
+/// Russian, Ukrainian, Belarusian and South Slavic Cyrillic letters
+fn cyrillic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        'є' => "ye",
+        'і' => "i",
+        'ї' => "yi",
+        'ґ' => "g",
+        'ў' => "u",
+        'ђ' => "dj",
+        'ј' => "j",
+        'љ' => "lj",
+        'њ' => "nj",
+        'ћ' => "c",
+        'џ' => "dz",
+        'ѓ' => "gj",
+        'ќ' => "kj",
+        'ѕ' => "dz",
+        _ => return None,
+    })
+}
+
+/// Greek letters, after accents have been removed by decomposition
+fn greek(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        _ => return None,
+    })
+}
+
+/// Arabic and Persian letters; short vowel marks are dropped as combining marks
+fn arabic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ا' | 'آ' | 'أ' | 'ى' => "a",
+        'إ' => "i",
+        'ء' | 'ؤ' | 'ئ' | 'ع' => "'",
+        'ب' => "b",
+        'ت' | 'ط' => "t",
+        'ث' => "th",
+        'ج' => "j",
+        'ح' | 'ه' | 'ة' => "h",
+        'خ' => "kh",
+        'د' | 'ض' => "d",
+        'ذ' => "dh",
+        'ر' => "r",
+        'ز' | 'ظ' => "z",
+        'س' | 'ص' => "s",
+        'ش' => "sh",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ك' | 'ک' => "k",
+        'ل' => "l",
+        'م' => "m",
+        'ن' => "n",
+        'و' => "w",
+        'ي' | 'ی' => "y",
+        'پ' => "p",
+        'چ' => "ch",
+        'ژ' => "zh",
+        'گ' => "g",
+        _ => return None,
+    })
+}
+
+/// Hebrew consonants, including final forms; niqqud are dropped as combining marks
+fn hebrew(c: char) -> Option<&'static str> {
+    Some(match c {
+        'א' | 'ע' => "",
+        'ב' => "b",
+        'ג' => "g",
+        'ד' => "d",
+        'ה' => "h",
+        'ו' => "v",
+        'ז' => "z",
+        'ח' => "ch",
+        'ט' | 'ת' => "t",
+        'י' => "y",
+        'כ' | 'ך' | 'ק' => "k",
+        'ל' => "l",
+        'מ' | 'ם' => "m",
+        'נ' | 'ן' => "n",
+        'ס' => "s",
+        'פ' | 'ף' => "p",
+        'צ' | 'ץ' => "ts",
+        'ר' => "r",
+        'ש' => "sh",
+        _ => return None,
+    })
+}
+
+/// Hepburn romanization of a hiragana syllable
+fn hiragana(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "wi",
+        'ゑ' => "we",
+        'を' => "wo",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+/// Small ya/yu/yo that combine with the preceding i-row syllable
+fn small_y_vowel(c: char) -> Option<char> {
+    match to_hiragana(c) {
+        'ゃ' => Some('a'),
+        'ゅ' => Some('u'),
+        'ょ' => Some('o'),
+        _ => None,
+    }
+}
+
+/// Romanize a run of kana, handling digraphs, the sokuon and long vowel marks
+fn push_kana(out: &mut String, kana: &[char]) {
+    let mut i = 0;
+    let mut double_next = false;
+
+    while i < kana.len() {
+        let c = to_hiragana(kana[i]);
+
+        if c == 'っ' {
+            double_next = true;
+            i += 1;
+            continue;
+        }
+        if c == 'ー' {
+            // Repeat the previous vowel
+            if let Some(vowel) = out.chars().last().filter(|v| "aiueo".contains(*v)) {
+                out.push(vowel);
+            }
+            i += 1;
+            continue;
+        }
+
+        let mut syllable = match hiragana(c) {
+            Some(romaji) => romaji.to_string(),
+            None => {
+                out.push(kana[i]);
+                i += 1;
+                continue;
+            }
+        };
+        i += 1;
+
+        // きゃ -> kya, しゃ -> sha, じゃ -> ja
+        if let Some(vowel) = kana.get(i).and_then(|&next| small_y_vowel(next)) {
+            if syllable.len() > 1 && syllable.ends_with('i') {
+                syllable.pop();
+                if !(syllable.ends_with('h') || syllable == "j") {
+                    syllable.push('y');
+                }
+                syllable.push(vowel);
+                i += 1;
+            }
+        }
+
+        if double_next {
+            // っち -> tchi, っか -> kka
+            if syllable.starts_with("ch") {
+                out.push('t');
+            } else if let Some(first) = syllable.chars().next().filter(|c| !"aiueon".contains(*c)) {
+                out.push(first);
+            }
+            double_next = false;
+        }
+        out.push_str(&syllable);
+    }
+}
+
+/// Push a mapped letter, preserving the case of the source letter
+fn push_cased(out: &mut String, mapped: &str, upper: bool, next_upper: bool) {
+    if !upper {
+        out.push_str(mapped);
+    } else if next_upper {
+        out.push_str(&mapped.to_ascii_uppercase());
+    } else {
+        let mut chars = mapped.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+}
+
+fn lookup(c: char) -> Option<&'static str> {
+    latin(c)
+        .or_else(|| cyrillic(c))
+        .or_else(|| greek(c))
+        .or_else(|| arabic(c))
+        .or_else(|| hebrew(c))
+}
+
+/// Transliterate text to ASCII where a mapping is known
+///
+/// Letters keep their case (`Ж` becomes `Zh`, `ЖУК` becomes `ZHUK`);
+/// characters from scripts without a mapping are kept unchanged.
+pub fn transliterate(text: &str) -> String {
+    let chars: Vec<char> = text.nfc().collect();
+    let mut out = String::with_capacity(chars.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_ascii() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        if is_kana(c) {
+            let start = i;
+            while i < chars.len() && is_kana(chars[i]) {
+                i += 1;
+            }
+            push_kana(&mut out, &chars[start..i]);
+            continue;
+        }
+
+        if is_combining_mark(c) {
+            // Vowel points and other marks that did not compose
+            i += 1;
+            continue;
+        }
+
+        let upper = c.is_uppercase();
+        let next_upper = chars.get(i + 1).is_some_and(|n| n.is_uppercase());
+        let lower = c.to_lowercase().next().unwrap_or(c);
+
+        // Letters such as й or ё are mapped before decomposition would
+        // strip the mark that distinguishes them
+        if let Some(mapped) = lookup(lower) {
+            push_cased(&mut out, mapped, upper, next_upper);
+        } else {
+            let bases: Vec<char> = lower.nfd().filter(|&d| !is_combining_mark(d)).collect();
+            if bases
+                .iter()
+                .any(|&base| base.is_ascii() || lookup(base).is_some())
+            {
+                for base in bases {
+                    match lookup(base) {
+                        Some(mapped) => push_cased(&mut out, mapped, upper, next_upper),
+                        None if base.is_ascii() && upper => out.push(base.to_ascii_uppercase()),
+                        None => out.push(base),
+                    }
+                }
+            } else {
+                // Nothing to transliterate, such as Hangul syllables or CJK ideographs
+                out.push(c);
+            }
+        }
+        i += 1;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latin() {
+        assert_eq!(transliterate("Crème Brûlée"), "Creme Brulee");
+        assert_eq!(transliterate("Straße"), "Strasse");
+        assert_eq!(transliterate("Łódź"), "Lodz");
+        assert_eq!(transliterate("Øresund Æble"), "Oresund Aeble");
+    }
+
+    #[test]
+    fn test_cyrillic() {
+        assert_eq!(transliterate("Москва"), "Moskva");
+        assert_eq!(transliterate("Щука ЖУК"), "Shchuka ZHUK");
+        assert_eq!(transliterate("Йошкар-Ола"), "Yoshkar-Ola");
+        assert_eq!(transliterate("Їжак"), "Yizhak");
+        assert_eq!(transliterate("Београд"), "Beograd");
+    }
+
+    #[test]
+    fn test_greek() {
+        assert_eq!(transliterate("Αθήνα"), "Athina");
+        assert_eq!(transliterate("ψυχή"), "psychi");
+    }
+
+    #[test]
+    fn test_arabic_and_hebrew() {
+        assert_eq!(transliterate("مُحَمَّد"), "mhmd");
+        assert_eq!(transliterate("شكرا"), "shkra");
+        assert_eq!(transliterate("שָׁלוֹם"), "shlvm");
+    }
+
+    #[test]
+    fn test_kana() {
+        assert_eq!(transliterate("すし"), "sushi");
+        assert_eq!(transliterate("トウキョウ"), "toukyou");
+        assert_eq!(transliterate("しゃしん"), "shashin");
+        assert_eq!(transliterate("きっぷ"), "kippu");
+        assert_eq!(transliterate("マッチ"), "matchi");
+        assert_eq!(transliterate("コーヒー"), "koohii");
+        assert_eq!(transliterate("じゃ"), "ja");
+    }
+
+    #[test]
+    fn test_passthrough() {
+        assert_eq!(transliterate("hello, world"), "hello, world");
+        assert_eq!(transliterate("東京"), "東京");
+        assert_eq!(transliterate(""), "");
+    }
+
+    #[test]
+    fn test_hangul_and_cjk_passthrough() {
+        // Hangul syllables decompose into several jamo but are kept as one character
+        assert_eq!(transliterate("한국"), "한국");
+        assert_eq!(transliterate("한국 ǅ"), "한국 ǅ");
+        assert_eq!(transliterate("서울 Café"), "서울 Cafe");
+        assert_eq!(transliterate("中文 한국어"), "中文 한국어");
+        assert_eq!(transliterate("㈜北京"), "㈜北京");
+    }
+}
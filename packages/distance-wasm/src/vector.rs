@@ -0,0 +1,183 @@
+// Dense vector kernels - similarity of embeddings stored as f32 arrays
+// Matrices are flat row-major arrays whose row length is the vector dimension,
+// matching how Float32Array batches arrive from JavaScript
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Vector comparison function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorMetric {
+    /// Cosine similarity, 0 when either vector is all zeros
+    Cosine,
+    /// Dot product
+    Dot,
+    /// Euclidean (L2) distance
+    Euclidean,
+    /// Manhattan (L1) distance
+    Manhattan,
+}
+
+impl VectorMetric {
+    pub fn parse(name: &str) -> Option<VectorMetric> {
+        match name.to_lowercase().as_str() {
+            "cosine" => Some(VectorMetric::Cosine),
+            "dot" | "dot_product" | "inner_product" | "ip" => Some(VectorMetric::Dot),
+            "euclidean" | "l2" => Some(VectorMetric::Euclidean),
+            "manhattan" | "l1" | "cityblock" => Some(VectorMetric::Manhattan),
+            _ => None,
+        }
+    }
+
+    /// Compare two vectors of equal length
+    pub fn score(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            VectorMetric::Cosine => cosine(a, b),
+            VectorMetric::Dot => dot(a, b),
+            VectorMetric::Euclidean => euclidean(a, b),
+            VectorMetric::Manhattan => manhattan(a, b),
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    crate::simd::dot(a, b)
+}
+
+#[cfg(not(feature = "simd"))]
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let norms = (dot(a, a) * dot(b, b)).sqrt();
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot(a, b) / norms
+    }
+}
+
+pub fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
+
+pub fn manhattan(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
+
+fn check_dimension(len: usize, dim: usize, what: &str) -> Result<(), String> {
+    if dim == 0 || !len.is_multiple_of(dim) {
+        Err(format!(
+            "{} has length {}, not a multiple of the dimension {}",
+            what, len, dim
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Compare two vectors, which must have the same length
+pub fn pairwise(a: &[f32], b: &[f32], metric: VectorMetric) -> Result<f32, String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "vectors have different lengths {} and {}",
+            a.len(),
+            b.len()
+        ));
+    }
+    Ok(metric.score(a, b))
+}
+
+/// Compare a query vector with every row of a matrix
+pub fn one_to_many(
+    query: &[f32],
+    matrix: &[f32],
+    metric: VectorMetric,
+) -> Result<Vec<f32>, String> {
+    check_dimension(matrix.len(), query.len(), "matrix")?;
+    Ok(matrix
+        .chunks_exact(query.len())
+        .map(|row| metric.score(query, row))
+        .collect())
+}
+
+/// Compare every row of `queries` with every row of `choices`, row-major by query
+pub fn cdist(
+    queries: &[f32],
+    choices: &[f32],
+    dim: usize,
+    metric: VectorMetric,
+) -> Result<Vec<f32>, String> {
+    check_dimension(queries.len(), dim, "queries")?;
+    check_dimension(choices.len(), dim, "choices")?;
+    let row = |query: &[f32]| -> Vec<f32> {
+        choices
+            .chunks_exact(dim)
+            .map(|choice| metric.score(query, choice))
+            .collect()
+    };
+
+    #[cfg(feature = "parallel")]
+    let rows: Vec<Vec<f32>> = queries.par_chunks_exact(dim).map(row).collect();
+    #[cfg(not(feature = "parallel"))]
+    let rows: Vec<Vec<f32>> = queries.chunks_exact(dim).map(row).collect();
+
+    Ok(rows.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: [f32; 3] = [1.0, 2.0, 2.0];
+    const B: [f32; 3] = [2.0, 0.0, 1.0];
+
+    #[test]
+    fn test_kernels() {
+        assert_eq!(dot(&A, &B), 4.0);
+        assert!((cosine(&A, &B) - 4.0 / (3.0 * 5f32.sqrt())).abs() < 1e-6);
+        assert_eq!(euclidean(&A, &B), 6f32.sqrt());
+        assert_eq!(manhattan(&A, &B), 4.0);
+        assert_eq!(cosine(&A, &[0.0; 3]), 0.0);
+        assert!((cosine(&A, &A) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pairwise_lengths() {
+        assert!(pairwise(&A, &B, VectorMetric::Dot).is_ok());
+        assert!(pairwise(&A, &B[..2], VectorMetric::Dot).is_err());
+    }
+
+    #[test]
+    fn test_one_to_many() {
+        let matrix: Vec<f32> = A.iter().chain(B.iter()).copied().collect();
+        assert_eq!(
+            one_to_many(&A, &matrix, VectorMetric::Dot).unwrap(),
+            vec![9.0, 4.0]
+        );
+        assert!(one_to_many(&A, &matrix[..5], VectorMetric::Dot).is_err());
+    }
+
+    #[test]
+    fn test_cdist() {
+        let queries: Vec<f32> = A.iter().chain(B.iter()).copied().collect();
+        let choices: Vec<f32> = B.to_vec();
+        assert_eq!(
+            cdist(&queries, &choices, 3, VectorMetric::Manhattan).unwrap(),
+            vec![4.0, 0.0]
+        );
+        assert!(cdist(&queries, &choices, 0, VectorMetric::Manhattan).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(VectorMetric::parse("L2"), Some(VectorMetric::Euclidean));
+        assert_eq!(VectorMetric::parse("hamming"), None);
+    }
+}
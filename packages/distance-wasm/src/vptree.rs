@@ -0,0 +1,274 @@
+// Vantage-point tree - nearest neighbour search under float-valued metrics
+// Each node splits the remaining items at the median distance to its vantage
+// point, so the triangle inequality prunes whole halves during a search
+
+use std::cmp::Ordering;
+
+/// Normalized distances in [0, 1] satisfying the triangle inequality
+///
+/// Plain normalized Levenshtein (`d / max(|a|, |b|)`) is excluded as it
+/// violates the triangle inequality ("ab", "aba", "ba").
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatMetric {
+    /// Yujian-Bo normalized Levenshtein, `2d / (|a| + |b| + d)`
+    YujianBo,
+    /// One minus the Jaccard similarity of the character multisets
+    Jaccard,
+    /// One minus the Jaccard similarity of the character bigram multisets
+    JaccardBigram,
+}
+
+impl FloatMetric {
+    pub fn parse(name: &str) -> Option<FloatMetric> {
+        match name.to_lowercase().as_str() {
+            "yujian_bo" | "yujian-bo" | "normalized_levenshtein" | "normalized-levenshtein" => {
+                Some(FloatMetric::YujianBo)
+            }
+            "jaccard" => Some(FloatMetric::Jaccard),
+            "jaccard_bigram" | "jaccard-bigram" => Some(FloatMetric::JaccardBigram),
+            _ => None,
+        }
+    }
+
+    pub fn distance(self, a: &str, b: &str) -> f64 {
+        match self {
+            FloatMetric::YujianBo => textdistance::nstr::yujian_bo(a, b),
+            FloatMetric::Jaccard => 1.0 - textdistance::nstr::jaccard(a, b),
+            FloatMetric::JaccardBigram => 1.0 - crate::bigram_jaccard(a, b),
+        }
+    }
+}
+
+struct Node {
+    item: u32,
+    /// Median distance from the vantage point to the items below it
+    threshold: f64,
+    /// Items at most `threshold` away
+    inside: Option<usize>,
+    /// Items at least `threshold` away
+    outside: Option<usize>,
+}
+
+/// Static VP-tree over a list of strings, identified by their position
+pub struct VpTree {
+    metric: FloatMetric,
+    items: Vec<String>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+fn by_distance(a: &(u32, f64), b: &(u32, f64)) -> Ordering {
+    a.1.total_cmp(&b.1).then(a.0.cmp(&b.0))
+}
+
+impl VpTree {
+    pub fn new(items: Vec<String>, metric: FloatMetric) -> VpTree {
+        let mut tree = VpTree {
+            metric,
+            items,
+            nodes: Vec::new(),
+            root: None,
+        };
+        let mut ids: Vec<u32> = (0..tree.items.len() as u32).collect();
+        tree.root = tree.build(&mut ids);
+        tree
+    }
+
+    fn build(&mut self, ids: &mut [u32]) -> Option<usize> {
+        let (&vantage, rest) = ids.split_first()?;
+
+        let mut points: Vec<(u32, f64)> = rest
+            .iter()
+            .map(|&id| {
+                let d = self
+                    .metric
+                    .distance(&self.items[vantage as usize], &self.items[id as usize]);
+                (id, d)
+            })
+            .collect();
+
+        let median = points.len() / 2;
+        let threshold = if points.is_empty() {
+            0.0
+        } else {
+            points.select_nth_unstable_by(median, by_distance);
+            points[median].1
+        };
+
+        let mut inside: Vec<u32> = points[..median].iter().map(|&(id, _)| id).collect();
+        let mut outside: Vec<u32> = points[median..].iter().map(|&(id, _)| id).collect();
+        let inside = self.build(&mut inside);
+        let outside = self.build(&mut outside);
+
+        self.nodes.push(Node {
+            item: vantage,
+            threshold,
+            inside,
+            outside,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&str> {
+        self.items.get(id as usize).map(String::as_str)
+    }
+
+    /// Every item within `radius` of the query as `(id, distance)`, closest first
+    pub fn within(&self, query: &str, radius: f64) -> Vec<(u32, f64)> {
+        let mut matches = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let d = self.metric.distance(query, &self.items[node.item as usize]);
+            if d <= radius {
+                matches.push((node.item, d));
+            }
+            if let Some(inside) = node.inside.filter(|_| d - radius <= node.threshold) {
+                stack.push(inside);
+            }
+            if let Some(outside) = node.outside.filter(|_| d + radius >= node.threshold) {
+                stack.push(outside);
+            }
+        }
+
+        matches.sort_unstable_by(by_distance);
+        matches
+    }
+
+    /// The `n` items closest to the query as `(id, distance)`, closest first
+    pub fn nearest(&self, query: &str, n: usize) -> Vec<(u32, f64)> {
+        let mut best: Vec<(u32, f64)> = Vec::with_capacity(n + 1);
+        if n > 0 {
+            if let Some(root) = self.root {
+                self.search_nearest(root, query, n, &mut best);
+            }
+        }
+        best
+    }
+
+    fn search_nearest(&self, index: usize, query: &str, n: usize, best: &mut Vec<(u32, f64)>) {
+        let node = &self.nodes[index];
+        let d = self.metric.distance(query, &self.items[node.item as usize]);
+
+        let candidate = (node.item, d);
+        let position = best
+            .binary_search_by(|probe| by_distance(probe, &candidate))
+            .unwrap_or_else(|p| p);
+        if position < n {
+            best.insert(position, candidate);
+            best.truncate(n);
+        }
+
+        let radius = |best: &Vec<(u32, f64)>| {
+            if best.len() < n {
+                f64::INFINITY
+            } else {
+                best[n - 1].1
+            }
+        };
+
+        // Visit the side containing the query first to shrink the radius sooner
+        let (first, second) = if d <= node.threshold {
+            (node.inside, node.outside)
+        } else {
+            (node.outside, node.inside)
+        };
+        for child in [first, second].iter().flatten() {
+            let r = radius(best);
+            let reachable = if Some(*child) == node.inside {
+                d - r <= node.threshold
+            } else {
+                d + r >= node.threshold
+            };
+            if reachable {
+                self.search_nearest(*child, query, n, best);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 12] = [
+        "kitten", "sitting", "mitten", "smitten", "bitten", "kitchen", "chicken", "thicken",
+        "written", "", "kit", "knitting",
+    ];
+
+    fn tree(metric: FloatMetric) -> VpTree {
+        VpTree::new(WORDS.iter().map(|w| w.to_string()).collect(), metric)
+    }
+
+    fn brute_force(metric: FloatMetric, query: &str) -> Vec<(u32, f64)> {
+        let mut all: Vec<(u32, f64)> = WORDS
+            .iter()
+            .enumerate()
+            .map(|(id, w)| (id as u32, metric.distance(query, w)))
+            .collect();
+        all.sort_unstable_by(by_distance);
+        all
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(FloatMetric::YujianBo.distance("abc", "abc"), 0.0);
+        // Levenshtein 1 between lengths 3 and 4
+        assert!((FloatMetric::YujianBo.distance("abc", "abcd") - 2.0 / 8.0).abs() < 1e-12);
+        assert_eq!(FloatMetric::Jaccard.distance("ab", "cd"), 1.0);
+        assert_eq!(FloatMetric::parse("levenshtein"), None);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let metrics = [
+            FloatMetric::YujianBo,
+            FloatMetric::Jaccard,
+            FloatMetric::JaccardBigram,
+        ];
+        for &metric in metrics.iter() {
+            let tree = tree(metric);
+            for query in ["kitten", "sittin", "", "xyz", "chicken soup"].iter() {
+                let expected = brute_force(metric, query);
+                for &radius in [0.0, 0.2, 0.5, 1.0].iter() {
+                    let within: Vec<_> = expected
+                        .iter()
+                        .copied()
+                        .filter(|&(_, d)| d <= radius)
+                        .collect();
+                    assert_eq!(tree.within(query, radius), within, "{:?} {}", metric, query);
+                }
+                for n in 0..=WORDS.len() + 1 {
+                    let nearest = &expected[..n.min(expected.len())];
+                    assert_eq!(
+                        tree.nearest(query, n),
+                        nearest,
+                        "{:?} {} {}",
+                        metric,
+                        query,
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest() {
+        let tree = tree(FloatMetric::YujianBo);
+        let nearest = tree.nearest("kitten", 3);
+        let terms: Vec<&str> = nearest
+            .iter()
+            .map(|&(id, _)| tree.get(id).unwrap())
+            .collect();
+        assert_eq!(terms, vec!["kitten", "mitten", "bitten"]);
+        assert!(VpTree::new(Vec::new(), FloatMetric::Jaccard)
+            .nearest("a", 3)
+            .is_empty());
+    }
+}
@@ -6,6 +6,8 @@
 #![cfg(test)]
 
 use distance_wasm::*;
+#[cfg(feature = "simd")]
+use std::hint::black_box;
 use std::time::Instant;
 
 // Test data — identical to the TS benchmark
@@ -55,11 +57,11 @@ const CORRECTNESS_CASES: &[(&str, &str)] = &[
 
 const ITERATIONS: u64 = 1000;
 
-fn bench_distance(label: &str, test_pairs: &[(&str, &str)], f: fn(&str, &str) -> u32) {
+fn bench_distance(label: &str, test_pairs: &[(&str, &str)], f: fn(&str, &str, Option<String>) -> u32) {
     let start = Instant::now();
     for _ in 0..ITERATIONS {
         for &(s1, s2) in test_pairs {
-            let _ = f(s1, s2);
+            let _ = f(s1, s2, None);
         }
     }
     let total = ITERATIONS as f64 * test_pairs.len() as f64;
@@ -67,11 +69,11 @@ fn bench_distance(label: &str, test_pairs: &[(&str, &str)], f: fn(&str, &str) ->
     println!("  {:<30} | {:>10.2} us/op", label, avg_us);
 }
 
-fn bench_similarity(label: &str, test_pairs: &[(&str, &str)], f: fn(&str, &str) -> f64) {
+fn bench_similarity(label: &str, test_pairs: &[(&str, &str)], f: fn(&str, &str, Option<String>) -> f64) {
     let start = Instant::now();
     for _ in 0..ITERATIONS {
         for &(s1, s2) in test_pairs {
-            let _ = f(s1, s2);
+            let _ = f(s1, s2, None);
         }
     }
     let total = ITERATIONS as f64 * test_pairs.len() as f64;
@@ -263,6 +265,64 @@ fn bench_bigram() {
     bench_similarity("cosine_bigram (medium)", MEDIUM_STRINGS, cosine_bigram);
 }
 
+// ============================================================================
+// SIMD kernels
+// ============================================================================
+#[cfg(feature = "simd")]
+fn bench_kernel<T>(label: &str, iterations: u64, mut f: impl FnMut() -> T) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    let avg_us = start.elapsed().as_micros() as f64 / iterations as f64;
+    println!("  {:<30} | {:>10.2} us/op", label, avg_us);
+}
+
+// Native timings of the scalar and lane loops; simd128 only exists in wasm
+// builds, which `test:simd` checks for correctness rather than speed
+#[cfg(feature = "simd")]
+#[test]
+fn bench_simd() {
+    println!("\n=== SIMD kernels (native scalar vs lane loops) ===");
+
+    let (long_a, long_b) = LONG_STRINGS[0];
+    let long_a = long_a.repeat(4);
+    let long_b = long_b.repeat(4);
+    bench_kernel("myers blocks (scalar)", ITERATIONS, || {
+        simd::myers_distance_scalar(black_box(&long_a), black_box(&long_b))
+    });
+    bench_kernel("myers blocks (lanes)", ITERATIONS, || {
+        simd::myers_distance_lanes(black_box(&long_a), black_box(&long_b))
+    });
+    assert_eq!(
+        simd::myers_distance_scalar(&long_a, &long_b),
+        simd::myers_distance_lanes(&long_a, &long_b)
+    );
+
+    let keys_a = simd::bigram_keys(&long_a);
+    let keys_b = simd::bigram_keys(&long_b);
+    bench_kernel("q-gram intersection (scalar)", ITERATIONS * 10, || {
+        simd::intersection_count_scalar(black_box(&keys_a), black_box(&keys_b))
+    });
+    bench_kernel("q-gram intersection (lanes)", ITERATIONS * 10, || {
+        simd::intersection_count_lanes(black_box(&keys_a), black_box(&keys_b))
+    });
+    assert_eq!(
+        simd::intersection_count_scalar(&keys_a, &keys_b),
+        simd::intersection_count_lanes(&keys_a, &keys_b)
+    );
+
+    let x: Vec<f32> = (0..384).map(|i| (i as f32 * 0.37).sin()).collect();
+    let y: Vec<f32> = (0..384).map(|i| (i as f32 * 0.11).cos()).collect();
+    bench_kernel("dot 384 (scalar)", ITERATIONS * 100, || {
+        simd::dot_scalar(black_box(&x), black_box(&y))
+    });
+    bench_kernel("dot 384 (lanes)", ITERATIONS * 100, || {
+        simd::dot_lanes(black_box(&x), black_box(&y))
+    });
+    assert!((simd::dot_scalar(&x, &y) - simd::dot_lanes(&x, &y)).abs() < 1e-3);
+}
+
 // ============================================================================
 // Correctness
 // ============================================================================
@@ -272,35 +332,35 @@ fn test_correctness() {
 
     for &(s1, s2) in CORRECTNESS_CASES {
         // Distance (exact integer match)
-        assert_eq_u32("levenshtein", levenshtein(s1, s2), levenshtein(s1, s2));
-        assert_eq_u32("damerau_levenshtein", damerau_levenshtein(s1, s2), damerau_levenshtein(s1, s2));
-        assert_eq_u32("hamming", hamming(s1, s2), hamming(s1, s2));
-        assert_eq_u32("sift4_simple", sift4_simple(s1, s2), sift4_simple(s1, s2));
-        assert_eq_u32("lcs_seq", lcs_seq(s1, s2), lcs_seq(s1, s2));
-        assert_eq_u32("lcs_str", lcs_str(s1, s2), lcs_str(s1, s2));
-        assert_eq_u32("smith_waterman", smith_waterman(s1, s2), smith_waterman(s1, s2));
+        assert_eq_u32("levenshtein", levenshtein(s1, s2, None), levenshtein(s1, s2, None));
+        assert_eq_u32("damerau_levenshtein", damerau_levenshtein(s1, s2, None), damerau_levenshtein(s1, s2, None));
+        assert_eq_u32("hamming", hamming(s1, s2, None), hamming(s1, s2, None));
+        assert_eq_u32("sift4_simple", sift4_simple(s1, s2, None), sift4_simple(s1, s2, None));
+        assert_eq_u32("lcs_seq", lcs_seq(s1, s2, None), lcs_seq(s1, s2, None));
+        assert_eq_u32("lcs_str", lcs_str(s1, s2, None), lcs_str(s1, s2, None));
+        assert_eq_u32("smith_waterman", smith_waterman(s1, s2, None), smith_waterman(s1, s2, None));
 
         // Similarity (tolerance 0.001)
-        assert_close("levenshtein_normalized", levenshtein_normalized(s1, s2), 1.0 - levenshtein_normalized(s1, s2), 0.001);
-        assert_close("damerau_levenshtein_normalized", damerau_levenshtein_normalized(s1, s2), 1.0 - damerau_levenshtein_normalized(s1, s2), 0.001);
-        assert_close("jaro", jaro(s1, s2), jaro(s1, s2), 0.001);
-        assert_close("jaroWinkler", jarowinkler(s1, s2), jarowinkler(s1, s2), 0.001);
-        assert_close("hamming_normalized", hamming_normalized(s1, s2), 1.0 - hamming_normalized(s1, s2), 0.001);
-        assert_close("sift4_simple_normalized", sift4_simple_normalized(s1, s2), 1.0 - sift4_simple_normalized(s1, s2), 0.001);
-        assert_close("lcs_seq_normalized", lcs_seq_normalized(s1, s2), lcs_seq_normalized(s1, s2), 0.001);
-        assert_close("lcs_str_normalized", lcs_str_normalized(s1, s2), lcs_str_normalized(s1, s2), 0.001);
-        assert_close("ratcliff_obershelp", ratcliff_obershelp(s1, s2), ratcliff_obershelp(s1, s2), 0.001);
-        assert_close("smith_waterman_normalized", smith_waterman_normalized(s1, s2), smith_waterman_normalized(s1, s2), 0.001);
-        assert_close("jaccard", jaccard(s1, s2), jaccard(s1, s2), 0.001);
-        assert_close("cosine", cosine(s1, s2), cosine(s1, s2), 0.001);
-        assert_close("sorensen", sorensen(s1, s2), sorensen(s1, s2), 0.001);
-        assert_close("tversky", tversky(s1, s2), tversky(s1, s2), 0.001);
-        assert_close("overlap", overlap(s1, s2), overlap(s1, s2), 0.001);
-        assert_close("prefix", prefix(s1, s2), prefix(s1, s2), 0.001);
-        assert_close("suffix", suffix(s1, s2), suffix(s1, s2), 0.001);
-        assert_close("length", length(s1, s2), 1.0 - length(s1, s2), 0.001);
-        assert_close("jaccard_bigram", jaccard_bigram(s1, s2), jaccard_bigram(s1, s2), 0.001);
-        assert_close("cosine_bigram", cosine_bigram(s1, s2), cosine_bigram(s1, s2), 0.001);
+        assert_close("levenshtein_normalized", levenshtein_normalized(s1, s2, None), 1.0 - levenshtein_normalized(s1, s2, None), 0.001);
+        assert_close("damerau_levenshtein_normalized", damerau_levenshtein_normalized(s1, s2, None), 1.0 - damerau_levenshtein_normalized(s1, s2, None), 0.001);
+        assert_close("jaro", jaro(s1, s2, None), jaro(s1, s2, None), 0.001);
+        assert_close("jaroWinkler", jarowinkler(s1, s2, None), jarowinkler(s1, s2, None), 0.001);
+        assert_close("hamming_normalized", hamming_normalized(s1, s2, None), 1.0 - hamming_normalized(s1, s2, None), 0.001);
+        assert_close("sift4_simple_normalized", sift4_simple_normalized(s1, s2, None), 1.0 - sift4_simple_normalized(s1, s2, None), 0.001);
+        assert_close("lcs_seq_normalized", lcs_seq_normalized(s1, s2, None), lcs_seq_normalized(s1, s2, None), 0.001);
+        assert_close("lcs_str_normalized", lcs_str_normalized(s1, s2, None), lcs_str_normalized(s1, s2, None), 0.001);
+        assert_close("ratcliff_obershelp", ratcliff_obershelp(s1, s2, None), ratcliff_obershelp(s1, s2, None), 0.001);
+        assert_close("smith_waterman_normalized", smith_waterman_normalized(s1, s2, None), smith_waterman_normalized(s1, s2, None), 0.001);
+        assert_close("jaccard", jaccard(s1, s2, None), jaccard(s1, s2, None), 0.001);
+        assert_close("cosine", cosine(s1, s2, None), cosine(s1, s2, None), 0.001);
+        assert_close("sorensen", sorensen(s1, s2, None), sorensen(s1, s2, None), 0.001);
+        assert_close("tversky", tversky(s1, s2, None), tversky(s1, s2, None), 0.001);
+        assert_close("overlap", overlap(s1, s2, None), overlap(s1, s2, None), 0.001);
+        assert_close("prefix", prefix(s1, s2, None), prefix(s1, s2, None), 0.001);
+        assert_close("suffix", suffix(s1, s2, None), suffix(s1, s2, None), 0.001);
+        assert_close("length", length(s1, s2, None), 1.0 - length(s1, s2, None), 0.001);
+        assert_close("jaccard_bigram", jaccard_bigram(s1, s2, None), jaccard_bigram(s1, s2, None), 0.001);
+        assert_close("cosine_bigram", cosine_bigram(s1, s2, None), cosine_bigram(s1, s2, None), 0.001);
     }
 
     println!("  All {} cases passed", CORRECTNESS_CASES.len());
@@ -0,0 +1,292 @@
+/*
+ * C ABI smoke test - exercises the scorers, error reporting and index handles
+ * through include/nlptools_distance.h. Build and run with `pnpm test:ffi`
+ */
+
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "nlptools_distance.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                    \
+  do {                                                                 \
+    if (!(cond)) {                                                     \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+              #cond);                                                  \
+      failures++;                                                      \
+    }                                                                  \
+  } while (0)
+
+#define STR(s) (s), strlen(s)
+
+static void test_scorers(void) {
+  uint32_t distance = 0;
+  double score = 0.0;
+
+  CHECK(nlp_levenshtein(STR("kitten"), STR("sitting"), NULL, 0, &distance) ==
+        NLP_STATUS_OK);
+  CHECK(distance == 3);
+
+  CHECK(nlp_jarowinkler(STR("MARTHA"), STR("MARHTA"), NULL, 0, &score) ==
+        NLP_STATUS_OK);
+  CHECK(fabs(score - 0.9611111) < 1e-6);
+
+  /* Preprocessing folds case before scoring */
+  CHECK(nlp_ratio(STR("New York"), STR("new york"), STR("case_fold"),
+                  &score) == NLP_STATUS_OK);
+  CHECK(score == 1.0);
+
+  CHECK(nlp_compare(STR("hello"), STR("hello"), STR("jaro"), NULL, 0,
+                    &score) == NLP_STATUS_OK);
+  CHECK(score == 1.0);
+
+  NlpStr words[] = {{STR("apple")}, {STR("banana")}};
+  double scores[4];
+  size_t count = 0;
+  CHECK(nlp_cdist(words, 2, words, 2, STR("ratio"), NULL, 0, scores, 3,
+                  &count) == NLP_STATUS_BUFFER_TOO_SMALL);
+  CHECK(count == 4);
+  CHECK(nlp_cdist(words, 2, words, 2, STR("ratio"), NULL, 0, scores, 4,
+                  &count) == NLP_STATUS_OK);
+  CHECK(scores[0] == 1.0 && scores[3] == 1.0 && scores[1] < 1.0);
+}
+
+static void test_errors(void) {
+  double score = 0.0;
+  size_t len = 0;
+
+  CHECK(nlp_ratio("\xff\xfe", 2, STR("a"), NULL, 0, &score) ==
+        NLP_STATUS_INVALID_UTF8);
+  CHECK(nlp_ratio(NULL, 3, STR("a"), NULL, 0, &score) ==
+        NLP_STATUS_NULL_POINTER);
+  const char *message = nlp_last_error(&len);
+  CHECK(strcmp(message, "null string with non-zero length") == 0);
+  CHECK(len == strlen(message));
+  CHECK(nlp_last_error(NULL) == message);
+
+  NlpHnswIndex *index = NULL;
+  CHECK(nlp_hnsw_new(4, STR("hamming"), 0, 0, 0, &index) ==
+        NLP_STATUS_INVALID_ARGUMENT);
+  CHECK(index == NULL);
+}
+
+static void test_bm25(void) {
+  NlpBm25Index *index = NULL;
+  const char *documents[] = {"the quick brown fox", "lazy dogs sleep",
+                             "quick quick fox"};
+  uint32_t id = 0;
+  NlpHit hits[4];
+  size_t count = 0;
+
+  CHECK(nlp_bm25_new(NULL, 0, NAN, NAN, NAN, NULL, 0, 0, 0, NULL, 0, &index) ==
+        NLP_STATUS_OK);
+  for (int i = 0; i < 3; i++) {
+    CHECK(nlp_bm25_add(index, STR(documents[i]), &id) == NLP_STATUS_OK);
+  }
+  CHECK(nlp_bm25_query(index, STR("quick fox"), 10, hits, 4, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 2 && hits[0].id == 2 && hits[0].score > hits[1].score);
+
+  /* A short buffer still reports the full count */
+  CHECK(nlp_bm25_query(index, STR("quick fox"), 10, hits, 1, &count) ==
+        NLP_STATUS_BUFFER_TOO_SMALL);
+  CHECK(count == 2);
+  nlp_bm25_free(index);
+}
+
+static void test_tfidf(void) {
+  NlpStr corpus[] = {{STR("the quick brown fox")},
+                     {STR("the lazy dog")},
+                     {STR("quick thinking")}};
+  NlpTfIdfModel *model = NULL;
+  NlpSoftTfIdf *soft = NULL;
+  NlpHit hits[2];
+  size_t count = 0;
+  size_t len = 0;
+  double score = 0.0;
+  double restored_score = 0.0;
+
+  CHECK(nlp_tfidf_new(corpus, 3, NULL, 0, 0, 0, false, true, NULL, 0,
+                      &model) == NLP_STATUS_OK);
+  CHECK(nlp_tfidf_similarity(model, STR("quick fox"), STR("the quick brown fox"),
+                             &score) == NLP_STATUS_OK);
+  CHECK(score > 0.0 && score < 1.0);
+  CHECK(nlp_tfidf_vectorize(model, STR("quick fox"), hits, 2, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 2);
+
+  /* Size the buffer with a first call, then round-trip the model */
+  CHECK(nlp_tfidf_serialize(model, NULL, 0, &len) ==
+        NLP_STATUS_BUFFER_TOO_SMALL);
+  char *json = malloc(len);
+  CHECK(nlp_tfidf_serialize(model, json, len, &len) == NLP_STATUS_OK);
+  nlp_tfidf_free(model);
+  CHECK(nlp_tfidf_deserialize(json, len, &model) == NLP_STATUS_OK);
+  free(json);
+  CHECK(nlp_tfidf_similarity(model, STR("quick fox"), STR("the quick brown fox"),
+                             &restored_score) == NLP_STATUS_OK);
+  CHECK(restored_score == score);
+  nlp_tfidf_free(model);
+
+  CHECK(nlp_soft_tfidf_new(corpus, 3, NULL, 0, NAN, NULL, 0, &soft) ==
+        NLP_STATUS_OK);
+  CHECK(nlp_soft_tfidf_similarity(soft, STR("quick fox"), STR("quikc fox"),
+                                  &score) == NLP_STATUS_OK);
+  CHECK(score > 0.9);
+  nlp_soft_tfidf_free(soft);
+}
+
+static void test_ngram(void) {
+  NlpNgramIndex *index = NULL;
+  const char *terms[] = {"color", "colour", "collar"};
+  uint32_t id = 0;
+  NlpHit hits[3];
+  size_t count = 0;
+
+  CHECK(nlp_ngram_new(0, &index) == NLP_STATUS_OK);
+  for (int i = 0; i < 3; i++) {
+    CHECK(nlp_ngram_insert(index, STR(terms[i]), &id) == NLP_STATUS_OK);
+  }
+  CHECK(nlp_ngram_search(index, STR("colr"), 1, hits, 3, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 1 && hits[0].id == 0 && hits[0].score == 1.0);
+  nlp_ngram_free(index);
+}
+
+static void test_terms(void) {
+  NlpStr terms[] = {{STR("book")}, {STR("books")}, {STR("cake")}, {STR("boo")}};
+  NlpBkTree *tree = NULL;
+  NlpTermDictionary *dictionary = NULL;
+  NlpTermMatch matches[4];
+  size_t count = 0;
+  bool inserted = false;
+
+  CHECK(nlp_bktree_new(NULL, 0, &tree) == NLP_STATUS_OK);
+  for (int i = 0; i < 4; i++) {
+    CHECK(nlp_bktree_insert(tree, terms[i].ptr, terms[i].len, &inserted) ==
+              NLP_STATUS_OK &&
+          inserted);
+  }
+  CHECK(nlp_bktree_within(tree, STR("bok"), 1, matches, 4, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 2 && matches[0].distance == 1);
+  CHECK(strncmp(matches[0].term.ptr, "boo", matches[0].term.len) == 0);
+  nlp_bktree_free(tree);
+
+  CHECK(nlp_termdict_new(terms, 4, &dictionary) == NLP_STATUS_OK);
+  CHECK(nlp_termdict_fuzzy_search(dictionary, STR("bok"), 1, false, matches, 4,
+                                  &count) == NLP_STATUS_OK);
+  CHECK(count == 2 && matches[1].term.len == 4 &&
+        strncmp(matches[1].term.ptr, "book", 4) == 0);
+  nlp_termdict_free(dictionary);
+}
+
+static void test_symspell(void) {
+  NlpSymSpell *index = NULL;
+  NlpSuggestion suggestions[2];
+  NlpSegmentation segmentation;
+  size_t count = 0;
+  uint32_t loaded = 0;
+
+  CHECK(nlp_symspell_new(0, 0, &index) == NLP_STATUS_OK);
+  CHECK(nlp_symspell_load_dictionary(
+            index, STR("the 500\nquick 100\nbrown 80\nfox 60\n"), &loaded) ==
+        NLP_STATUS_OK);
+  CHECK(loaded == 4);
+  CHECK(nlp_symspell_lookup(index, STR("quikc"), NULL, 0, -1, suggestions, 2,
+                            &count) == NLP_STATUS_OK);
+  CHECK(count == 1 && suggestions[0].distance == 1 &&
+        strncmp(suggestions[0].term.ptr, "quick", 5) == 0);
+  CHECK(nlp_symspell_word_segmentation(index, STR("thequickbrwnfox"), 1,
+                                       &segmentation) == NLP_STATUS_OK);
+  CHECK(segmentation.corrected.len == 19 &&
+        strncmp(segmentation.corrected.ptr, "the quick brown fox", 19) == 0);
+  nlp_symspell_free(index);
+}
+
+static void test_vptree(void) {
+  NlpStr items[] = {{STR("apple")}, {STR("apply")}, {STR("banana")}};
+  NlpVpTree *tree = NULL;
+  NlpHit hits[3];
+  size_t count = 0;
+
+  CHECK(nlp_vptree_new(items, 3, NULL, 0, &tree) == NLP_STATUS_OK);
+  CHECK(nlp_vptree_nearest(tree, STR("appel"), 2, hits, 3, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 2 && hits[0].id <= 1 && hits[1].id <= 1);
+  nlp_vptree_free(tree);
+}
+
+static void test_hnsw(void) {
+  NlpHnswIndex *index = NULL;
+  float vectors[3][2] = {{1.0f, 0.0f}, {0.0f, 1.0f}, {0.7f, 0.7f}};
+  float query[2] = {1.0f, 0.1f};
+  NlpHit hits[2];
+  size_t count = 0;
+  size_t len = 0;
+
+  CHECK(nlp_hnsw_new(2, NULL, 0, 0, 0, 0, &index) == NLP_STATUS_OK);
+  for (uint32_t id = 0; id < 3; id++) {
+    CHECK(nlp_hnsw_insert(index, id, vectors[id], 2) == NLP_STATUS_OK);
+  }
+
+  /* Size the buffer with a first call, then round-trip the index */
+  CHECK(nlp_hnsw_serialize(index, NULL, 0, &len) ==
+        NLP_STATUS_BUFFER_TOO_SMALL);
+  uint8_t *buffer = malloc(len);
+  CHECK(nlp_hnsw_serialize(index, buffer, len, &len) == NLP_STATUS_OK);
+  nlp_hnsw_free(index);
+
+  NlpHnswIndex *restored = NULL;
+  CHECK(nlp_hnsw_deserialize(buffer, len, &restored) == NLP_STATUS_OK);
+  free(buffer);
+  CHECK(nlp_hnsw_search(restored, query, 2, 2, hits, 2, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 2 && hits[0].id == 0 && hits[1].id == 2);
+  nlp_hnsw_free(restored);
+}
+
+static void test_quantized(void) {
+  NlpQuantizedStore *store = NULL;
+  float vectors[3][4] = {
+      {1.0f, 0.0f, 0.0f, 0.0f},
+      {0.0f, 1.0f, 0.0f, 0.0f},
+      {0.0f, 0.0f, 1.0f, 0.5f},
+  };
+  NlpHit hits[1];
+  size_t count = 0;
+  bool deleted = false;
+
+  CHECK(nlp_quantized_new(4, STR("l2"), &store) == NLP_STATUS_OK);
+  for (uint32_t id = 0; id < 3; id++) {
+    CHECK(nlp_quantized_insert(store, id, vectors[id], 4) == NLP_STATUS_OK);
+  }
+  CHECK(nlp_quantized_search(store, vectors[2], 4, 1, 0, hits, 1, &count) ==
+        NLP_STATUS_OK);
+  CHECK(count == 1 && hits[0].id == 2);
+  CHECK(nlp_quantized_delete(store, 2, &deleted) == NLP_STATUS_OK && deleted);
+  nlp_quantized_free(store);
+}
+
+int main(void) {
+  test_scorers();
+  test_errors();
+  test_tfidf();
+  test_bm25();
+  test_ngram();
+  test_terms();
+  test_vptree();
+  test_hnsw();
+  test_quantized();
+  test_symspell();
+
+  if (failures > 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return 1;
+  }
+  printf("ffi: all checks passed\n");
+  return 0;
+}
@@ -0,0 +1,144 @@
+// Binding parity suite - runs the same checks against the native addon
+// (distance.node) and the wasm package built for Node.js (pkg/node), so both
+// builds are held to one set of expectations. Set DISTANCE_BINDINGS to "addon"
+// or "wasm" to test one of them. Build and run with `pnpm test:node`
+
+const assert = require("node:assert/strict");
+const path = require("node:path");
+const { describe, test } = require("node:test");
+
+const root = path.join(__dirname, "..", "..");
+const bindings = {
+  addon: () => require(path.join(root, "distance.node")),
+  wasm: () => require(path.join(root, "pkg", "node", "distance_wasm.js")),
+};
+
+const close = (actual, expected) =>
+  assert.ok(Math.abs(actual - expected) < 1e-9, `${actual} != ${expected}`);
+
+// wasm returns typed arrays and class instances where the addon returns plain
+// arrays and objects, so compare through these
+const list = (values) => Array.from(values);
+const pick = (items, ...keys) =>
+  Array.from(items, (item) => Object.fromEntries(keys.map((key) => [key, item[key]])));
+
+const selected = (process.env.DISTANCE_BINDINGS || "addon,wasm").split(",");
+
+for (const name of selected) {
+  describe(name, () => {
+    const lib = bindings[name]();
+
+    test("scorers", () => {
+      assert.equal(lib.levenshtein("kitten", "sitting"), 3);
+      assert.equal(lib.levenshtein("café", "café", "nfc"), 0);
+      close(lib.jaro("hello", "hallo"), 0.8666666666666667);
+      assert.equal(lib.compare("kitten", "sitting", "levenshtein"), 3 / 7);
+      assert.equal(lib.partial_ratio_alignment("this is a test", "is a").score, 1);
+      assert.equal(lib.transliterate("Москва"), "Moskva");
+      assert.equal(lib.to_pinyin("北京", "tone"), "bei3 jing1");
+      assert.throws(() => lib.to_pinyin("北京", "tonal"), /unsupported pinyin style/);
+      lib.validate_preprocessor("nfkc,case_fold");
+      assert.throws(() => lib.validate_preprocessor("lowercase"), /unknown preprocessing option/);
+    });
+
+    test("batch", () => {
+      const items = ["apple", "banana", "apply", "Apple"];
+      assert.equal(lib.cdist(["apple"], items, "levenshtein").length, 4);
+      assert.deepEqual(
+        lib.extract("apple", items, "levenshtein", 2).map((hit) => hit.id),
+        [0, 2],
+      );
+      assert.deepEqual(list(lib.dedupe(items, "levenshtein", 0.8, "case_fold")), [0, 1, 0, 0]);
+      assert.throws(
+        () => lib.cdist(["apple"], items, "ratio", "lowercase"),
+        /unknown preprocessing option/,
+      );
+    });
+
+    test("vectors", () => {
+      const a = new Float32Array([1, 0]);
+      close(lib.vector_cosine(a, a), 1);
+      assert.throws(() => lib.vector_cosine(a, new Float32Array([1])));
+    });
+
+    test("corpus models", () => {
+      const corpus = ["acme inc", "globex inc", "initech inc"];
+      const tfidf = new lib.TfIdfModel(corpus);
+      assert.equal(tfidf.vocabulary_size, 4);
+      assert.equal(tfidf.feature_names().length, 4);
+      close(tfidf.similarity("acme inc", "acme inc"), 1);
+      const restored = lib.TfIdfModel.deserialize(tfidf.serialize());
+      assert.deepEqual(
+        list(restored.vectorize("acme inc").values),
+        list(tfidf.vectorize("acme inc").values),
+      );
+      assert.throws(() => lib.TfIdfModel.deserialize("{"));
+      assert.throws(
+        () => new lib.TfIdfModel(corpus, undefined, undefined, undefined, undefined, undefined, "lowercase"),
+        /unknown preprocessing option/,
+      );
+
+      const soft = new lib.SoftTfIdf(corpus);
+      assert.ok(soft.similarity("acme inc", "acmee inc") > 0.9);
+
+      const bm25 = new lib.Bm25Index("bm25+");
+      corpus.forEach((doc) => bm25.add(doc));
+      assert.equal(bm25.size, 3);
+      assert.equal(bm25.query("globex", 1)[0].id, 1);
+      assert.ok(bm25.remove(1));
+      assert.equal(bm25.query("globex").length, 0);
+    });
+
+    test("string indexes", () => {
+      const ngram = new lib.NgramIndex(2);
+      ["apple", "apply", "banana"].forEach((text) => ngram.insert(text));
+      assert.deepEqual(pick(ngram.search("appel", 2), "id", "distance"), [
+        { id: 0, distance: 2 },
+        { id: 1, distance: 2 },
+      ]);
+      assert.equal(ngram.get(2), "banana");
+
+      const bk = new lib.BkTree();
+      ["book", "books", "cake"].forEach((term) => bk.insert(term));
+      assert.deepEqual(pick(bk.within("boo", 1), "term", "distance"), [
+        { term: "book", distance: 1 },
+      ]);
+      assert.throws(() => new lib.BkTree("cosine"));
+
+      const dictionary = new lib.TermDictionary(["book", "books", "cake"]);
+      assert.ok(dictionary.contains("cake"));
+      assert.deepEqual(dictionary.fuzzy_search("bok", 1).map((m) => m.term), ["book"]);
+
+      const vp = new lib.VpTree(["apple", "apply", "banana"]);
+      assert.equal(vp.size, 3);
+      assert.equal(vp.nearest("appel", 1)[0].term.startsWith("appl"), true);
+
+      const spell = new lib.SymSpell();
+      spell.load_dictionary("hello 10\nworld 5");
+      assert.equal(spell.lookup("helo")[0].term, "hello");
+      assert.throws(() => spell.lookup("helo", "best"), /unsupported verbosity/);
+      assert.equal(spell.word_segmentation("helloworld").segmented, "hello world");
+    });
+
+    test("vector indexes", () => {
+      const hnsw = new lib.HnswIndex(2, "l2");
+      hnsw.insert(1, new Float32Array([0, 1]));
+      hnsw.insert(2, new Float32Array([1, 0]));
+      assert.equal(hnsw.search(new Float32Array([0.9, 0.1]), 1)[0].id, 2);
+      assert.deepEqual(hnsw.search(new Float32Array([0.9, 0.1]), 1, [1]).map((hit) => hit.id), [1]);
+      hnsw.ef_search = 8;
+      assert.equal(hnsw.ef_search, 8);
+      assert.equal(lib.HnswIndex.deserialize(hnsw.serialize()).size, 2);
+      assert.throws(() => hnsw.insert(3, new Float32Array([1])));
+
+      const store = new lib.QuantizedStore(2, "cosine");
+      store.insert(7, new Float32Array([1, 0]));
+      store.insert(8, new Float32Array([0, 1]));
+      assert.equal(store.search(new Float32Array([1, 0.1]), 1)[0].id, 7);
+      assert.equal(store.search_binary(new Float32Array([0.1, 1]), 1)[0].id, 8);
+      assert.equal(store.get(7).length, 2);
+      // Missing vectors are null from the addon and undefined from wasm
+      assert.equal(store.get(9) ?? null, null);
+    });
+  });
+}
@@ -0,0 +1,64 @@
+//! The simd128 kernels checked against their scalar versions in a wasm runtime.
+//!
+//! Run with: pnpm --filter @nlptools/distance-wasm test:simd
+
+#![cfg(all(target_arch = "wasm32", feature = "simd"))]
+
+use distance_wasm::simd::*;
+use wasm_bindgen_test::*;
+
+fn text(seed: u64, len: usize, alphabet: &[char]) -> String {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            alphabet[(state % alphabet.len() as u64) as usize]
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn built_with_simd128() {
+    assert!(cfg!(all(feature = "simd", target_feature = "simd128")));
+}
+
+#[wasm_bindgen_test]
+fn dot_lanes_match_scalar() {
+    for n in 0..19 {
+        let a: Vec<f32> = (0..n).map(|i| i as f32 * 0.5).collect();
+        let b: Vec<f32> = (0..n).map(|i| (i % 3) as f32 - 1.0).collect();
+        assert_eq!(dot_lanes(&a, &b), dot_scalar(&a, &b), "n = {}", n);
+        assert_eq!(dot(&a, &b), dot_lanes(&a, &b));
+    }
+    assert_eq!(dot_lanes(&[1.0, 2.0, 3.0, 4.0, 5.0], &[1.0; 3]), 6.0);
+}
+
+#[wasm_bindgen_test]
+fn block_matches_count_every_lane() {
+    let a: Vec<u64> = (0..40).map(|i| i * 3).collect();
+    let b: Vec<u64> = (0..50).map(|i| i * 2).collect();
+    assert_eq!(intersection_count_lanes(&a, &b), 17);
+    assert_eq!(intersection_count_lanes(&b, &a), 17);
+    // Identical and disjoint blocks exercise all four lanes and none of them
+    assert_eq!(intersection_count_lanes(&a, &a), a.len());
+    let odd: Vec<u64> = (0..40).map(|i| i * 2 + 1).collect();
+    assert_eq!(intersection_count_lanes(&b, &odd), 0);
+    // Keys above u32::MAX must compare all 64 bits
+    let high: Vec<u64> = (0..8).map(|i| (i << 40) | 7).collect();
+    let low: Vec<u64> = (0..8).map(|i| (i << 20) | 7).collect();
+    assert_eq!(intersection_count_lanes(&high, &low), 1);
+    assert_eq!(intersection_count(&high, &high), 8);
+}
+
+#[wasm_bindgen_test]
+fn myers_lanes_match_scalar() {
+    let alphabet = ['a', 'b', 'c', 'd', 'é', '語'];
+    for seed in 1..40u64 {
+        let a = text(seed, 20 + (seed as usize * 37) % 300, &alphabet);
+        let b = text(seed * 7919, 30 + (seed as usize * 53) % 400, &alphabet);
+        let expected = myers_distance_scalar(&a, &b);
+        assert_eq!(myers_distance_lanes(&a, &b), expected, "seed {}", seed);
+    }
+}